@@ -1,8 +1,10 @@
 use crate::expander::ast::{Node, expression::Expression};
+use crate::span::Span;
 
 pub struct ArrayAccessExpression {
     parent: Box<Expression>,
     index: Box<Expression>,
+    span: Span,
 }
 
 impl ArrayAccessExpression {
@@ -15,6 +17,18 @@ impl ArrayAccessExpression {
     }
 
     pub fn new(parent: Box<Expression>, index: Box<Expression>) -> Self {
-        Self { index, parent }
+        Self {
+            index,
+            parent,
+            span: Span::default(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
 }
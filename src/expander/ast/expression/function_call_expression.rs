@@ -1,8 +1,10 @@
 use crate::expander::ast::{Node, expression::Expression};
+use crate::span::Span;
 
 pub struct FunctionCallExpression {
     function_identifier: Box<Expression>,
     args: Vec<Box<Expression>>,
+    span: Span,
 }
 
 impl FunctionCallExpression {
@@ -22,10 +24,28 @@ impl FunctionCallExpression {
         Self {
             function_identifier: identifier,
             args: vec![],
+            span: Span::default(),
         }
     }
 
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+
     pub fn add_arg(&mut self, arg: Box<Expression>) {
         self.args.push(arg);
     }
+
+    /// `name` returns the name of the called function when it is called by name (eg. `toc` in
+    /// `toc()`, but not `user.greet` in `user.greet()`).
+    pub fn name(&self) -> Option<&str> {
+        match self.function_identifier.as_ref() {
+            Expression::VariableAccess(variable) => Some(&variable.variable_name),
+            _ => None,
+        }
+    }
 }
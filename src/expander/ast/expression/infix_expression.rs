@@ -1,9 +1,11 @@
 use super::{super::operators::Op, Expression, Node};
+use crate::span::Span;
 
 pub struct InfixExpression {
     pub left: Box<Expression>,
     pub right: Box<Expression>,
     pub operator: Op,
+    span: Span,
 }
 
 impl InfixExpression {
@@ -21,6 +23,15 @@ impl InfixExpression {
             left,
             right,
             operator,
+            span: Span::default(),
         }
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
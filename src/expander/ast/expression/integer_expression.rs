@@ -1,5 +1,8 @@
+use crate::span::Span;
+
 pub struct IntegerExpression {
     pub value: i64,
+    span: Span,
 }
 
 impl IntegerExpression {
@@ -8,6 +11,17 @@ impl IntegerExpression {
     }
 
     pub fn new(value: i64) -> Self {
-        Self { value }
+        Self {
+            value,
+            span: Span::default(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
 }
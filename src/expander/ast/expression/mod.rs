@@ -10,8 +10,10 @@ pub mod string_expression;
 pub mod variable_access_expression;
 
 pub use super::operators::{Comparators, Math, Op};
+use crate::expander::builtins;
 use crate::expander::environment::Environment;
 pub use crate::expander::object::{FALSE, NONE, Object, TRUE};
+use crate::span::Span;
 pub use array_access_expression::*;
 pub use function_call_expression::*;
 pub use infix_expression::*;
@@ -30,8 +32,8 @@ pub enum Expression {
     FunctionCall(FunctionCallExpression),
     Integer(IntegerExpression),
     String(StringExpression),
-    True,
-    False,
+    True(Span),
+    False(Span),
     Empty,
 }
 
@@ -46,19 +48,56 @@ impl Node for Expression {
             Self::FunctionCall(i) => i.literal(),
             Self::Integer(i) => i.literal(),
             Self::String(i) => i.literal(),
-            Self::True => "true".to_string(),
-            Self::False => "false".to_string(),
+            Self::True(_) => "true".to_string(),
+            Self::False(_) => "false".to_string(),
             Self::Empty => "EMPTY".to_string(),
         }
     }
 
     fn translate(&self, env: &mut Environment) -> String {
+        if let Self::FunctionCall(call) = self
+            && let Some(markdown) = builtins::expand(call)
+        {
+            return markdown;
+        }
+
         self.evaluate(env).inspect()
     }
 
     fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
         self
     }
+
+    /// an `Empty` expression isn't in the source, so it has no span.
+    fn span(&self) -> Span {
+        match self {
+            Self::OperatorInfix(i) => i.span(),
+            Self::Prefix(i) => i.span(),
+            Self::VariableAccess(i) => i.span(),
+            Self::ObjectAccess(i) => i.span(),
+            Self::ArrayAccess(i) => i.span(),
+            Self::FunctionCall(i) => i.span(),
+            Self::Integer(i) => i.span(),
+            Self::String(i) => i.span(),
+            Self::True(span) | Self::False(span) => *span,
+            Self::Empty => Span::default(),
+        }
+    }
+
+    fn set_span(&mut self, span: Span) {
+        match self {
+            Self::OperatorInfix(i) => i.set_span(span),
+            Self::Prefix(i) => i.set_span(span),
+            Self::VariableAccess(i) => i.set_span(span),
+            Self::ObjectAccess(i) => i.set_span(span),
+            Self::ArrayAccess(i) => i.set_span(span),
+            Self::FunctionCall(i) => i.set_span(span),
+            Self::Integer(i) => i.set_span(span),
+            Self::String(i) => i.set_span(span),
+            Self::True(own) | Self::False(own) => *own = span,
+            Self::Empty => {}
+        }
+    }
 }
 
 fn evaluate_prefix_expressions(
@@ -168,8 +207,8 @@ impl Expression {
                 evaluate_infix_expression(infix_expression, env)
             }
             Self::Integer(integer_expression) => Object::Integer(integer_expression.value),
-            Self::True => TRUE,
-            Self::False => FALSE,
+            Self::True(_) => TRUE,
+            Self::False(_) => FALSE,
             Self::VariableAccess(variable_access_expression) => {
                 evaluate_variable_access_expression(variable_access_expression, env)
             }
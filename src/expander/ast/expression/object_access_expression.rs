@@ -1,8 +1,10 @@
 use crate::expander::ast::{Node, expression::Expression};
+use crate::span::Span;
 
 pub struct ObjectAccessExpression {
     parent: Box<Expression>,
     child: Box<Expression>,
+    span: Span,
 }
 
 impl ObjectAccessExpression {
@@ -17,6 +19,15 @@ impl ObjectAccessExpression {
         Self {
             parent: parent,
             child: child,
+            span: Span::default(),
         }
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
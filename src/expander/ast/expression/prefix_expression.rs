@@ -1,8 +1,10 @@
 use crate::expander::ast::{Node, expression::Expression, operators::Op};
+use crate::span::Span;
 
 pub struct PrefixExpression {
     pub operator: Op,
     pub right: Box<Expression>,
+    span: Span,
 }
 
 impl PrefixExpression {
@@ -18,9 +20,18 @@ impl PrefixExpression {
         Self {
             operator: op,
             right,
+            span: Span::default(),
         }
     }
 
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+
     fn evaluate(&self) -> isize {
         // match self.operator {
         //     Op::Math(math_operator) => {
@@ -1,5 +1,8 @@
+use crate::span::Span;
+
 pub struct StringExpression {
     value: String,
+    span: Span,
 }
 
 impl StringExpression {
@@ -8,6 +11,17 @@ impl StringExpression {
     }
 
     pub fn new(value: String) -> Self {
-        Self { value }
+        Self {
+            value,
+            span: Span::default(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
 }
@@ -1,5 +1,8 @@
+use crate::span::Span;
+
 pub struct VariableAccessExpression {
     pub variable_name: String,
+    span: Span,
 }
 
 impl VariableAccessExpression {
@@ -13,6 +16,15 @@ impl VariableAccessExpression {
     pub fn new(variable_name: String) -> Self {
         VariableAccessExpression {
             variable_name: variable_name,
+            span: Span::default(),
         }
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
@@ -1,3 +1,4 @@
+use crate::span::Span;
 use std::any::Any;
 
 use super::{
@@ -11,6 +12,7 @@ pub struct IfBlock {
     expression: Expression,
     valid: Vec<Box<MarcNode>>,
     invalid: Vec<Box<MarcNode>>,
+    span: Span,
 }
 
 impl IfBlock {
@@ -19,6 +21,7 @@ impl IfBlock {
             expression,
             valid: vec![],
             invalid: vec![],
+            span: Span::default(),
         }
     }
 
@@ -65,12 +68,21 @@ impl Node for IfBlock {
     fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
 
 pub struct ForBlock {
     main_list: Expression,
     variable: VariableAccessExpression,
     operations: Vec<Box<MarcNode>>,
+    span: Span,
 }
 
 impl ForBlock {
@@ -79,6 +91,7 @@ impl ForBlock {
             main_list: list,
             variable,
             operations: vec![],
+            span: Span::default(),
         }
     }
 
@@ -114,15 +127,27 @@ impl Node for ForBlock {
     fn as_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
 
 pub struct BlockBlock {
     children: Vec<Box<MarcNode>>,
+    span: Span,
 }
 
 impl BlockBlock {
     pub fn new() -> BlockBlock {
-        BlockBlock { children: vec![] }
+        BlockBlock {
+            children: vec![],
+            span: Span::default(),
+        }
     }
 
     pub fn add_child(&mut self, node: Box<MarcNode>) {
@@ -152,4 +177,12 @@ impl Node for BlockBlock {
     fn as_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
@@ -1,6 +1,7 @@
 use std::any::Any;
 
 use crate::expander::environment::Environment;
+use crate::span::Span;
 
 pub mod expression;
 pub mod marcblocks;
@@ -12,6 +13,10 @@ pub trait Node: Any {
     fn token_literal(&self) -> String;
     fn translate(&self, env: &mut Environment) -> String;
     fn as_any(self: Box<Self>) -> Box<dyn Any>;
+
+    /// `span` returns where the node is in the source of the document.
+    fn span(&self) -> Span;
+    fn set_span(&mut self, span: Span);
 }
 
 pub enum MarcNode {
@@ -20,7 +25,9 @@ pub enum MarcNode {
     Import(statement::ImportStatement),
     BlockBlock(marcblocks::BlockBlock),
     Text(text_node::TextNode),
-    Expression(Box<expression::Expression>),
+    // expressions don't keep a span of their own (see `Expression::span`), so the node holding
+    // one keeps the span of its `{{ ... }}`
+    Expression(Box<expression::Expression>, Span),
     Statement(Box<dyn Node>),
 }
 
@@ -30,7 +37,7 @@ impl Node for MarcNode {
             MarcNode::For(b) => b.token_literal(),
             MarcNode::If(b) => b.token_literal(),
             MarcNode::Text(b) => b.token_literal(),
-            MarcNode::Expression(b) => b.token_literal(),
+            MarcNode::Expression(b, _) => b.token_literal(),
             MarcNode::Import(b) => b.token_literal(),
             MarcNode::Statement(b) => b.token_literal(),
             MarcNode::BlockBlock(b) => b.token_literal(),
@@ -42,13 +49,37 @@ impl Node for MarcNode {
             MarcNode::For(b) => b.translate(env),
             MarcNode::If(b) => b.translate(env),
             MarcNode::Text(b) => b.translate(env),
-            MarcNode::Expression(b) => b.translate(env),
+            MarcNode::Expression(b, _) => b.translate(env),
             MarcNode::Import(b) => b.translate(env),
             MarcNode::Statement(b) => b.translate(env),
             MarcNode::BlockBlock(b) => b.translate(env),
         }
     }
 
+    fn span(&self) -> Span {
+        match self {
+            MarcNode::For(b) => b.span(),
+            MarcNode::If(b) => b.span(),
+            MarcNode::Text(b) => b.span(),
+            MarcNode::Expression(_, span) => *span,
+            MarcNode::Import(b) => b.span(),
+            MarcNode::Statement(b) => b.span(),
+            MarcNode::BlockBlock(b) => b.span(),
+        }
+    }
+
+    fn set_span(&mut self, span: Span) {
+        match self {
+            MarcNode::For(b) => b.set_span(span),
+            MarcNode::If(b) => b.set_span(span),
+            MarcNode::Text(b) => b.set_span(span),
+            MarcNode::Expression(_, expression_span) => *expression_span = span,
+            MarcNode::Import(b) => b.set_span(span),
+            MarcNode::Statement(b) => b.set_span(span),
+            MarcNode::BlockBlock(b) => b.set_span(span),
+        }
+    }
+
     fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
         self
     }
@@ -56,6 +87,7 @@ impl Node for MarcNode {
 
 pub struct Document {
     nodes: Vec<Box<dyn Node>>,
+    span: Span,
 }
 
 impl Node for Document {
@@ -70,16 +102,31 @@ impl Node for Document {
     fn as_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
 
 impl Document {
     pub fn new() -> Self {
-        Self { nodes: vec![] }
+        Self {
+            nodes: vec![],
+            span: Span::default(),
+        }
     }
 
     pub fn add_node(&mut self, block: Box<dyn Node>) {
         self.nodes.push(block);
     }
+
+    pub fn nodes(&self) -> &[Box<dyn Node>] {
+        &self.nodes
+    }
 }
 
 impl Node for Vec<Box<dyn Node>> {
@@ -105,4 +152,20 @@ impl Node for Vec<Box<dyn Node>> {
     fn as_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
+
+    /// the span of a list of nodes goes from its first node to its last one. the nodes without
+    /// a span (like the empty expression a blank line of a block is parsed into) are left out.
+    fn span(&self) -> Span {
+        let mut spans = self
+            .iter()
+            .map(|node| node.span())
+            .filter(|span| *span != Span::default());
+        match (spans.next(), spans.next_back()) {
+            (Some(first), Some(last)) => first.to(last),
+            (Some(first), None) => first,
+            _ => Span::default(),
+        }
+    }
+
+    fn set_span(&mut self, _span: Span) {}
 }
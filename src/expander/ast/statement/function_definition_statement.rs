@@ -1,26 +1,35 @@
 use crate::expander::ast::MarcNode;
 use crate::expander::ast::{Node, statement::ReturnStatement};
+use crate::expander::builtins;
 use crate::expander::environment::Environment;
+use crate::span::Span;
 
 pub struct FunctionDefinitionStatement {
     name: String,
     params: Vec<String>,
     body: Vec<Box<MarcNode>>,
     return_statement: Option<ReturnStatement>,
+    span: Span,
 }
 
 impl FunctionDefinitionStatement {
+    /// `new` panics if `name` is the name of a built-in function, which can't be redefined.
     pub fn new(
         name: String,
         params: Vec<String>,
         body: Vec<Box<MarcNode>>,
         return_statement: Option<ReturnStatement>,
     ) -> Self {
+        if builtins::is_builtin(&name) {
+            panic!("`{}` is a built-in function and can't be redefined", name);
+        }
+
         Self {
             name,
             params,
             body,
             return_statement,
+            span: Span::default(),
         }
     }
 
@@ -55,4 +64,12 @@ impl Node for FunctionDefinitionStatement {
     fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
@@ -1,13 +1,19 @@
 use crate::expander::environment::Environment;
+use crate::span::Span;
 
 pub struct ImportStatement {
     src: String,
     alias: String,
+    span: Span,
 }
 
 impl ImportStatement {
     pub fn new(src: String, alias: String) -> Self {
-        ImportStatement { src, alias }
+        ImportStatement {
+            src,
+            alias,
+            span: Span::default(),
+        }
     }
 
     pub fn literal(&self) -> String {
@@ -30,4 +36,12 @@ impl crate::expander::ast::Node for ImportStatement {
     fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
@@ -4,6 +4,7 @@ pub mod return_statement;
 pub mod variable_assignment_statement;
 
 use crate::expander::{ast::Node, environment::Environment};
+use crate::span::Span;
 pub use function_definition_statement::*;
 pub use import_statement::*;
 pub use return_statement::*;
@@ -34,4 +35,22 @@ impl Node for Statement {
     fn as_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        match self {
+            Self::VariableAssignment(statement) => statement.span(),
+            Self::Import(statement) => statement.span(),
+            Self::FunctionDefinition(statement) => statement.span(),
+            Self::Return(statement) => statement.span(),
+        }
+    }
+
+    fn set_span(&mut self, span: Span) {
+        match self {
+            Self::VariableAssignment(statement) => statement.set_span(span),
+            Self::Import(statement) => statement.set_span(span),
+            Self::FunctionDefinition(statement) => statement.set_span(span),
+            Self::Return(statement) => statement.set_span(span),
+        }
+    }
 }
@@ -2,14 +2,19 @@ use crate::expander::{
     ast::{Node, expression::Expression},
     environment::Environment,
 };
+use crate::span::Span;
 
 pub struct ReturnStatement {
     value: Box<Expression>,
+    span: Span,
 }
 
 impl ReturnStatement {
     pub fn new(value: Box<Expression>) -> Self {
-        Self { value }
+        Self {
+            value,
+            span: Span::default(),
+        }
     }
 
     pub fn literal(&self) -> String {
@@ -29,4 +34,12 @@ impl Node for ReturnStatement {
     fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
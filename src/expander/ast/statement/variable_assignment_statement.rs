@@ -1,15 +1,21 @@
 use crate::expander::ast::Node;
 use crate::expander::ast::expression::Expression;
 use crate::expander::environment::Environment;
+use crate::span::Span;
 
 pub struct VariableAssignmentStatement {
     identifier: String,
     value: Box<Expression>,
+    span: Span,
 }
 
 impl VariableAssignmentStatement {
     pub fn new(identifier: String, value: Box<Expression>) -> Self {
-        Self { identifier, value }
+        Self {
+            identifier,
+            value,
+            span: Span::default(),
+        }
     }
 
     pub fn literal(&self) -> String {
@@ -36,4 +42,12 @@ impl Node for VariableAssignmentStatement {
     fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
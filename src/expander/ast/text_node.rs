@@ -1,15 +1,20 @@
 use crate::expander::environment::Environment;
+use crate::span::Span;
 
 use super::Node;
 
 #[derive(Clone)]
 pub struct TextNode {
     text: String,
+    span: Span,
 }
 
 impl TextNode {
     pub fn new(text: String) -> Self {
-        TextNode { text }
+        TextNode {
+            text,
+            span: Span::default(),
+        }
     }
 }
 
@@ -25,4 +30,12 @@ impl Node for TextNode {
     fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
         self
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
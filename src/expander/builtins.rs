@@ -0,0 +1,25 @@
+use crate::expander::ast::expression::FunctionCallExpression;
+
+/// `expand` returns the markdown that a call to one of the built-in functions expands to, or
+/// `None` if `call` isn't a call to a built-in function.
+///
+/// built-in functions hand work over to the marc parser, which knows things about the document
+/// that the expander doesn't (eg. all of its headings or tasks). their names are reserved, see
+/// [`is_builtin`].
+pub fn expand(call: &FunctionCallExpression) -> Option<String> {
+    match call.name()? {
+        // `{{ toc() }}` is the same as writing the `[[toc]]` marker
+        "toc" => Some("[[toc]]".to_string()),
+        // `{{ progress() }}` is the number of done and total tasks of the document (`3/5`)
+        "progress" => Some("[[progress]]".to_string()),
+        _ => None,
+    }
+}
+
+/// `is_builtin` reports whether `name` is the name of a built-in function.
+///
+/// built-in calls are expanded before normal evaluation, so a user-defined function with one of
+/// these names could never be called; such definitions are rejected instead.
+pub fn is_builtin(name: &str) -> bool {
+    matches!(name, "toc" | "progress")
+}
@@ -1,5 +1,8 @@
 use crate::expander::{
-    ast::expression::{ArrayAccessExpression, Expression},
+    ast::{
+        Node,
+        expression::{ArrayAccessExpression, Expression},
+    },
     parselets::InfixParselet,
     parser::Parser,
     precedence::Precedence,
@@ -25,9 +28,9 @@ impl InfixParselet for ArrayParselet {
             parser.advance_token();
         }
 
-        Box::new(Expression::ArrayAccess(ArrayAccessExpression::new(
-            left,
-            next_expression,
-        )))
+        let start = left.span();
+        let mut array_access = ArrayAccessExpression::new(left, next_expression);
+        array_access.set_span(parser.span_to_curr_token(start));
+        Box::new(Expression::ArrayAccess(array_access))
     }
 }
@@ -13,12 +13,13 @@ impl PrefixParselet for BooleanParselet {
         Precedence::VARIABLE as u8
     }
 
-    fn parse_expression(&self, _: &mut Parser, token: Token) -> Box<Expression> {
+    fn parse_expression(&self, parser: &mut Parser, token: Token) -> Box<Expression> {
 
         if token.token_type == TokenType::False || token.token_type == TokenType::True {
+            let span = parser.token_span(&token);
             let boolean_value = match token.token_type {
-                TokenType::True => Expression::True,
-                _ => Expression::False,
+                TokenType::True => Expression::True(span),
+                _ => Expression::False(span),
             };
 
             Box::new(boolean_value)
@@ -1,5 +1,5 @@
 use crate::expander::{
-    ast::expression::Expression,
+    ast::{Node, expression::Expression},
     parselets::PrefixParselet,
     parser::Parser,
     precedence::Precedence,
@@ -13,10 +13,10 @@ impl PrefixParselet for GroupedExpressionParselet {
         Precedence::PREFIX as u8
     }
 
-    fn parse_expression(&self, parser: &mut Parser, _: Token) -> Box<Expression> {
+    fn parse_expression(&self, parser: &mut Parser, token: Token) -> Box<Expression> {
         parser.advance_token(); // move the cursor past the `(` char to the next token.
 
-        let next_expression = parser.parse_expression(0);
+        let mut next_expression = parser.parse_expression(0);
 
         if parser.get_curr_token().token_type != TokenType::RightParen {
             if parser.peek_token().token_type != TokenType::RightParen {
@@ -25,6 +25,8 @@ impl PrefixParselet for GroupedExpressionParselet {
             parser.advance_token();
         }
 
+        // the parentheses are part of the grouped expression
+        next_expression.set_span(parser.span_to_curr_token(parser.token_span(&token)));
         next_expression
     }
 }
@@ -1,5 +1,8 @@
 use crate::expander::{
-    ast::expression::{Expression, FunctionCallExpression},
+    ast::{
+        Node,
+        expression::{Expression, FunctionCallExpression},
+    },
     parselets::InfixParselet,
     parser::Parser,
     precedence::Precedence,
@@ -27,7 +30,9 @@ impl InfixParselet for FunctionCallParselet {
             }
             parser.advance_token();
         }
+        let start = identifier.span();
         let mut function_call_expression = FunctionCallExpression::new(identifier);
+        function_call_expression.set_span(parser.span_to_curr_token(start));
 
         for arg in args {
             function_call_expression.add_arg(arg);
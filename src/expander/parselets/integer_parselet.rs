@@ -13,11 +13,13 @@ impl PrefixParselet for IntegerParselet {
         Precedence::VARIABLE as u8
     }
 
-    fn parse_expression(&self, _: &mut Parser, token: Token) -> Box<Expression> {
+    fn parse_expression(&self, parser: &mut Parser, token: Token) -> Box<Expression> {
         if token.token_type == TokenType::Integer {
             let integer_value = token.literal.parse::<i64>().expect("invalid integer value");
 
-            Box::new(Expression::Integer(IntegerExpression::new(integer_value)))
+            let mut integer = IntegerExpression::new(integer_value);
+            integer.set_span(parser.token_span(&token));
+            Box::new(Expression::Integer(integer))
         } else {
             panic!("expected an integer token, got {}", token.literal);
         }
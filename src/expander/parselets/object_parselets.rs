@@ -1,5 +1,8 @@
 use crate::expander::{
-    ast::expression::{Expression, ObjectAccessExpression},
+    ast::{
+        Node,
+        expression::{Expression, ObjectAccessExpression},
+    },
     parselets::InfixParselet,
     parser::Parser,
     precedence::Precedence,
@@ -19,9 +22,9 @@ impl InfixParselet for ObjectNotationParselet {
 
         let next_expression = parser.parse_expression(precedence);
 
-        Box::new(Expression::ObjectAccess(ObjectAccessExpression::new(
-            left,
-            next_expression,
-        )))
+        let start = left.span();
+        let mut object_access = ObjectAccessExpression::new(left, next_expression);
+        object_access.set_span(parser.span_to_curr_token(start));
+        Box::new(Expression::ObjectAccess(object_access))
     }
 }
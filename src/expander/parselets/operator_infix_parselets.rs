@@ -1,5 +1,6 @@
 use crate::expander::{
     ast::{
+        Node,
         expression::{Expression, InfixExpression},
         operators::Op,
     },
@@ -39,12 +40,11 @@ impl InfixParselet for OperatorInfixParselet {
 
         let precedence = self.get_precedence(token.clone());
         parser.advance_token();
+        let start = left.span();
         let next_expression = parser.parse_expression(precedence);
 
-        Box::new(Expression::OperatorInfix(InfixExpression::new(
-            left,
-            next_expression,
-            operator,
-        )))
+        let mut infix = InfixExpression::new(left, next_expression, operator);
+        infix.set_span(parser.span_to_curr_token(start));
+        Box::new(Expression::OperatorInfix(infix))
     }
 }
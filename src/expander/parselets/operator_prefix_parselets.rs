@@ -25,9 +25,8 @@ impl PrefixParselet for OperatorPrefixParselet {
         let operator = operator.unwrap();
         parser.advance_token();
         let next_expression = parser.parse_expression(self.get_precedence());
-        Box::new(Expression::Prefix(PrefixExpression::new(
-            operator,
-            next_expression,
-        )))
+        let mut prefix = PrefixExpression::new(operator, next_expression);
+        prefix.set_span(parser.span_to_curr_token(parser.token_span(&token)));
+        Box::new(Expression::Prefix(prefix))
     }
 }
@@ -19,7 +19,10 @@ impl PrefixParselet for StringParselet {
             panic!("expected a string quote token, got {}", token.literal);
         }
 
+        let start = parser.token_span(&token);
         let string_value = parser.parse_quoted_string(token.token_type);
-        Box::new(Expression::String(StringExpression::new(string_value)))
+        let mut string = StringExpression::new(string_value);
+        string.set_span(parser.span_to_curr_token(start));
+        Box::new(Expression::String(string))
     }
 }
@@ -13,12 +13,12 @@ impl PrefixParselet for VariableAccessParselet {
         Precedence::VARIABLE as u8
     }
 
-    fn parse_expression(&self, _: &mut Parser, token: Token) -> Box<Expression> {
+    fn parse_expression(&self, parser: &mut Parser, token: Token) -> Box<Expression> {
         println!("parsing a variable with token {token:?}");
         if token.token_type == TokenType::Identifier {
-            Box::new(Expression::VariableAccess(VariableAccessExpression::new(
-                token.literal,
-            )))
+            let mut variable = VariableAccessExpression::new(token.literal.clone());
+            variable.set_span(parser.token_span(&token));
+            Box::new(Expression::VariableAccess(variable))
         } else {
             panic!("expected an identifier token, got {}", token.literal);
         }
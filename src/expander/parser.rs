@@ -20,16 +20,24 @@ use crate::expander::{
     },
     token::{Token, TokenType as TT},
 };
+use crate::span::{LineIndex, Span};
 use std::collections::HashMap;
 use std::panic;
 
 pub struct Parser {
+    prev_token: Token,
     curr_token: Token,
     next_token: Token,
     lexer: Lexer,
     prefix_parselets: HashMap<TT, &'static dyn PrefixParselet>,
     infix_parselets: HashMap<TT, &'static dyn InfixParselet>,
     in_programming_context: bool,
+    // the source being parsed, which the spans of the nodes are positions in
+    source: LineIndex,
+    // the document the source is a part of (eg. the text of a statement, which its expression
+    // is parsed out of) and where the source starts in it. the spans are then positions in the
+    // document.
+    document: Option<(LineIndex, usize)>,
 }
 
 static VARIABLE_ACCESS_PARSELET: VariableAccessParselet = VariableAccessParselet;
@@ -61,13 +69,17 @@ impl Parser {
     }
 
     pub fn new(lexer: Lexer) -> Self {
+        let source = LineIndex::new(&lexer.src);
         let mut parser = Parser {
+            prev_token: Token::new(TT::EOF, String::new(), 0, 0),
             curr_token: Token::new(TT::EOF, String::new(), 0, 0),
             next_token: Token::new(TT::EOF, String::new(), 0, 0),
             lexer,
             prefix_parselets: HashMap::new(),
             infix_parselets: HashMap::new(),
             in_programming_context: false,
+            source,
+            document: None,
         };
 
         parser.advance_token();
@@ -105,7 +117,7 @@ impl Parser {
     }
 
     pub fn advance_token(&mut self) {
-        self.curr_token = self.next_token.clone();
+        self.prev_token = std::mem::replace(&mut self.curr_token, self.next_token.clone());
         self.next_token = self.lexer.next_token();
     }
 
@@ -114,7 +126,8 @@ impl Parser {
             return self.parse_block_node();
         }
 
-        let marcnode = match self.curr_token.token_type {
+        let start = self.curr_token.clone();
+        let mut marcnode = match self.curr_token.token_type {
             TT::Text | TT::NewLine => {
                 MarcNode::Text(TextNode::new(self.curr_token.literal.clone()))
             }
@@ -141,21 +154,56 @@ impl Parser {
                         let block_block = self.parse_block_block();
                         MarcNode::BlockBlock(block_block)
                     }
-                    _ => MarcNode::Expression(Box::new(Expression::Empty)),
+                    _ => MarcNode::Expression(Box::new(Expression::Empty), Span::default()),
                 }
             }
             TT::LeftDoubleBrace => {
                 self.advance_token();
-                MarcNode::Expression(self.parse_expression(0))
+                let expression = self.parse_expression(0);
+                // the closing `}}` is part of the node, so it is in its span
+                if self.next_token.token_type == TT::RightDoubleBrace {
+                    self.advance_token();
+                }
+                MarcNode::Expression(expression, Span::default())
+            }
+            TT::True => {
+                let span = self.token_span(&start);
+                MarcNode::Expression(Box::new(Expression::True(span)), Span::default())
+            }
+            TT::False => {
+                let span = self.token_span(&start);
+                MarcNode::Expression(Box::new(Expression::False(span)), Span::default())
             }
-            TT::True => MarcNode::Expression(Box::new(Expression::True)),
-            TT::False => MarcNode::Expression(Box::new(Expression::False)),
-            _ => MarcNode::Expression(Box::new(Expression::Empty)),
+            _ => MarcNode::Expression(Box::new(Expression::Empty), Span::default()),
         };
         self.advance_token();
+        marcnode.set_span(self.span_from(&start));
         Box::new(marcnode)
     }
 
+    /// `token_span` returns the span of `token` in the source.
+    pub fn token_span(&self, token: &Token) -> Span {
+        let start = self.source.offset(token.start_line, token.start_col);
+        let end = start + token.literal.len();
+        match &self.document {
+            Some((document, offset)) => document.span(offset + start, offset + end),
+            None => self.source.span(start, end),
+        }
+    }
+
+    /// `span_to_curr_token` returns the span from `start` to the end of curr_token. a parselet
+    /// leaves curr_token on the last token of the expression it parses, so it is the span of
+    /// the expression that starts at `start`.
+    pub fn span_to_curr_token(&self, start: Span) -> Span {
+        start.to(self.token_span(&self.curr_token))
+    }
+
+    /// `span_from` returns the span of the node that starts with `start` and ends with the token
+    /// before curr_token (the node's tokens have all been read).
+    fn span_from(&self, start: &Token) -> Span {
+        self.token_span(start).to(self.token_span(&self.prev_token))
+    }
+
     pub fn get_precedence(&self, token: &Token) -> u8 {
         match self.infix_parselets.get(&token.token_type) {
             Some(parselet) => parselet.get_precedence(token.clone()),
@@ -420,14 +468,15 @@ impl Parser {
     fn parse_block_node(&mut self) -> Box<MarcNode> {
         let res = match self.curr_token.token_type {
             TT::Text | TT::NewLine => {
-                let text = self.curr_token.literal.clone();
-                let node = self.parse_text_as_node(&text);
+                let token = self.curr_token.clone();
+                let mut node = self.parse_text_as_node(&token.literal);
                 self.advance_token();
+                node.set_span(self.span_from(&token));
                 node
             }
             TT::LeftDoubleBrace | TT::KeywordStart | TT::At => self.parse(),
             _ => {
-                let node = MarcNode::Expression(Box::new(Expression::Empty));
+                let node = MarcNode::Expression(Box::new(Expression::Empty), Span::default());
                 self.advance_token();
                 Box::new(node)
             }
@@ -438,7 +487,10 @@ impl Parser {
     fn parse_text_as_node(&self, text: &str) -> Box<MarcNode> {
         let trimmed = text.trim();
         if trimmed.is_empty() {
-            return Box::new(MarcNode::Expression(Box::new(Expression::Empty)));
+            return Box::new(MarcNode::Expression(
+                Box::new(Expression::Empty),
+                Span::default(),
+            ));
         }
 
         if let Some(statement) = self.try_parse_assignment_from_text(trimmed) {
@@ -451,7 +503,7 @@ impl Parser {
 
         if self.is_expression_candidate(trimmed) {
             if let Some(expr) = self.try_parse_expression_from_str(trimmed) {
-                return Box::new(MarcNode::Expression(expr));
+                return Box::new(MarcNode::Expression(expr, Span::default()));
             }
         }
 
@@ -470,6 +522,8 @@ impl Parser {
         })
     }
 
+    /// `try_parse_expression_from_str` parses `text`, the end of the literal of curr_token, as an
+    /// expression, whose spans are then positions in the document.
     fn try_parse_expression_from_str(&self, text: &str) -> Option<Box<Expression>> {
         /*
          * TODO: This is an interesting situation. I had to do let expr = format!("{text}\n"),
@@ -481,10 +535,18 @@ impl Parser {
          * doesn't resolve the underlying issue)
          */
         let expr = format!("{text}\n");
+        let literal = self.curr_token.literal.trim_end();
+        let start =
+            self.token_span(&self.curr_token).start + literal.len().saturating_sub(text.len());
+        let document = match &self.document {
+            Some((document, _)) => document.clone(),
+            None => self.source.clone(),
+        };
         let result = panic::catch_unwind(|| {
             let mut lexer = Lexer::from(expr.as_str());
             lexer.set_detailed(true);
             let mut parser = Parser::new(lexer);
+            parser.document = Some((document, start));
             parser.parse_expression(0)
         });
 
@@ -573,6 +635,7 @@ impl Parser {
 
     pub fn parse_document(&mut self) -> Document {
         let mut program = Document::new();
+        program.set_span(self.source.span(0, self.lexer.src.len()));
 
         while self.curr_token.token_type != TT::EOF {
             let node = self.parse();
@@ -1,5 +1,6 @@
 pub mod expander;
 pub mod marc;
+pub mod span;
 mod utils;
 
 pub use utils::*;
@@ -1,6 +1,9 @@
-use md_to_html::marc::{ast::Node, lexer::Lexer, parser::Parser, token::TokenType};
+use md_to_html::marc::{
+    ast::Node, lexer::Lexer, options::RenderOptions, parser::Parser, token::TokenType,
+};
 
 use std::env;
+use std::path::Path;
 
 fn main() {
     let args: Vec<_> = env::args().collect();
@@ -23,8 +26,16 @@ fn main() {
     let mut p = Parser::new(l);
 
     let program = p.parse_program();
+    for warning in p.warnings() {
+        eprintln!("warning: {}", warning);
+    }
 
-    let html = program.evaluate();
+    // local images are looked up next to the markdown file
+    let options = RenderOptions {
+        image_root: Path::new(in_file).parent().map(Path::to_path_buf),
+        ..RenderOptions::default()
+    };
+    let html = program.evaluate(&options);
 
     let out_file: Vec<_> = in_file.split(".").collect();
     let out_file = out_file.first().unwrap().to_string() + ".html";
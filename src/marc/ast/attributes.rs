@@ -0,0 +1,182 @@
+use super::Node;
+use crate::marc::render::Renderer;
+use crate::span::Span;
+use crate::utils;
+
+/// `Attributes` is an attribute block like `{#custom-id .class key=value}` that sets the
+/// attributes of the html element a node is rendered as. values with white space in them are
+/// quoted: `{title="a title"}`.
+///
+/// the block is written right after an inline element (`*text*{.note}`, `[link](/){.button}`),
+/// at the end of a heading, or on the line after a block. the nodes that are written out as an
+/// html element keep their attributes (see `Node::attributes`); a block after a node that
+/// doesn't take attributes (like plain text) is written out as the text it was parsed from.
+#[derive(Default)]
+pub struct Attributes {
+    pub literal: String,
+    pub id: Option<String>,
+    pub classes: Vec<String>,
+    pub pairs: Vec<(String, String)>,
+    pub span: Span,
+}
+
+impl Attributes {
+    /// `parse` parses an attribute block (with its braces). it returns `None` if `literal` isn't
+    /// a valid attribute block.
+    pub fn parse(literal: &str) -> Option<Self> {
+        let inner = literal.strip_prefix('{')?.strip_suffix('}')?.trim();
+        if inner.is_empty() {
+            return None;
+        }
+
+        let mut attributes = Attributes {
+            literal: literal.to_string(),
+            ..Attributes::default()
+        };
+        let mut rest = inner;
+        while !rest.is_empty() {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            if let Some(id) = rest[..end].strip_prefix('#') {
+                if id.is_empty() {
+                    return None;
+                }
+                attributes.id = Some(id.to_string());
+            } else if let Some(class) = rest[..end].strip_prefix('.') {
+                if class.is_empty() {
+                    return None;
+                }
+                attributes.classes.push(class.to_string());
+            } else {
+                let (key, value) = rest.split_once('=')?;
+                if key.is_empty() || !key.chars().all(is_key_char) {
+                    return None;
+                }
+
+                let (value, value_len) = match value.strip_prefix('"') {
+                    Some(quoted) => {
+                        let close = quoted.find('"')?;
+                        (&quoted[..close], close + 2)
+                    }
+                    None => {
+                        let len = value.find(char::is_whitespace).unwrap_or(value.len());
+                        (&value[..len], len)
+                    }
+                };
+                match key {
+                    "id" => attributes.id = Some(value.to_string()),
+                    "class" => attributes
+                        .classes
+                        .extend(value.split_whitespace().map(str::to_string)),
+                    _ => attributes.pairs.push((key.to_string(), value.to_string())),
+                }
+                rest = rest[key.len() + 1 + value_len..].trim_start();
+                continue;
+            }
+            rest = rest[end..].trim_start();
+        }
+
+        Some(attributes)
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// `get` returns the value of the `key=value` attribute named `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .rev()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// `pairs` returns the `key=value` attributes (other than `id` and `class`), in the order
+    /// they were written.
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.pairs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.classes.is_empty() && self.pairs.is_empty()
+    }
+
+    /// `take_id` removes the id from the attributes and returns it.
+    pub fn take_id(&mut self) -> Option<String> {
+        self.id.take()
+    }
+
+    /// `merge` adds `other` to the attributes: its id replaces the current one and its classes
+    /// and `key=value` attributes are added after the current ones.
+    pub fn merge(&mut self, other: Attributes) {
+        if other.id.is_some() {
+            self.id = other.id;
+        }
+        self.classes.extend(other.classes);
+        self.pairs.extend(other.pairs);
+    }
+
+    /// `html` writes the attributes out as the attributes of an html element, each one with a
+    /// space before it (eg. ` id="intro" class="lead"`). `classes` are the classes the element
+    /// always has; the classes of the attribute block come after them.
+    pub fn html(&self, classes: &[&str]) -> String {
+        let mut html = String::new();
+        if let Some(id) = &self.id {
+            html.push_str(&format!(" id=\"{}\"", utils::escape_html(id)));
+        }
+        html + &self.html_without_id(classes)
+    }
+
+    /// `html_without_id` is `html` for elements that get their id some other way (like headings).
+    pub fn html_without_id(&self, classes: &[&str]) -> String {
+        let mut html = String::new();
+        let classes: Vec<&str> = classes
+            .iter()
+            .copied()
+            .chain(self.classes.iter().map(String::as_str))
+            .collect();
+        if !classes.is_empty() {
+            html.push_str(&format!(
+                " class=\"{}\"",
+                utils::escape_html(&classes.join(" "))
+            ));
+        }
+        for (name, value) in &self.pairs {
+            html.push_str(&format!(" {}=\"{}\"", name, utils::escape_html(value)));
+        }
+        html
+    }
+}
+
+fn is_key_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' || ch == ':'
+}
+
+impl Node for Attributes {
+    fn token_literal(&self) -> String {
+        format!(
+            "Attributes(id={:?}, classes={:?}, pairs={:?})",
+            self.id, self.classes, self.pairs
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_attributes(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
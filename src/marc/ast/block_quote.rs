@@ -1,35 +1,155 @@
-use super::{Block, Node, inline_container::InlineContainer};
+use super::{Block, Node, attributes::Attributes};
+use crate::marc::render::Renderer;
+use crate::span::Span;
 
+/// `BlockQuote` is a run of lines starting with `>`. the text after the `>`s is parsed on its own,
+/// so a quote can hold any block: paragraphs, lists, code blocks, other quotes, etc.
 pub struct BlockQuote {
-    inner: Box<dyn Node>,
+    pub children: Vec<Block>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl BlockQuote {
     pub fn new() -> Self {
         BlockQuote {
-            inner: Box::new(InlineContainer::new()),
+            children: vec![],
+            attributes: Attributes::default(),
+            span: Span::default(),
         }
     }
 
-    pub fn set_inner(&mut self, inner: Box<dyn Node>) {
-        self.inner = inner;
+    pub fn add_child(&mut self, child: impl Into<Block>) {
+        self.children.push(child.into());
     }
 }
 
 impl Node for BlockQuote {
     fn token_literal(&self) -> String {
-        format!("BlockQuote(\"content: {}\")", self.inner.token_literal())
+        format!("BlockQuote(content={})", self.children.token_literal())
     }
 
-    fn evaluate(&self) -> String {
-        format!("<blockquote>{}</blockquote>", self.inner.evaluate())
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_block_quote(self)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
-        self
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
 }
 
-impl Block for BlockQuote {
-    fn block_token(&self) {}
+/// `AlertKind` is the kind of a (github style) alert, given by the `[!KIND]` marker on the first
+/// line of the quote.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlertKind {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl AlertKind {
+    /// `from_marker` returns the kind of alert that `marker` (eg. `[!NOTE]`) starts, if any. the
+    /// kind isn't case-sensitive.
+    pub fn from_marker(marker: &str) -> Option<AlertKind> {
+        let kind = marker.strip_prefix("[!")?.strip_suffix(']')?;
+        match kind.to_uppercase().as_str() {
+            "NOTE" => Some(AlertKind::Note),
+            "TIP" => Some(AlertKind::Tip),
+            "IMPORTANT" => Some(AlertKind::Important),
+            "WARNING" => Some(AlertKind::Warning),
+            "CAUTION" => Some(AlertKind::Caution),
+            _ => None,
+        }
+    }
+
+    /// `title` is the heading of the alert as it is shown to the reader.
+    pub fn title(&self) -> &'static str {
+        match self {
+            AlertKind::Note => "Note",
+            AlertKind::Tip => "Tip",
+            AlertKind::Important => "Important",
+            AlertKind::Warning => "Warning",
+            AlertKind::Caution => "Caution",
+        }
+    }
+}
+
+/// `Alert` is a block quote whose first line is an alert marker (`> [!NOTE]`, `> [!WARNING]`,
+/// etc.). it is written out as a callout that the stylesheet styles by its kind.
+pub struct Alert {
+    pub kind: AlertKind,
+    pub children: Vec<Block>,
+    pub attributes: Attributes,
+    pub span: Span,
+}
+
+impl Alert {
+    pub fn new(kind: AlertKind) -> Self {
+        Alert {
+            kind,
+            children: vec![],
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn add_child(&mut self, child: impl Into<Block>) {
+        self.children.push(child.into());
+    }
+
+    pub fn kind(&self) -> AlertKind {
+        self.kind
+    }
+}
+
+impl Node for Alert {
+    fn token_literal(&self) -> String {
+        format!(
+            "Alert(kind={:?}, content={})",
+            self.kind,
+            self.children.token_literal()
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_alert(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
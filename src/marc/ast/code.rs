@@ -1,15 +1,21 @@
-use std::any::Any;
+use std::ops::RangeInclusive;
 
-use super::{Block, Inline, Node};
+use super::{Node, attributes::Attributes};
+use crate::marc::render::Renderer;
+use crate::span::Span;
 
 pub struct InlineCode {
-    code_content: String,
+    pub code_content: String,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl InlineCode {
     pub fn new(content: String) -> Self {
         InlineCode {
             code_content: content,
+            attributes: Attributes::default(),
+            span: Span::default(),
         }
     }
 }
@@ -19,22 +25,38 @@ impl Node for InlineCode {
         format!("InlineCode(\"{}\")", self.code_content)
     }
 
-    fn evaluate(&self) -> String {
-        format!("<code>{}</code>", self.code_content)
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_inline_code(self)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn plain_text(&self) -> String {
+        self.code_content.clone()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
     }
-}
 
-impl Inline for InlineCode {
-    fn inline_token(&self) {}
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
 
 pub struct CodeBlock {
-    code_content: String,
-    language: String,
+    pub code_content: String,
+    pub language: String,
+    pub title: Option<String>,
+    pub highlighted_lines: Vec<RangeInclusive<usize>>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl CodeBlock {
@@ -42,27 +64,181 @@ impl CodeBlock {
         CodeBlock {
             code_content: content,
             language,
+            title: None,
+            highlighted_lines: vec![],
+            attributes: Attributes::default(),
+            span: Span::default(),
         }
     }
+
+    /// `from_info_string` creates a code block whose language, title and highlighted lines are
+    /// read from the info string of its opening fence.
+    pub fn from_info_string(content: String, info_string: &str) -> Self {
+        let info = InfoString::parse(info_string);
+        let mut code_block = CodeBlock::new(content, info.language);
+        code_block.title = info.title;
+        code_block.highlighted_lines = info.highlighted_lines;
+        code_block
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        self.title = Some(title);
+    }
+
+    pub fn set_highlighted_lines(&mut self, lines: Vec<RangeInclusive<usize>>) {
+        self.highlighted_lines = lines;
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// `is_line_highlighted` reports whether the (1-indexed) `line` of the code block falls in
+    /// one of its highlighted ranges.
+    pub fn is_line_highlighted(&self, line: usize) -> bool {
+        self.highlighted_lines
+            .iter()
+            .any(|range| range.contains(&line))
+    }
 }
 
 impl Node for CodeBlock {
     fn token_literal(&self) -> String {
         format!(
-            "CodeBlock(\"{}\", lang={})",
-            self.code_content, self.language
+            "CodeBlock(\"{}\", lang={}, title={:?}, highlighted={:?})",
+            self.code_content, self.language, self.title, self.highlighted_lines
         )
     }
 
-    fn evaluate(&self) -> String {
-        format!("<pre>{}</pre>", self.code_content)
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_code_block(self)
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// `InfoString` holds the details read from the text after the opening fence of a code block,
+/// eg. ```` ```rust title="main.rs" {3-5} ````.
+///
+/// The first word is the language, `title="..."` sets the caption shown above the code and
+/// `{...}` holds a comma separated list of lines or line ranges (`1,3-5`) to highlight.
+#[derive(Debug, PartialEq)]
+pub struct InfoString {
+    pub language: String,
+    pub title: Option<String>,
+    pub highlighted_lines: Vec<RangeInclusive<usize>>,
 }
 
-impl Block for CodeBlock {
-    fn block_token(&self) {}
+impl InfoString {
+    pub fn parse(info_string: &str) -> Self {
+        let mut info = InfoString {
+            language: String::new(),
+            title: None,
+            highlighted_lines: vec![],
+        };
+
+        for (index, word) in Self::split_words(info_string).into_iter().enumerate() {
+            if let Some(ranges) = word.strip_prefix('{') {
+                info.highlighted_lines = Self::parse_line_ranges(ranges.trim_end_matches('}'));
+            } else if let Some((key, value)) = word.split_once('=') {
+                if key == "title" {
+                    info.title = Some(Self::unquote(value).to_string());
+                }
+            } else if index == 0 {
+                info.language = word;
+            }
+        }
+
+        info
+    }
+
+    /// `split_words` splits the info string on white space, keeping quoted values and `{...}`
+    /// groups together. a `{` directly after the language (`rust{1,3}`) also starts a new word.
+    fn split_words(info_string: &str) -> Vec<String> {
+        let mut words = vec![];
+        let mut word = String::new();
+        let mut closing: Option<char> = None;
+
+        for ch in info_string.trim().chars() {
+            match closing {
+                Some(close) => {
+                    word.push(ch);
+                    if ch == close {
+                        closing = None;
+                    }
+                }
+                None if ch.is_whitespace() => {
+                    if !word.is_empty() {
+                        words.push(std::mem::take(&mut word));
+                    }
+                }
+                None => {
+                    if ch == '{' && !word.is_empty() {
+                        words.push(std::mem::take(&mut word));
+                    }
+                    closing = match ch {
+                        '"' | '\'' => Some(ch),
+                        '{' => Some('}'),
+                        _ => None,
+                    };
+                    word.push(ch);
+                }
+            }
+        }
+
+        if !word.is_empty() {
+            words.push(word);
+        }
+        words
+    }
+
+    fn unquote(value: &str) -> &str {
+        for quote in ['"', '\''] {
+            if let Some(inner) = value
+                .strip_prefix(quote)
+                .and_then(|value| value.strip_suffix(quote))
+            {
+                return inner;
+            }
+        }
+        value
+    }
+
+    fn parse_line_ranges(ranges: &str) -> Vec<RangeInclusive<usize>> {
+        ranges
+            .split(',')
+            .filter_map(|range| {
+                let range = range.trim();
+                match range.split_once('-') {
+                    Some((start, end)) => {
+                        let start = start.trim().parse().ok()?;
+                        let end = end.trim().parse().ok()?;
+                        Some(start..=end)
+                    }
+                    None => {
+                        let line = range.parse().ok()?;
+                        Some(line..=line)
+                    }
+                }
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,85 @@
+use super::{Block, Node, attributes::Attributes};
+use crate::marc::render::Renderer;
+use crate::span::Span;
+
+/// `Container` is a fenced block of markdown with a name:
+///
+/// ```text
+/// :::warning Read this first {#first}
+/// any *markdown*, including other containers
+/// :::
+/// ```
+///
+/// the text between the name and the attribute block is the argument of the container (eg. the
+/// summary of a `details` container). a container is written out as a `<div>` with its name as
+/// its class, unless a renderer is registered for its name in `RenderOptions::containers`.
+pub struct Container {
+    pub name: String,
+    pub argument: String,
+    pub children: Vec<Block>,
+    pub attributes: Attributes,
+    pub span: Span,
+}
+
+impl Container {
+    pub fn new(name: String, argument: String) -> Self {
+        Container {
+            name,
+            argument,
+            children: vec![],
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn add_child(&mut self, child: impl Into<Block>) {
+        self.children.push(child.into());
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn argument(&self) -> &str {
+        &self.argument
+    }
+
+    pub fn children(&self) -> &[Block] {
+        &self.children
+    }
+}
+
+impl Node for Container {
+    fn token_literal(&self) -> String {
+        format!(
+            "Container(name={:?}, argument={:?}, content={})",
+            self.name,
+            self.argument,
+            self.children.token_literal()
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_container(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
@@ -0,0 +1,175 @@
+use super::{Block, Inline, Node, attributes::Attributes, inline_container::InlineContainer};
+use crate::marc::render::Renderer;
+use crate::span::Span;
+
+/// `DefinitionList` is a list of terms, each followed by one or more definitions:
+///
+/// ```text
+/// Term
+/// : the definition of the term
+/// : another definition
+/// ```
+#[derive(Default)]
+pub struct DefinitionList {
+    pub items: Vec<DefinitionItem>,
+    pub attributes: Attributes,
+    pub span: Span,
+}
+
+impl DefinitionList {
+    pub fn new() -> Self {
+        DefinitionList::default()
+    }
+
+    pub fn add_item(&mut self, item: DefinitionItem) {
+        self.items.push(item);
+    }
+
+    pub fn items(&self) -> &[DefinitionItem] {
+        &self.items
+    }
+}
+
+impl Node for DefinitionList {
+    fn token_literal(&self) -> String {
+        let items: Vec<String> = self.items.iter().map(|item| item.token_literal()).collect();
+        format!("DefinitionList([{}])", items.join(","))
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_definition_list(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.items.iter().map(|item| item.plain_text()).collect()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// `DefinitionItem` is a term of a definition list with its definitions.
+pub struct DefinitionItem {
+    pub term: Vec<Inline>,
+    pub definitions: Vec<Definition>,
+    pub span: Span,
+}
+
+impl DefinitionItem {
+    pub fn new(term: InlineContainer) -> Self {
+        DefinitionItem {
+            term: term.children,
+            definitions: vec![],
+            span: Span::default(),
+        }
+    }
+
+    pub fn add_definition(&mut self, definition: Definition) {
+        self.definitions.push(definition);
+    }
+
+    pub fn term(&self) -> &[Inline] {
+        &self.term
+    }
+
+    pub fn definitions(&self) -> &[Definition] {
+        &self.definitions
+    }
+}
+
+impl Node for DefinitionItem {
+    fn token_literal(&self) -> String {
+        let definitions: Vec<String> = self
+            .definitions
+            .iter()
+            .map(|definition| definition.token_literal())
+            .collect();
+        format!(
+            "DefinitionItem(term={}, definitions=[{}])",
+            self.term.token_literal(),
+            definitions.join(",")
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_definition_item(self)
+    }
+
+    fn plain_text(&self) -> String {
+        let definitions: String = self
+            .definitions
+            .iter()
+            .map(|definition| definition.plain_text())
+            .collect();
+        self.term.plain_text() + &definitions
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// `Definition` is one of the definitions of a term. like a list item, it can hold any block.
+#[derive(Default)]
+pub struct Definition {
+    pub children: Vec<Block>,
+    pub attributes: Attributes,
+    pub span: Span,
+}
+
+impl Definition {
+    pub fn new() -> Self {
+        Definition::default()
+    }
+
+    pub fn add_child(&mut self, child: impl Into<Block>) {
+        self.children.push(child.into());
+    }
+}
+
+impl Node for Definition {
+    fn token_literal(&self) -> String {
+        format!("Definition(content={})", self.children.token_literal())
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_definition(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
@@ -0,0 +1,133 @@
+use super::{Block, Node};
+use crate::marc::render::Renderer;
+use crate::span::Span;
+
+/// `FootnoteReference` is a `[^label]` in the text. footnotes are numbered in the order they are
+/// first referenced, and `reference` counts the references to the same footnote so each one
+/// gets its own id for the back-link to point to.
+pub struct FootnoteReference {
+    pub number: usize,
+    pub reference: usize,
+    pub span: Span,
+}
+
+impl FootnoteReference {
+    pub fn new(number: usize, reference: usize) -> Self {
+        FootnoteReference {
+            number,
+            reference,
+            span: Span::default(),
+        }
+    }
+}
+
+impl Node for FootnoteReference {
+    fn token_literal(&self) -> String {
+        format!(
+            "FootnoteReference(number={}, reference={})",
+            self.number, self.reference
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_footnote_reference(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.number.to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// `Footnote` is the (parsed) content of a `[^label]: ...` definition, to be written out in the
+/// footnotes section at the end of the document.
+pub struct Footnote {
+    pub number: usize,
+    pub references: usize,
+    pub children: Vec<Block>,
+    pub span: Span,
+}
+
+impl Footnote {
+    pub fn new(number: usize, references: usize, children: Vec<Block>) -> Self {
+        Footnote {
+            number,
+            references,
+            children,
+            span: Span::default(),
+        }
+    }
+}
+
+impl Node for Footnote {
+    fn token_literal(&self) -> String {
+        format!(
+            "Footnote(number={}, references={}, content={})",
+            self.number,
+            self.references,
+            self.children.token_literal()
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_footnote(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// `FootnoteSection` lists the footnotes of the document. the parser adds it at the end of the
+/// document when there are footnotes.
+#[derive(Default)]
+pub struct FootnoteSection {
+    pub footnotes: Vec<Footnote>,
+    pub span: Span,
+}
+
+impl FootnoteSection {
+    pub fn new() -> Self {
+        FootnoteSection {
+            footnotes: vec![],
+            span: Span::default(),
+        }
+    }
+
+    pub fn add_footnote(&mut self, footnote: Footnote) {
+        self.footnotes.push(footnote);
+    }
+}
+
+impl Node for FootnoteSection {
+    fn token_literal(&self) -> String {
+        format!("FootnoteSection({})", self.footnotes.token_literal())
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_footnote_section(self)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
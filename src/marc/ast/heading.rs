@@ -1,53 +1,87 @@
-use std::any::Any;
-
-use super::{Block, Node, inline_container::InlineContainer};
+use super::{Inline, Node, attributes::Attributes, inline_container::InlineContainer};
+use crate::marc::render::Renderer;
+use crate::span::Span;
 
 pub struct Heading {
-    // `content` is a Vec because a heading can have pure text as well as other inline nodes
+    // `children` is a Vec because a heading can have pure text as well as other inline nodes
     // like links, bold and italicized texts, etc. and they should all be recorded/parsed
     // independently
-    inner: Box<dyn Node>,
-    level: i8,
+    pub children: Vec<Inline>,
+    pub level: i8,
+    // `id` is the id of the html element, so other documents can link to the heading. the
+    // parser sets it to the slug of the heading's text, or to the id in `{#custom-id}`.
+    pub id: Option<String>,
+    // the other attributes of the heading, from its `{.class key=value}` block
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl Heading {
     pub fn new(level: i8) -> Self {
         Heading {
             level,
-            inner: Box::new(InlineContainer::new()),
+            children: vec![],
+            id: None,
+            attributes: Attributes::default(),
+            span: Span::default(),
         }
     }
 
-    pub fn set_inner(&mut self, content: Box<dyn Node>) {
-        self.inner = content;
+    pub fn set_inner(&mut self, content: InlineContainer) {
+        self.children = content.children;
+    }
+
+    pub fn set_id(&mut self, id: String) {
+        self.id = Some(id);
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn level(&self) -> i8 {
+        self.level
     }
 }
 
 impl Node for Heading {
     fn token_literal(&self) -> String {
-        format!(
-            "Heading{}(children: {})",
-            self.level,
-            self.inner.token_literal()
-        )
+        match &self.id {
+            Some(id) => format!(
+                "Heading{}(id: \"{}\", children: {})",
+                self.level,
+                id,
+                self.children.token_literal()
+            ),
+            None => format!(
+                "Heading{}(children: {})",
+                self.level,
+                self.children.token_literal()
+            ),
+        }
     }
 
-    fn evaluate(&self) -> String {
-        format!(
-            "<h{}>{}</h{}>",
-            self.level,
-            self.inner.evaluate(),
-            self.level
-        )
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_heading(self)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
     }
-}
 
-impl Block for Heading {
-    fn block_token(&self) {
-        todo!()
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
 }
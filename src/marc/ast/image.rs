@@ -1,31 +1,125 @@
-use std::any::Any;
-
-use super::{Block, Node};
+use super::{Node, attributes::Attributes};
+use crate::marc::render::Renderer;
+use crate::span::Span;
 
 pub struct Image {
-    src: String,
-    alt: String,
+    pub src: String,
+    pub alt: String,
+    pub title: Option<String>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl Image {
     pub fn new(src: String, alt: String) -> Self {
-        Image { src, alt }
+        Image {
+            src,
+            alt,
+            title: None,
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        self.title = Some(title);
+    }
+
+    /// `set_attributes` sets the attributes of the image, from a `{width=300 .rounded}` block
+    /// written right after it.
+    pub fn set_attributes(&mut self, attributes: Attributes) {
+        self.attributes = attributes;
+    }
+
+    pub fn src(&self) -> &str {
+        &self.src
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
     }
 }
+
 impl Node for Image {
     fn token_literal(&self) -> String {
-        format!("Image(src = \"{}\", alt = \"{}\")", self.src, self.alt)
+        match &self.title {
+            Some(title) => format!(
+                "Image(src = \"{}\", alt = \"{}\", title = \"{}\")",
+                self.src, self.alt, title
+            ),
+            None => format!("Image(src = \"{}\", alt = \"{}\")", self.src, self.alt),
+        }
     }
 
-    fn evaluate(&self) -> String {
-        format!("<img src = \"{}\" alt = \"{}\" />", self.src, self.alt)
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_image(self)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
     }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// `Figure` is an image on a line of its own with a title, which is shown as the caption of
+/// the image: `![alt](src "caption")`.
+pub struct Figure {
+    pub image: Image,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
-impl Block for Image {
-    fn block_token(&self) {}
+impl Figure {
+    pub fn new(image: Image) -> Self {
+        Figure {
+            image,
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+}
+
+impl Node for Figure {
+    fn token_literal(&self) -> String {
+        format!("Figure({})", self.image.token_literal())
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_figure(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.image.title.clone().unwrap_or_default()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
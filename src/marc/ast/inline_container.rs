@@ -1,9 +1,13 @@
-use std::any::Any;
-
-use super::{Inline, Node};
+use super::{Inline, Node, attributes::Attributes};
+use crate::marc::render::Renderer;
+use crate::span::Span;
 
+/// `InlineContainer` is a run of inline nodes, eg. the content of a line while it is parsed. the
+/// nodes that hold inline content (paragraphs, links, etc.) are given it as an
+/// `InlineContainer`.
+#[derive(Default)]
 pub struct InlineContainer {
-    children: Vec<Box<dyn Node>>,
+    pub children: Vec<Inline>,
 }
 
 impl InlineContainer {
@@ -11,14 +15,54 @@ impl InlineContainer {
         InlineContainer { children: vec![] }
     }
 
-    pub fn add_child(&mut self, child: Box<dyn Node>) {
-        self.children.push(child);
+    pub fn add_child(&mut self, child: impl Into<Inline>) {
+        self.children.push(child.into());
     }
 
     pub fn extend(&mut self, inner: InlineContainer) {
-        for child in inner.children {
-            self.add_child(child);
+        self.children.extend(inner.children);
+    }
+
+    /// `take_attributes` removes the attribute block at the end of the container (if there's
+    /// one) and returns it.
+    pub fn take_attributes(&mut self) -> Option<Attributes> {
+        // the attribute block can be followed by white space
+        let position = self
+            .children
+            .iter()
+            .rposition(|child| !child.plain_text().trim().is_empty())?;
+        if !matches!(self.children[position], Inline::Attributes(_)) {
+            return None;
+        }
+
+        self.children.truncate(position + 1);
+        let Some(Inline::Attributes(attributes)) = self.children.pop() else {
+            return None;
+        };
+
+        // the white space between the content and the attribute block isn't part of the content
+        if let Some(Inline::Text(text)) = self.children.last_mut() {
+            text.trim_end();
+            if text.plain_text().is_empty() {
+                self.children.pop();
+            }
+        }
+
+        Some(attributes)
+    }
+
+    /// `trim_line_end` removes the white space at the end of the container, which ends a line of
+    /// a paragraph, and returns whether the line ended with a hard line break.
+    pub fn trim_line_end(&mut self) -> bool {
+        let Some(Inline::Text(text)) = self.children.last_mut() else {
+            return false;
+        };
+
+        let hard_break = text.trim_line_end();
+        if text.is_empty() {
+            self.children.pop();
         }
+        hard_break
     }
 }
 
@@ -27,15 +71,17 @@ impl Node for InlineContainer {
         self.children.token_literal()
     }
 
-    fn evaluate(&self) -> String {
-        self.children.evaluate()
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_inlines(&self.children)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn span(&self) -> Span {
+        self.children.span()
     }
-}
 
-impl Inline for InlineContainer {
-    fn inline_token(&self) {}
+    fn set_span(&mut self, _span: Span) {}
 }
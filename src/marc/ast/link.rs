@@ -1,32 +1,75 @@
-use std::any::Any;
-
-use super::{Inline, Node};
+use super::{Inline, Node, attributes::Attributes, inline_container::InlineContainer};
+use crate::marc::render::Renderer;
+use crate::span::Span;
 
 pub struct Link {
-    alt_text: String,
-    href: String,
+    // `children` is the link text, which can have other inline nodes (bold text, code, images,
+    // etc.) in it
+    pub children: Vec<Inline>,
+    pub href: String,
+    pub title: Option<String>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl Node for Link {
     fn token_literal(&self) -> String {
-        format!("Link(href: \"{}\", alt: \"{}\")", self.href, self.alt_text)
+        match &self.title {
+            Some(title) => format!(
+                "Link(href: \"{}\", title: \"{}\", children: {})",
+                self.href,
+                title,
+                self.children.token_literal()
+            ),
+            None => format!(
+                "Link(href: \"{}\", children: {})",
+                self.href,
+                self.children.token_literal()
+            ),
+        }
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_link(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
     }
 
-    fn evaluate(&self) -> String {
-        format!("<a href = \"{}\">{}</a>", self.href, self.alt_text)
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
 }
 
 impl Link {
-    pub fn new(alt_text: String, href: String) -> Self {
-        Link { alt_text, href }
+    pub fn new(href: String) -> Self {
+        Link {
+            children: vec![],
+            href,
+            title: None,
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
     }
-}
 
-impl Inline for Link {
-    fn inline_token(&self) {}
+    pub fn set_inner(&mut self, content: InlineContainer) {
+        self.children = content.children;
+    }
+
+    pub fn set_title(&mut self, title: String) {
+        self.title = Some(title);
+    }
 }
@@ -1,15 +1,23 @@
-use super::{Block, Node, inline_container::InlineContainer};
+use super::{Block, Node, attributes::Attributes};
+use crate::marc::render::Renderer;
+use crate::span::Span;
 
 pub struct UnorderedList {
-    items: Vec<Box<dyn Node>>,
+    pub items: Vec<ListItem>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl UnorderedList {
     pub fn new() -> Self {
-        Self { items: vec![] }
+        Self {
+            items: vec![],
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
     }
 
-    pub fn add_list_item(&mut self, item: Box<dyn Node>) {
+    pub fn add_list_item(&mut self, item: ListItem) {
         self.items.push(item);
     }
 }
@@ -19,29 +27,51 @@ impl Node for UnorderedList {
         format!("UnorderedList(content={})", self.items.token_literal())
     }
 
-    fn evaluate(&self) -> String {
-        format!("<ul>{}</ul>", self.items.evaluate())
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_unordered_list(self)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
-        self
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
     }
-}
 
-impl Block for UnorderedList {
-    fn block_token(&self) {}
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
 
 pub struct OrderedList {
-    items: Vec<Box<dyn Node>>,
+    pub items: Vec<ListItem>,
+    // `start` is the number of the first item, which the list is numbered from (`3.` starts it
+    // at 3)
+    pub start: usize,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl OrderedList {
     pub fn new() -> Self {
-        Self { items: vec![] }
+        Self {
+            items: vec![],
+            start: 1,
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
     }
 
-    pub fn add_list_item(&mut self, item: Box<dyn Node>) {
+    pub fn set_start(&mut self, start: usize) {
+        self.start = start;
+    }
+
+    pub fn add_list_item(&mut self, item: ListItem) {
         self.items.push(item);
     }
 }
@@ -51,49 +81,150 @@ impl Node for OrderedList {
         format!("OrderedList(content={})", self.items.token_literal())
     }
 
-    fn evaluate(&self) -> String {
-        format!("<ol>{}</ol>", self.items.evaluate())
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_ordered_list(self)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
-        self
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
     }
-}
 
-impl Block for OrderedList {
-    fn block_token(&self) {}
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
 
 pub struct ListItem {
-    inner: Box<dyn Node>,
+    pub children: Vec<Block>,
+    // whether the checkbox of a task list item (`- [x] ...`) is checked. `None` for the items
+    // that aren't tasks.
+    pub checked: Option<bool>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl ListItem {
     pub fn new() -> Self {
         Self {
-            inner: Box::new(InlineContainer::new()),
+            children: vec![],
+            checked: None,
+            attributes: Attributes::default(),
+            span: Span::default(),
         }
     }
 
-    pub fn set_inner(&mut self, inner: Box<dyn Node>) {
-        self.inner = inner;
+    pub fn add_child(&mut self, child: impl Into<Block>) {
+        self.children.push(child.into());
+    }
+
+    /// `set_checked` makes the item a task with the given state.
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = Some(checked);
+    }
+
+    pub fn checked(&self) -> Option<bool> {
+        self.checked
     }
 }
 
 impl Node for ListItem {
     fn token_literal(&self) -> String {
-        format!("ListItem(content={})", self.inner.token_literal())
+        match self.checked {
+            Some(checked) => format!(
+                "ListItem(checked={}, content={})",
+                checked,
+                self.children.token_literal()
+            ),
+            None => format!("ListItem(content={})", self.children.token_literal()),
+        }
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_list_item(self)
     }
 
-    fn evaluate(&self) -> String {
-        format!("<li>{}</li>", self.inner.evaluate())
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn std::any::Any> {
-        self
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
 }
 
-impl Block for ListItem {
-    fn block_token(&self) {}
+/// `TaskProgress` is the number of task list items of a document and how many of them are done.
+/// in a document, it is the `[[progress]]` marker, which is written out as `done/total`.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct TaskProgress {
+    pub done: usize,
+    pub total: usize,
+    pub span: Span,
+}
+
+impl TaskProgress {
+    pub fn new(done: usize, total: usize) -> Self {
+        Self {
+            done,
+            total,
+            span: Span::default(),
+        }
+    }
+
+    pub fn done(&self) -> usize {
+        self.done
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// `add_task` counts another task (done if `checked`).
+    pub fn add_task(&mut self, checked: bool) {
+        self.total += 1;
+        if checked {
+            self.done += 1;
+        }
+    }
+}
+
+impl Node for TaskProgress {
+    fn token_literal(&self) -> String {
+        format!("TaskProgress({}/{})", self.done, self.total)
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_task_progress(self)
+    }
+
+    fn plain_text(&self) -> String {
+        format!("{}/{}", self.done, self.total)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
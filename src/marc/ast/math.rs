@@ -0,0 +1,115 @@
+use super::{Node, attributes::Attributes};
+use crate::marc::render::Renderer;
+use crate::span::Span;
+
+/// `InlineMath` is TeX math in a paragraph: `$...$`, or `$$...$$` for display math.
+pub struct InlineMath {
+    pub tex: String,
+    pub display: bool,
+    pub attributes: Attributes,
+    pub span: Span,
+}
+
+impl InlineMath {
+    pub fn new(tex: String, display: bool) -> Self {
+        InlineMath {
+            tex,
+            display,
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn tex(&self) -> &str {
+        &self.tex
+    }
+
+    pub fn is_display(&self) -> bool {
+        self.display
+    }
+}
+
+impl Node for InlineMath {
+    fn token_literal(&self) -> String {
+        if self.display {
+            format!("InlineMath(display=true, \"{}\")", self.tex)
+        } else {
+            format!("InlineMath(\"{}\")", self.tex)
+        }
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_inline_math(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.tex.clone()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// `MathBlock` is display math on lines of its own, between two `$$` lines.
+pub struct MathBlock {
+    pub tex: String,
+    pub attributes: Attributes,
+    pub span: Span,
+}
+
+impl MathBlock {
+    pub fn new(tex: String) -> Self {
+        MathBlock {
+            tex,
+            attributes: Attributes::default(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn tex(&self) -> &str {
+        &self.tex
+    }
+}
+
+impl Node for MathBlock {
+    fn token_literal(&self) -> String {
+        format!("MathBlock(\"{}\")", self.tex)
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_math_block(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.tex.clone()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
@@ -1,110 +1,377 @@
-use std::any::Any;
+use crate::marc::ast::{
+    attributes::Attributes,
+    block_quote::{Alert, BlockQuote},
+    code::{CodeBlock, InlineCode},
+    container::Container,
+    definition_list::DefinitionList,
+    footnote::{FootnoteReference, FootnoteSection},
+    heading::Heading,
+    image::{Figure, Image},
+    inline_container::InlineContainer,
+    link::Link,
+    list::{OrderedList, TaskProgress, UnorderedList},
+    math::{InlineMath, MathBlock},
+    text::{BoldText, EscapedText, HardBreak, ItalicizedText, ParagraphText, SoftBreak, Text},
+    thematic_break::ThematicBreak,
+    toc::TableOfContents,
+    visit::VisitMut,
+};
+use crate::marc::options::RenderOptions;
+use crate::marc::render::{HtmlRenderer, Renderer};
+use crate::span::Span;
 
+pub mod attributes;
 pub mod block_quote;
 pub mod code;
+pub mod container;
+pub mod definition_list;
+pub mod footnote;
 pub mod heading;
 pub mod image;
 pub mod inline_container;
 pub mod link;
 pub mod list;
+pub mod math;
 pub mod text;
+pub mod thematic_break;
+pub mod toc;
+pub mod visit;
 
-pub trait Node: Any {
+/// `Node` is implemented by every node of the document (and by `Block` and `Inline`, which hold
+/// them). it writes the node out with a `Renderer` (as html with `evaluate`), and as a debug
+/// representation (used by the tests) with `token_literal`.
+pub trait Node {
     fn token_literal(&self) -> String;
-    fn evaluate(&self) -> String;
-    fn as_any(self: Box<Self>) -> Box<dyn Any>;
-}
 
-pub trait Block: Node {
-    fn block_token(&self);
+    /// `render` writes the node out with `renderer`, ie. with the method of the renderer for its
+    /// kind of node.
+    fn render(&self, renderer: &mut dyn Renderer) -> String;
+
+    /// `evaluate` writes the node out as html
+    fn evaluate(&self, options: &RenderOptions) -> String {
+        self.render(&mut HtmlRenderer::new(options))
+    }
+
+    /// `plain_text` returns the text of the node without any markup (eg. to generate the id of a
+    /// heading). nodes without any text, like images, return an empty string.
+    fn plain_text(&self) -> String {
+        String::new()
+    }
+
+    /// `attributes` returns the attributes set on the node with an attribute block
+    /// (`{#id .class key=value}`). only the nodes written out as an html element have
+    /// attributes; the others (like text) return `None`.
+    fn attributes(&self) -> Option<&Attributes> {
+        None
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        None
+    }
+
+    /// `span` returns where the node is in the source of the document. the nodes that aren't
+    /// written in the source (like the footnotes section) have an empty span.
+    fn span(&self) -> Span;
+    fn set_span(&mut self, span: Span);
 }
 
-pub trait Inline: Node {
-    fn inline_token(&self);
+/// `Block` is a node that makes up the structure of a document: paragraphs, headings, lists,
+/// quotes, etc. the blocks that hold other blocks (eg. list items) hold them as `Block`s too.
+pub enum Block {
+    Paragraph(ParagraphText),
+    Heading(Heading),
+    ThematicBreak(ThematicBreak),
+    CodeBlock(CodeBlock),
+    MathBlock(MathBlock),
+    BlockQuote(BlockQuote),
+    Alert(Alert),
+    UnorderedList(UnorderedList),
+    OrderedList(OrderedList),
+    DefinitionList(DefinitionList),
+    Container(Container),
+    Figure(Figure),
+    TableOfContents(TableOfContents),
+    FootnoteSection(FootnoteSection),
+    /// `Plain` is the content of a paragraph that isn't wrapped in <p> tags, ie. a paragraph of a
+    /// tight list item or definition.
+    Plain(Vec<Inline>),
+    /// `Inline` is an inline node on its way into the paragraph (or other inline container) it is
+    /// part of. the parser uses it for the nodes of a line: a line that starts with a link,
+    /// emphasis, code, etc. starts a paragraph like any other text.
+    Inline(Inline),
 }
 
-pub struct Program {
-    nodes: Vec<Box<dyn Node>>,
+impl Block {
+    /// `node` returns the node the block holds
+    pub fn node(&self) -> &dyn Node {
+        match self {
+            Block::Paragraph(node) => node,
+            Block::Heading(node) => node,
+            Block::ThematicBreak(node) => node,
+            Block::CodeBlock(node) => node,
+            Block::MathBlock(node) => node,
+            Block::BlockQuote(node) => node,
+            Block::Alert(node) => node,
+            Block::UnorderedList(node) => node,
+            Block::OrderedList(node) => node,
+            Block::DefinitionList(node) => node,
+            Block::Container(node) => node,
+            Block::Figure(node) => node,
+            Block::TableOfContents(node) => node,
+            Block::FootnoteSection(node) => node,
+            Block::Plain(node) => node,
+            Block::Inline(node) => node,
+        }
+    }
+
+    pub fn node_mut(&mut self) -> &mut dyn Node {
+        match self {
+            Block::Paragraph(node) => node,
+            Block::Heading(node) => node,
+            Block::ThematicBreak(node) => node,
+            Block::CodeBlock(node) => node,
+            Block::MathBlock(node) => node,
+            Block::BlockQuote(node) => node,
+            Block::Alert(node) => node,
+            Block::UnorderedList(node) => node,
+            Block::OrderedList(node) => node,
+            Block::DefinitionList(node) => node,
+            Block::Container(node) => node,
+            Block::Figure(node) => node,
+            Block::TableOfContents(node) => node,
+            Block::FootnoteSection(node) => node,
+            Block::Plain(node) => node,
+            Block::Inline(node) => node,
+        }
+    }
 }
 
-impl Node for Program {
+impl Node for Block {
     fn token_literal(&self) -> String {
-        self.nodes.token_literal()
+        self.node().token_literal()
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_block(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.node().plain_text()
     }
 
-    fn evaluate(&self) -> String {
-        let inside: String = self.nodes.iter().map(|node| node.evaluate()).collect();
-        format!(
-            "
-            <!DOCTYPE html>
-            <html>
-            <head>
-                <meta charset=\"UTF-8\">
-                <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">
-                <link href = \"./styles.css\" rel = \"stylesheet\"></link>
-            </head>
-            <body>{}</body>
-            </html>",
-            inside
-        )
+    fn attributes(&self) -> Option<&Attributes> {
+        self.node().attributes()
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        self.node_mut().attributes_mut()
+    }
+
+    fn span(&self) -> Span {
+        self.node().span()
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.node_mut().set_span(span);
     }
 }
 
-impl Node for Vec<Box<dyn Node>> {
-    fn token_literal(&self) -> String {
-        if self.len() > 0 {
-            let literal: String = self.iter().map(|x| x.token_literal() + ",").collect();
+/// `Inline` is a node in the text of a block: text, emphasis, links, code spans, etc.
+pub enum Inline {
+    Text(Text),
+    EscapedText(EscapedText),
+    Bold(BoldText),
+    Italics(ItalicizedText),
+    Code(InlineCode),
+    Link(Link),
+    Image(Image),
+    Math(InlineMath),
+    FootnoteReference(FootnoteReference),
+    TaskProgress(TaskProgress),
+    SoftBreak(SoftBreak),
+    HardBreak(HardBreak),
+    /// `Attributes` is an attribute block that doesn't belong to any element, which is written
+    /// out as the text it was parsed from.
+    Attributes(Attributes),
+    /// `Block` is a block in inline content. emphasis that isn't closed on its line goes on until
+    /// it is closed (or the document ends), over the blocks in between.
+    Block(Box<Block>),
+}
 
-            let len = literal.len() - 1;
-            format!("[{}]", &literal[..len])
-        } else {
-            String::from("")
+impl Inline {
+    /// `node` returns the node the inline node holds
+    pub fn node(&self) -> &dyn Node {
+        match self {
+            Inline::Text(node) => node,
+            Inline::EscapedText(node) => node,
+            Inline::Bold(node) => node,
+            Inline::Italics(node) => node,
+            Inline::Code(node) => node,
+            Inline::Link(node) => node,
+            Inline::Image(node) => node,
+            Inline::Math(node) => node,
+            Inline::FootnoteReference(node) => node,
+            Inline::TaskProgress(node) => node,
+            Inline::SoftBreak(node) => node,
+            Inline::HardBreak(node) => node,
+            Inline::Attributes(node) => node,
+            Inline::Block(node) => node.as_ref(),
         }
     }
 
-    fn evaluate(&self) -> String {
-        if self.len() > 0 {
-            let literal: String = self.iter().map(|x| x.evaluate()).collect();
-            literal
-        } else {
-            String::from("")
+    pub fn node_mut(&mut self) -> &mut dyn Node {
+        match self {
+            Inline::Text(node) => node,
+            Inline::EscapedText(node) => node,
+            Inline::Bold(node) => node,
+            Inline::Italics(node) => node,
+            Inline::Code(node) => node,
+            Inline::Link(node) => node,
+            Inline::Image(node) => node,
+            Inline::Math(node) => node,
+            Inline::FootnoteReference(node) => node,
+            Inline::TaskProgress(node) => node,
+            Inline::SoftBreak(node) => node,
+            Inline::HardBreak(node) => node,
+            Inline::Attributes(node) => node,
+            Inline::Block(node) => node.as_mut(),
         }
     }
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
-    }
 }
 
-impl Node for Vec<Box<dyn Inline>> {
+impl Node for Inline {
     fn token_literal(&self) -> String {
-        if self.len() > 0 {
-            let literal: String = self.iter().map(|x| x.token_literal() + ",").collect();
+        self.node().token_literal()
+    }
 
-            let len = literal.len() - 1;
-            format!("[{}]", &literal[..len])
-        } else {
-            String::from("")
-        }
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_inline(self)
     }
 
-    fn evaluate(&self) -> String {
-        if self.len() > 0 {
-            let literal: String = self.iter().map(|x| x.evaluate()).collect();
-            literal
-        } else {
-            String::from("")
+    fn plain_text(&self) -> String {
+        self.node().plain_text()
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        self.node().attributes()
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        self.node_mut().attributes_mut()
+    }
+
+    fn span(&self) -> Span {
+        self.node().span()
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.node_mut().set_span(span);
+    }
+}
+
+impl From<Inline> for Block {
+    fn from(inline: Inline) -> Self {
+        Block::Inline(inline)
+    }
+}
+
+impl From<Block> for Inline {
+    /// an inline node that isn't in a paragraph is an inline node again, any other block is a
+    /// block in inline content
+    fn from(block: Block) -> Self {
+        match block {
+            Block::Inline(inline) => inline,
+            block => Inline::Block(Box::new(block)),
         }
     }
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+}
+
+impl From<Vec<Inline>> for Block {
+    fn from(inlines: Vec<Inline>) -> Self {
+        Block::Plain(inlines)
+    }
+}
+
+impl From<InlineContainer> for Block {
+    fn from(container: InlineContainer) -> Self {
+        Block::Plain(container.children)
     }
 }
 
-impl Node for Vec<Box<dyn Block>> {
+// every node converts into the variant of `Block` or `Inline` that holds it
+macro_rules! impl_from_node {
+    ($enum:ident, $($variant:ident($node:ty)),* $(,)?) => {
+        $(
+            impl From<$node> for $enum {
+                fn from(node: $node) -> Self {
+                    $enum::$variant(node)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_node!(
+    Block,
+    Paragraph(ParagraphText),
+    Heading(Heading),
+    ThematicBreak(ThematicBreak),
+    CodeBlock(CodeBlock),
+    MathBlock(MathBlock),
+    BlockQuote(BlockQuote),
+    Alert(Alert),
+    UnorderedList(UnorderedList),
+    OrderedList(OrderedList),
+    DefinitionList(DefinitionList),
+    Container(Container),
+    Figure(Figure),
+    TableOfContents(TableOfContents),
+    FootnoteSection(FootnoteSection),
+);
+
+impl_from_node!(
+    Inline,
+    Text(Text),
+    EscapedText(EscapedText),
+    Bold(BoldText),
+    Italics(ItalicizedText),
+    Code(InlineCode),
+    Link(Link),
+    Image(Image),
+    Math(InlineMath),
+    FootnoteReference(FootnoteReference),
+    TaskProgress(TaskProgress),
+    SoftBreak(SoftBreak),
+    HardBreak(HardBreak),
+    Attributes(Attributes),
+);
+
+pub struct Program {
+    pub blocks: Vec<Block>,
+    pub headings: Vec<toc::TocEntry>,
+    pub task_progress: list::TaskProgress,
+    pub span: Span,
+}
+
+impl Node for Program {
+    fn token_literal(&self) -> String {
+        self.blocks.token_literal()
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_program(self)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+impl<T: Node> Node for Vec<T> {
     fn token_literal(&self) -> String {
         if self.len() > 0 {
             let literal: String = self.iter().map(|x| x.token_literal() + ",").collect();
@@ -116,25 +383,83 @@ impl Node for Vec<Box<dyn Block>> {
         }
     }
 
-    fn evaluate(&self) -> String {
-        todo!()
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        self.iter().map(|x| x.render(renderer)).collect()
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn plain_text(&self) -> String {
+        self.iter().map(|x| x.plain_text()).collect()
     }
-}
 
+    /// the span of a list of nodes goes from its first node to its last one
+    fn span(&self) -> Span {
+        match (self.first(), self.last()) {
+            (Some(first), Some(last)) => first.span().to(last.span()),
+            _ => Span::default(),
+        }
+    }
+
+    fn set_span(&mut self, _span: Span) {}
+}
 impl Program {
     pub fn new() -> Program {
-        Program { nodes: vec![] }
+        Program {
+            blocks: vec![],
+            headings: vec![],
+            task_progress: list::TaskProgress::default(),
+            span: Span::default(),
+        }
+    }
+
+    pub fn add_block(&mut self, block: impl Into<Block>) {
+        self.blocks.push(block.into());
     }
 
-    pub fn add_block(&mut self, block: Box<dyn Node>) {
-        self.nodes.push(block);
+    pub fn get_blocks(&self) -> &Vec<Block> {
+        &self.blocks
     }
 
-    pub fn get_blocks(&self) -> &Vec<Box<dyn Node>> {
-        &self.nodes
+    pub fn into_blocks(self) -> Vec<Block> {
+        self.blocks
+    }
+
+    /// `headings` returns every heading of the document (with an id), in order. it is the data
+    /// the table of contents is built from.
+    pub fn headings(&self) -> &[toc::TocEntry] {
+        &self.headings
+    }
+
+    /// `set_headings` records the headings of the document and fills in every table of contents
+    /// in it with them.
+    pub fn set_headings(&mut self, headings: Vec<toc::TocEntry>) {
+        for block in self.blocks.iter_mut() {
+            if let Block::TableOfContents(toc) = block {
+                toc.set_entries(headings.clone());
+            }
+        }
+        self.headings = headings;
+    }
+
+    /// `task_progress` returns how many of the task list items (`- [ ] ...`) of the document
+    /// are done.
+    pub fn task_progress(&self) -> list::TaskProgress {
+        self.task_progress
+    }
+
+    /// `set_task_progress` records the task progress of the document and fills in every
+    /// `[[progress]]` marker in it with it.
+    pub fn set_task_progress(&mut self, task_progress: list::TaskProgress) {
+        ProgressMarkers(task_progress).visit_program_mut(self);
+        self.task_progress = task_progress;
+    }
+}
+
+/// `ProgressMarkers` sets the progress written out by the `[[progress]]` markers of a document.
+struct ProgressMarkers(list::TaskProgress);
+
+impl VisitMut for ProgressMarkers {
+    fn visit_task_progress_mut(&mut self, progress: &mut TaskProgress) {
+        progress.done = self.0.done;
+        progress.total = self.0.total;
     }
 }
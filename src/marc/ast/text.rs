@@ -1,14 +1,42 @@
-use std::any::Any;
-
-use super::{Block, Inline, Node, inline_container::InlineContainer};
+use super::{Inline, Node, attributes::Attributes, inline_container::InlineContainer};
+use crate::marc::render::Renderer;
+use crate::span::Span;
 
 pub struct Text {
-    value: String,
+    pub value: String,
+    pub span: Span,
 }
 
 impl Text {
     pub fn new(value: String) -> Self {
-        Text { value: value }
+        Text {
+            value: value,
+            span: Span::default(),
+        }
+    }
+
+    pub fn trim_end(&mut self) {
+        self.value.truncate(self.value.trim_end().len());
+    }
+
+    /// `trim_line_end` removes the white space at the end of the text, which ends a line of a
+    /// paragraph, and returns whether the line ended with a hard line break: two or more spaces
+    /// or a backslash.
+    pub fn trim_line_end(&mut self) -> bool {
+        let hard_break = self.value.ends_with("  ") || self.value.ends_with('\\');
+        if self.value.ends_with('\\') {
+            self.value.pop();
+        }
+        self.trim_end();
+        hard_break
+    }
+
+    pub fn trim_start(&mut self) {
+        self.value = self.value.trim_start().to_string();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
     }
 }
 
@@ -17,121 +45,275 @@ impl Node for Text {
         format!("Text(\"{}\")", self.value.clone())
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_text(self, None, None)
     }
 
-    fn evaluate(&self) -> String {
+    fn plain_text(&self) -> String {
         self.value.to_owned()
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
 
-impl Inline for Text {
-    fn inline_token(&self) {
-        todo!()
+/// `EscapedText` is a character written with a backslash escape (`\*`) or an entity reference
+/// (`&copy;`). it holds the character itself, which is always written out as text (so `\<` and
+/// `&lt;` are both `&lt;` in the html), as it is: text transforms don't change it. urls that
+/// aren't links are kept as escaped text too.
+pub struct EscapedText {
+    pub value: String,
+    pub span: Span,
+}
+
+impl EscapedText {
+    pub fn new(value: String) -> Self {
+        EscapedText {
+            value,
+            span: Span::default(),
+        }
     }
 }
 
+impl Node for EscapedText {
+    fn token_literal(&self) -> String {
+        format!("EscapedText(\"{}\")", self.value)
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_escaped_text(self)
+    }
+
+    fn plain_text(&self) -> String {
+        self.value.to_owned()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+#[derive(Default)]
 pub struct BoldText {
-    inner: Box<dyn Node>,
+    pub children: Vec<Inline>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl BoldText {
     pub fn new() -> Self {
-        BoldText {
-            inner: Box::new(InlineContainer::new()),
-        }
+        BoldText::default()
     }
 
-    pub fn set_inner(&mut self, content: Box<dyn Node>) {
-        self.inner = content;
+    pub fn set_inner(&mut self, content: InlineContainer) {
+        self.children = content.children;
     }
 }
 
 impl Node for BoldText {
     fn token_literal(&self) -> String {
-        format!("Bold({})", self.inner.token_literal())
+        format!("Bold({})", self.children.token_literal())
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_bold(self)
     }
 
-    fn evaluate(&self) -> String {
-        format!("<strong>{}</strong>", self.inner.evaluate())
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
     }
-}
 
-impl Inline for BoldText {
-    fn inline_token(&self) {
-        todo!()
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
 }
 
+#[derive(Default)]
 pub struct ItalicizedText {
-    inner: Box<dyn Node>,
+    pub children: Vec<Inline>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl ItalicizedText {
     pub fn new() -> Self {
-        ItalicizedText {
-            inner: Box::new(InlineContainer::new()),
-        }
+        ItalicizedText::default()
     }
 
-    pub fn set_inner(&mut self, content: Box<dyn Node>) {
-        self.inner = content;
+    pub fn set_inner(&mut self, content: InlineContainer) {
+        self.children = content.children;
     }
 }
 
 impl Node for ItalicizedText {
     fn token_literal(&self) -> String {
-        format!("Italics({})", self.inner.token_literal())
+        format!("Italics({})", self.children.token_literal())
     }
 
-    fn evaluate(&self) -> String {
-        format!("<em>{}</em>", self.inner.evaluate())
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_italics(self)
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn span(&self) -> Span {
+        self.span
     }
-}
 
-impl Inline for ItalicizedText {
-    fn inline_token(&self) {}
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
 
+#[derive(Default)]
 pub struct ParagraphText {
-    inner: Box<dyn Node>,
+    pub children: Vec<Inline>,
+    pub attributes: Attributes,
+    pub span: Span,
 }
 
 impl ParagraphText {
     pub fn new() -> Self {
-        ParagraphText {
-            inner: Box::new(InlineContainer::new()),
-        }
+        ParagraphText::default()
     }
 
-    pub fn set_inner(&mut self, content: Box<dyn Node>) {
-        self.inner = content;
+    pub fn set_inner(&mut self, content: InlineContainer) {
+        self.children = content.children;
+    }
+
+    /// `into_inner` returns the content of the paragraph without the paragraph around it
+    pub fn into_inner(self) -> Vec<Inline> {
+        self.children
     }
 }
 
 impl Node for ParagraphText {
     fn token_literal(&self) -> String {
-        format!("Paragraph({})", self.inner.token_literal())
+        format!("Paragraph({})", self.children.token_literal())
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_paragraph(self)
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn plain_text(&self) -> String {
+        self.children.plain_text()
+    }
+
+    fn span(&self) -> Span {
+        self.span
     }
 
-    fn as_any(self: Box<Self>) -> Box<dyn Any> {
-        self
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
     }
+}
+
+/// `SoftBreak` is the end of a line inside a paragraph. it is written out as
+/// `RenderOptions::soft_break`.
+#[derive(Default)]
+pub struct SoftBreak {
+    pub span: Span,
+}
 
-    fn evaluate(&self) -> String {
-        format!("<p>{}</p>", self.inner.evaluate())
+impl SoftBreak {
+    pub fn new() -> Self {
+        SoftBreak::default()
     }
 }
 
-impl Block for ParagraphText {
-    fn block_token(&self) {}
+impl Node for SoftBreak {
+    fn token_literal(&self) -> String {
+        "SoftBreak".to_string()
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_soft_break(self)
+    }
+
+    fn plain_text(&self) -> String {
+        " ".to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+/// `HardBreak` is the end of a line inside a paragraph that is kept in the html, ie. a line
+/// ending with two spaces or a backslash.
+#[derive(Default)]
+pub struct HardBreak {
+    pub span: Span,
+}
+
+impl HardBreak {
+    pub fn new() -> Self {
+        HardBreak::default()
+    }
+}
+
+impl Node for HardBreak {
+    fn token_literal(&self) -> String {
+        "HardBreak".to_string()
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_hard_break(self)
+    }
+
+    fn plain_text(&self) -> String {
+        "\n".to_string()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
 }
@@ -0,0 +1,42 @@
+use super::{Node, attributes::Attributes};
+use crate::marc::render::Renderer;
+use crate::span::Span;
+
+/// `ThematicBreak` is a `---`, `***` or `___` line, which separates the sections of a document.
+#[derive(Default)]
+pub struct ThematicBreak {
+    pub attributes: Attributes,
+    pub span: Span,
+}
+
+impl ThematicBreak {
+    pub fn new() -> Self {
+        ThematicBreak::default()
+    }
+}
+
+impl Node for ThematicBreak {
+    fn token_literal(&self) -> String {
+        "ThematicBreak".to_string()
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_thematic_break(self)
+    }
+
+    fn attributes(&self) -> Option<&Attributes> {
+        Some(&self.attributes)
+    }
+
+    fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        Some(&mut self.attributes)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
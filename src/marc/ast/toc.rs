@@ -0,0 +1,61 @@
+use super::Node;
+use crate::marc::render::Renderer;
+use crate::span::Span;
+
+/// `TocEntry` is what the table of contents needs to know about a heading of the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    pub level: i8,
+    pub id: String,
+    pub text: String,
+}
+
+impl TocEntry {
+    pub fn new(level: i8, id: String, text: String) -> Self {
+        TocEntry { level, id, text }
+    }
+}
+
+/// `TableOfContents` is written where the document has a `[[toc]]` marker (or an expander
+/// `{{ toc() }}` call). it is rendered as a nested list of links to the headings of the document
+/// whose level is between `RenderOptions::toc_min_level` and `RenderOptions::toc_max_level`.
+#[derive(Default)]
+pub struct TableOfContents {
+    pub entries: Vec<TocEntry>,
+    pub span: Span,
+}
+
+impl TableOfContents {
+    pub fn new() -> Self {
+        TableOfContents {
+            entries: vec![],
+            span: Span::default(),
+        }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<TocEntry>) {
+        self.entries = entries;
+    }
+
+    pub fn entries(&self) -> &[TocEntry] {
+        &self.entries
+    }
+}
+
+impl Node for TableOfContents {
+    fn token_literal(&self) -> String {
+        format!("TableOfContents({:?})", self.entries)
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> String {
+        renderer.render_table_of_contents(self)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
@@ -0,0 +1,601 @@
+//! `visit` walks the nodes of a document, to read them with a `Visitor` or to rewrite them in
+//! place with a `VisitMut`.
+//!
+//! every method of the traits has a default that walks the children of its node (with the
+//! `walk_*` function of the same name), so a visitor only implements the methods of the nodes it
+//! is interested in. a method that is overridden decides whether the children of its node are
+//! visited, by calling the `walk_*` function or not.
+//!
+//! a visitor collecting the headings of a document:
+//!
+//! ```
+//! use md_to_html::marc::ast::{Node, heading::Heading, visit::{self, Visitor}};
+//! use md_to_html::marc::{lexer::Lexer, parser::Parser};
+//!
+//! #[derive(Default)]
+//! struct HeadingCollector {
+//!     headings: Vec<(i8, String)>,
+//! }
+//!
+//! impl Visitor for HeadingCollector {
+//!     fn visit_heading(&mut self, heading: &Heading) {
+//!         self.headings.push((heading.level, heading.plain_text().trim().to_string()));
+//!         visit::walk_heading(self, heading);
+//!     }
+//! }
+//!
+//! let program = Parser::new(Lexer::from("# Intro\n\n> ## Quoted\n\n## *Usage*")).parse_program();
+//! let mut collector = HeadingCollector::default();
+//! collector.visit_program(&program);
+//! assert_eq!(
+//!     collector.headings,
+//!     vec![(1, "Intro".to_string()), (2, "Quoted".to_string()), (2, "Usage".to_string())]
+//! );
+//! ```
+//!
+//! a visitor rewriting the links of a document:
+//!
+//! ```
+//! use md_to_html::marc::ast::{Node, link::Link, visit::{self, VisitMut}};
+//! use md_to_html::marc::{lexer::Lexer, options::RenderOptions, parser::Parser};
+//!
+//! struct LinkRewriter;
+//!
+//! impl VisitMut for LinkRewriter {
+//!     fn visit_link_mut(&mut self, link: &mut Link) {
+//!         if let Some(page) = link.href.strip_suffix(".md") {
+//!             link.href = format!("{}.html", page);
+//!         }
+//!         visit::walk_link_mut(self, link);
+//!     }
+//! }
+//!
+//! let mut program = Parser::new(Lexer::from("see [the guide](guide.md)")).parse_program();
+//! LinkRewriter.visit_program_mut(&mut program);
+//! let html = program.blocks[0].evaluate(&RenderOptions::default());
+//! assert_eq!(html, "<p>see <a href = \"guide.html\">the guide</a></p>");
+//! ```
+
+use super::{
+    Block, Inline, Program,
+    attributes::Attributes,
+    block_quote::{Alert, BlockQuote},
+    code::{CodeBlock, InlineCode},
+    container::Container,
+    definition_list::{Definition, DefinitionItem, DefinitionList},
+    footnote::{Footnote, FootnoteReference, FootnoteSection},
+    heading::Heading,
+    image::{Figure, Image},
+    link::Link,
+    list::{ListItem, OrderedList, TaskProgress, UnorderedList},
+    math::{InlineMath, MathBlock},
+    text::{BoldText, EscapedText, HardBreak, ItalicizedText, ParagraphText, SoftBreak, Text},
+    thematic_break::ThematicBreak,
+    toc::TableOfContents,
+};
+
+/// `Visitor` reads the nodes of a document. `visit_block` and `visit_inline` are called for
+/// every block and inline node, before the method of the node they hold.
+pub trait Visitor {
+    fn visit_program(&mut self, program: &Program) {
+        walk_program(self, program);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) {
+        walk_inline(self, inline);
+    }
+
+    fn visit_paragraph(&mut self, paragraph: &ParagraphText) {
+        walk_paragraph(self, paragraph);
+    }
+
+    fn visit_heading(&mut self, heading: &Heading) {
+        walk_heading(self, heading);
+    }
+
+    fn visit_thematic_break(&mut self, _thematic_break: &ThematicBreak) {}
+
+    fn visit_code_block(&mut self, _code_block: &CodeBlock) {}
+
+    fn visit_math_block(&mut self, _math_block: &MathBlock) {}
+
+    fn visit_block_quote(&mut self, block_quote: &BlockQuote) {
+        walk_block_quote(self, block_quote);
+    }
+
+    fn visit_alert(&mut self, alert: &Alert) {
+        walk_alert(self, alert);
+    }
+
+    fn visit_unordered_list(&mut self, list: &UnorderedList) {
+        walk_unordered_list(self, list);
+    }
+
+    fn visit_ordered_list(&mut self, list: &OrderedList) {
+        walk_ordered_list(self, list);
+    }
+
+    fn visit_list_item(&mut self, item: &ListItem) {
+        walk_list_item(self, item);
+    }
+
+    fn visit_definition_list(&mut self, list: &DefinitionList) {
+        walk_definition_list(self, list);
+    }
+
+    fn visit_definition_item(&mut self, item: &DefinitionItem) {
+        walk_definition_item(self, item);
+    }
+
+    fn visit_definition(&mut self, definition: &Definition) {
+        walk_definition(self, definition);
+    }
+
+    fn visit_container(&mut self, container: &Container) {
+        walk_container(self, container);
+    }
+
+    fn visit_figure(&mut self, figure: &Figure) {
+        walk_figure(self, figure);
+    }
+
+    fn visit_table_of_contents(&mut self, _toc: &TableOfContents) {}
+
+    fn visit_footnote_section(&mut self, section: &FootnoteSection) {
+        walk_footnote_section(self, section);
+    }
+
+    fn visit_footnote(&mut self, footnote: &Footnote) {
+        walk_footnote(self, footnote);
+    }
+
+    /// `visit_plain` is called for the content of a `Block::Plain`, a paragraph without <p> tags.
+    fn visit_plain(&mut self, children: &[Inline]) {
+        walk_plain(self, children);
+    }
+
+    fn visit_text(&mut self, _text: &Text) {}
+
+    fn visit_escaped_text(&mut self, _text: &EscapedText) {}
+
+    fn visit_bold(&mut self, bold: &BoldText) {
+        walk_bold(self, bold);
+    }
+
+    fn visit_italics(&mut self, italics: &ItalicizedText) {
+        walk_italics(self, italics);
+    }
+
+    fn visit_inline_code(&mut self, _code: &InlineCode) {}
+
+    fn visit_link(&mut self, link: &Link) {
+        walk_link(self, link);
+    }
+
+    fn visit_image(&mut self, _image: &Image) {}
+
+    fn visit_inline_math(&mut self, _math: &InlineMath) {}
+
+    fn visit_footnote_reference(&mut self, _reference: &FootnoteReference) {}
+
+    fn visit_task_progress(&mut self, _progress: &TaskProgress) {}
+
+    fn visit_soft_break(&mut self, _soft_break: &SoftBreak) {}
+
+    fn visit_hard_break(&mut self, _hard_break: &HardBreak) {}
+
+    /// `visit_attributes` is called for an attribute block that doesn't belong to any element
+    /// (`Inline::Attributes`). the attributes of the other nodes are a field of the node.
+    fn visit_attributes(&mut self, _attributes: &Attributes) {}
+}
+
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &Program) {
+    for block in &program.blocks {
+        visitor.visit_block(block);
+    }
+}
+
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Block) {
+    match block {
+        Block::Paragraph(node) => visitor.visit_paragraph(node),
+        Block::Heading(node) => visitor.visit_heading(node),
+        Block::ThematicBreak(node) => visitor.visit_thematic_break(node),
+        Block::CodeBlock(node) => visitor.visit_code_block(node),
+        Block::MathBlock(node) => visitor.visit_math_block(node),
+        Block::BlockQuote(node) => visitor.visit_block_quote(node),
+        Block::Alert(node) => visitor.visit_alert(node),
+        Block::UnorderedList(node) => visitor.visit_unordered_list(node),
+        Block::OrderedList(node) => visitor.visit_ordered_list(node),
+        Block::DefinitionList(node) => visitor.visit_definition_list(node),
+        Block::Container(node) => visitor.visit_container(node),
+        Block::Figure(node) => visitor.visit_figure(node),
+        Block::TableOfContents(node) => visitor.visit_table_of_contents(node),
+        Block::FootnoteSection(node) => visitor.visit_footnote_section(node),
+        Block::Plain(children) => visitor.visit_plain(children),
+        Block::Inline(node) => visitor.visit_inline(node),
+    }
+}
+
+pub fn walk_inline<V: Visitor + ?Sized>(visitor: &mut V, inline: &Inline) {
+    match inline {
+        Inline::Text(node) => visitor.visit_text(node),
+        Inline::EscapedText(node) => visitor.visit_escaped_text(node),
+        Inline::Bold(node) => visitor.visit_bold(node),
+        Inline::Italics(node) => visitor.visit_italics(node),
+        Inline::Code(node) => visitor.visit_inline_code(node),
+        Inline::Link(node) => visitor.visit_link(node),
+        Inline::Image(node) => visitor.visit_image(node),
+        Inline::Math(node) => visitor.visit_inline_math(node),
+        Inline::FootnoteReference(node) => visitor.visit_footnote_reference(node),
+        Inline::TaskProgress(node) => visitor.visit_task_progress(node),
+        Inline::SoftBreak(node) => visitor.visit_soft_break(node),
+        Inline::HardBreak(node) => visitor.visit_hard_break(node),
+        Inline::Attributes(node) => visitor.visit_attributes(node),
+        Inline::Block(node) => visitor.visit_block(node),
+    }
+}
+
+fn walk_blocks<V: Visitor + ?Sized>(visitor: &mut V, blocks: &[Block]) {
+    for block in blocks {
+        visitor.visit_block(block);
+    }
+}
+
+fn walk_inlines<V: Visitor + ?Sized>(visitor: &mut V, children: &[Inline]) {
+    for child in children {
+        visitor.visit_inline(child);
+    }
+}
+
+pub fn walk_paragraph<V: Visitor + ?Sized>(visitor: &mut V, paragraph: &ParagraphText) {
+    walk_inlines(visitor, &paragraph.children);
+}
+
+pub fn walk_heading<V: Visitor + ?Sized>(visitor: &mut V, heading: &Heading) {
+    walk_inlines(visitor, &heading.children);
+}
+
+pub fn walk_block_quote<V: Visitor + ?Sized>(visitor: &mut V, block_quote: &BlockQuote) {
+    walk_blocks(visitor, &block_quote.children);
+}
+
+pub fn walk_alert<V: Visitor + ?Sized>(visitor: &mut V, alert: &Alert) {
+    walk_blocks(visitor, &alert.children);
+}
+
+pub fn walk_unordered_list<V: Visitor + ?Sized>(visitor: &mut V, list: &UnorderedList) {
+    for item in &list.items {
+        visitor.visit_list_item(item);
+    }
+}
+
+pub fn walk_ordered_list<V: Visitor + ?Sized>(visitor: &mut V, list: &OrderedList) {
+    for item in &list.items {
+        visitor.visit_list_item(item);
+    }
+}
+
+pub fn walk_list_item<V: Visitor + ?Sized>(visitor: &mut V, item: &ListItem) {
+    walk_blocks(visitor, &item.children);
+}
+
+pub fn walk_definition_list<V: Visitor + ?Sized>(visitor: &mut V, list: &DefinitionList) {
+    for item in &list.items {
+        visitor.visit_definition_item(item);
+    }
+}
+
+pub fn walk_definition_item<V: Visitor + ?Sized>(visitor: &mut V, item: &DefinitionItem) {
+    walk_inlines(visitor, &item.term);
+    for definition in &item.definitions {
+        visitor.visit_definition(definition);
+    }
+}
+
+pub fn walk_definition<V: Visitor + ?Sized>(visitor: &mut V, definition: &Definition) {
+    walk_blocks(visitor, &definition.children);
+}
+
+pub fn walk_container<V: Visitor + ?Sized>(visitor: &mut V, container: &Container) {
+    walk_blocks(visitor, &container.children);
+}
+
+pub fn walk_figure<V: Visitor + ?Sized>(visitor: &mut V, figure: &Figure) {
+    visitor.visit_image(&figure.image);
+}
+
+pub fn walk_footnote_section<V: Visitor + ?Sized>(visitor: &mut V, section: &FootnoteSection) {
+    for footnote in &section.footnotes {
+        visitor.visit_footnote(footnote);
+    }
+}
+
+pub fn walk_footnote<V: Visitor + ?Sized>(visitor: &mut V, footnote: &Footnote) {
+    walk_blocks(visitor, &footnote.children);
+}
+
+pub fn walk_plain<V: Visitor + ?Sized>(visitor: &mut V, children: &[Inline]) {
+    walk_inlines(visitor, children);
+}
+
+pub fn walk_bold<V: Visitor + ?Sized>(visitor: &mut V, bold: &BoldText) {
+    walk_inlines(visitor, &bold.children);
+}
+
+pub fn walk_italics<V: Visitor + ?Sized>(visitor: &mut V, italics: &ItalicizedText) {
+    walk_inlines(visitor, &italics.children);
+}
+
+pub fn walk_link<V: Visitor + ?Sized>(visitor: &mut V, link: &Link) {
+    walk_inlines(visitor, &link.children);
+}
+
+/// `VisitMut` rewrites the nodes of a document in place. it has the methods of `Visitor` (with
+/// a `_mut` suffix), called with mutable references: a visitor can change the fields of a node,
+/// or replace a whole node from `visit_block_mut` or `visit_inline_mut`.
+///
+/// the table of contents and `Program::headings` are built while the document is parsed, so
+/// they don't follow the changes made to the headings.
+pub trait VisitMut {
+    fn visit_program_mut(&mut self, program: &mut Program) {
+        walk_program_mut(self, program);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_inline_mut(&mut self, inline: &mut Inline) {
+        walk_inline_mut(self, inline);
+    }
+
+    fn visit_paragraph_mut(&mut self, paragraph: &mut ParagraphText) {
+        walk_paragraph_mut(self, paragraph);
+    }
+
+    fn visit_heading_mut(&mut self, heading: &mut Heading) {
+        walk_heading_mut(self, heading);
+    }
+
+    fn visit_thematic_break_mut(&mut self, _thematic_break: &mut ThematicBreak) {}
+
+    fn visit_code_block_mut(&mut self, _code_block: &mut CodeBlock) {}
+
+    fn visit_math_block_mut(&mut self, _math_block: &mut MathBlock) {}
+
+    fn visit_block_quote_mut(&mut self, block_quote: &mut BlockQuote) {
+        walk_block_quote_mut(self, block_quote);
+    }
+
+    fn visit_alert_mut(&mut self, alert: &mut Alert) {
+        walk_alert_mut(self, alert);
+    }
+
+    fn visit_unordered_list_mut(&mut self, list: &mut UnorderedList) {
+        walk_unordered_list_mut(self, list);
+    }
+
+    fn visit_ordered_list_mut(&mut self, list: &mut OrderedList) {
+        walk_ordered_list_mut(self, list);
+    }
+
+    fn visit_list_item_mut(&mut self, item: &mut ListItem) {
+        walk_list_item_mut(self, item);
+    }
+
+    fn visit_definition_list_mut(&mut self, list: &mut DefinitionList) {
+        walk_definition_list_mut(self, list);
+    }
+
+    fn visit_definition_item_mut(&mut self, item: &mut DefinitionItem) {
+        walk_definition_item_mut(self, item);
+    }
+
+    fn visit_definition_mut(&mut self, definition: &mut Definition) {
+        walk_definition_mut(self, definition);
+    }
+
+    fn visit_container_mut(&mut self, container: &mut Container) {
+        walk_container_mut(self, container);
+    }
+
+    fn visit_figure_mut(&mut self, figure: &mut Figure) {
+        walk_figure_mut(self, figure);
+    }
+
+    fn visit_table_of_contents_mut(&mut self, _toc: &mut TableOfContents) {}
+
+    fn visit_footnote_section_mut(&mut self, section: &mut FootnoteSection) {
+        walk_footnote_section_mut(self, section);
+    }
+
+    fn visit_footnote_mut(&mut self, footnote: &mut Footnote) {
+        walk_footnote_mut(self, footnote);
+    }
+
+    /// `visit_plain_mut` is called for the content of a `Block::Plain`, which nodes can be added
+    /// to or removed from.
+    fn visit_plain_mut(&mut self, children: &mut Vec<Inline>) {
+        walk_plain_mut(self, children);
+    }
+
+    fn visit_text_mut(&mut self, _text: &mut Text) {}
+
+    fn visit_escaped_text_mut(&mut self, _text: &mut EscapedText) {}
+
+    fn visit_bold_mut(&mut self, bold: &mut BoldText) {
+        walk_bold_mut(self, bold);
+    }
+
+    fn visit_italics_mut(&mut self, italics: &mut ItalicizedText) {
+        walk_italics_mut(self, italics);
+    }
+
+    fn visit_inline_code_mut(&mut self, _code: &mut InlineCode) {}
+
+    fn visit_link_mut(&mut self, link: &mut Link) {
+        walk_link_mut(self, link);
+    }
+
+    fn visit_image_mut(&mut self, _image: &mut Image) {}
+
+    fn visit_inline_math_mut(&mut self, _math: &mut InlineMath) {}
+
+    fn visit_footnote_reference_mut(&mut self, _reference: &mut FootnoteReference) {}
+
+    fn visit_task_progress_mut(&mut self, _progress: &mut TaskProgress) {}
+
+    fn visit_soft_break_mut(&mut self, _soft_break: &mut SoftBreak) {}
+
+    fn visit_hard_break_mut(&mut self, _hard_break: &mut HardBreak) {}
+
+    fn visit_attributes_mut(&mut self, _attributes: &mut Attributes) {}
+}
+
+pub fn walk_program_mut<V: VisitMut + ?Sized>(visitor: &mut V, program: &mut Program) {
+    for block in program.blocks.iter_mut() {
+        visitor.visit_block_mut(block);
+    }
+}
+
+pub fn walk_block_mut<V: VisitMut + ?Sized>(visitor: &mut V, block: &mut Block) {
+    match block {
+        Block::Paragraph(node) => visitor.visit_paragraph_mut(node),
+        Block::Heading(node) => visitor.visit_heading_mut(node),
+        Block::ThematicBreak(node) => visitor.visit_thematic_break_mut(node),
+        Block::CodeBlock(node) => visitor.visit_code_block_mut(node),
+        Block::MathBlock(node) => visitor.visit_math_block_mut(node),
+        Block::BlockQuote(node) => visitor.visit_block_quote_mut(node),
+        Block::Alert(node) => visitor.visit_alert_mut(node),
+        Block::UnorderedList(node) => visitor.visit_unordered_list_mut(node),
+        Block::OrderedList(node) => visitor.visit_ordered_list_mut(node),
+        Block::DefinitionList(node) => visitor.visit_definition_list_mut(node),
+        Block::Container(node) => visitor.visit_container_mut(node),
+        Block::Figure(node) => visitor.visit_figure_mut(node),
+        Block::TableOfContents(node) => visitor.visit_table_of_contents_mut(node),
+        Block::FootnoteSection(node) => visitor.visit_footnote_section_mut(node),
+        Block::Plain(children) => visitor.visit_plain_mut(children),
+        Block::Inline(node) => visitor.visit_inline_mut(node),
+    }
+}
+
+pub fn walk_inline_mut<V: VisitMut + ?Sized>(visitor: &mut V, inline: &mut Inline) {
+    match inline {
+        Inline::Text(node) => visitor.visit_text_mut(node),
+        Inline::EscapedText(node) => visitor.visit_escaped_text_mut(node),
+        Inline::Bold(node) => visitor.visit_bold_mut(node),
+        Inline::Italics(node) => visitor.visit_italics_mut(node),
+        Inline::Code(node) => visitor.visit_inline_code_mut(node),
+        Inline::Link(node) => visitor.visit_link_mut(node),
+        Inline::Image(node) => visitor.visit_image_mut(node),
+        Inline::Math(node) => visitor.visit_inline_math_mut(node),
+        Inline::FootnoteReference(node) => visitor.visit_footnote_reference_mut(node),
+        Inline::TaskProgress(node) => visitor.visit_task_progress_mut(node),
+        Inline::SoftBreak(node) => visitor.visit_soft_break_mut(node),
+        Inline::HardBreak(node) => visitor.visit_hard_break_mut(node),
+        Inline::Attributes(node) => visitor.visit_attributes_mut(node),
+        Inline::Block(node) => visitor.visit_block_mut(node),
+    }
+}
+
+fn walk_blocks_mut<V: VisitMut + ?Sized>(visitor: &mut V, blocks: &mut [Block]) {
+    for block in blocks {
+        visitor.visit_block_mut(block);
+    }
+}
+
+fn walk_inlines_mut<V: VisitMut + ?Sized>(visitor: &mut V, children: &mut [Inline]) {
+    for child in children {
+        visitor.visit_inline_mut(child);
+    }
+}
+
+pub fn walk_paragraph_mut<V: VisitMut + ?Sized>(visitor: &mut V, paragraph: &mut ParagraphText) {
+    walk_inlines_mut(visitor, &mut paragraph.children);
+}
+
+pub fn walk_heading_mut<V: VisitMut + ?Sized>(visitor: &mut V, heading: &mut Heading) {
+    walk_inlines_mut(visitor, &mut heading.children);
+}
+
+pub fn walk_block_quote_mut<V: VisitMut + ?Sized>(visitor: &mut V, block_quote: &mut BlockQuote) {
+    walk_blocks_mut(visitor, &mut block_quote.children);
+}
+
+pub fn walk_alert_mut<V: VisitMut + ?Sized>(visitor: &mut V, alert: &mut Alert) {
+    walk_blocks_mut(visitor, &mut alert.children);
+}
+
+pub fn walk_unordered_list_mut<V: VisitMut + ?Sized>(visitor: &mut V, list: &mut UnorderedList) {
+    for item in list.items.iter_mut() {
+        visitor.visit_list_item_mut(item);
+    }
+}
+
+pub fn walk_ordered_list_mut<V: VisitMut + ?Sized>(visitor: &mut V, list: &mut OrderedList) {
+    for item in list.items.iter_mut() {
+        visitor.visit_list_item_mut(item);
+    }
+}
+
+pub fn walk_list_item_mut<V: VisitMut + ?Sized>(visitor: &mut V, item: &mut ListItem) {
+    walk_blocks_mut(visitor, &mut item.children);
+}
+
+pub fn walk_definition_list_mut<V: VisitMut + ?Sized>(visitor: &mut V, list: &mut DefinitionList) {
+    for item in list.items.iter_mut() {
+        visitor.visit_definition_item_mut(item);
+    }
+}
+
+pub fn walk_definition_item_mut<V: VisitMut + ?Sized>(visitor: &mut V, item: &mut DefinitionItem) {
+    walk_inlines_mut(visitor, &mut item.term);
+    for definition in item.definitions.iter_mut() {
+        visitor.visit_definition_mut(definition);
+    }
+}
+
+pub fn walk_definition_mut<V: VisitMut + ?Sized>(visitor: &mut V, definition: &mut Definition) {
+    walk_blocks_mut(visitor, &mut definition.children);
+}
+
+pub fn walk_container_mut<V: VisitMut + ?Sized>(visitor: &mut V, container: &mut Container) {
+    walk_blocks_mut(visitor, &mut container.children);
+}
+
+pub fn walk_figure_mut<V: VisitMut + ?Sized>(visitor: &mut V, figure: &mut Figure) {
+    visitor.visit_image_mut(&mut figure.image);
+}
+
+pub fn walk_footnote_section_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    section: &mut FootnoteSection,
+) {
+    for footnote in section.footnotes.iter_mut() {
+        visitor.visit_footnote_mut(footnote);
+    }
+}
+
+pub fn walk_footnote_mut<V: VisitMut + ?Sized>(visitor: &mut V, footnote: &mut Footnote) {
+    walk_blocks_mut(visitor, &mut footnote.children);
+}
+
+pub fn walk_plain_mut<V: VisitMut + ?Sized>(visitor: &mut V, children: &mut [Inline]) {
+    walk_inlines_mut(visitor, children);
+}
+
+pub fn walk_bold_mut<V: VisitMut + ?Sized>(visitor: &mut V, bold: &mut BoldText) {
+    walk_inlines_mut(visitor, &mut bold.children);
+}
+
+pub fn walk_italics_mut<V: VisitMut + ?Sized>(visitor: &mut V, italics: &mut ItalicizedText) {
+    walk_inlines_mut(visitor, &mut italics.children);
+}
+
+pub fn walk_link_mut<V: VisitMut + ?Sized>(visitor: &mut V, link: &mut Link) {
+    walk_inlines_mut(visitor, &mut link.children);
+}
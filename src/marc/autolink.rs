@@ -0,0 +1,149 @@
+/// `autolink_len` returns the (byte) length of the autolink (`<https://example.com>` or
+/// `<me@example.com>`, with its angle brackets) at the start of `text`, if there's one.
+pub fn autolink_len(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix('<')?;
+    let end = rest.find(['>', '<', ' ', '\t', '\n'])?;
+    if !rest[end..].starts_with('>') {
+        return None;
+    }
+
+    let inner = &rest[..end];
+    if is_absolute_uri(inner) || email_len(inner) == Some(inner.len()) {
+        Some(end + 2)
+    } else {
+        None
+    }
+}
+
+/// `autolink_destination` returns the destination of an autolink given its text (without the
+/// angle brackets) or the text of an extended autolink: email addresses get a `mailto:` and
+/// `www.` links get an `http://` in front of them.
+pub fn autolink_destination(text: &str) -> String {
+    if is_absolute_uri(text) {
+        text.to_string()
+    } else if text.starts_with("www.") {
+        format!("http://{}", text)
+    } else {
+        format!("mailto:{}", text)
+    }
+}
+
+/// `is_absolute_uri` reports whether `text` is a scheme (2 to 32 letters, digits, `+`, `.` or
+/// `-`, starting with a letter) followed by `:` and no spaces or control characters.
+fn is_absolute_uri(text: &str) -> bool {
+    let Some(colon) = text.find(':') else {
+        return false;
+    };
+
+    let scheme = &text[..colon];
+    (2..=32).contains(&scheme.len())
+        && scheme.starts_with(|ch: char| ch.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || "+.-".contains(ch))
+        && !text[colon..]
+            .chars()
+            .any(|ch| ch.is_whitespace() || ch.is_control() || ch == '<' || ch == '>')
+}
+
+/// `extended_autolink_len` returns the (byte) length of the bare url (`https://...`,
+/// `http://...` or `www....`) or email address at the start of `text`, if there's one.
+///
+/// trailing punctuation (eg. the `.` that ends a sentence or the `)` closing a parenthesized
+/// url) isn't part of the link.
+pub fn extended_autolink_len(text: &str) -> Option<usize> {
+    let domain_start = ["https://", "http://", "www."]
+        .iter()
+        .find(|prefix| text.starts_with(*prefix))
+        .map(|prefix| if *prefix == "www." { 0 } else { prefix.len() });
+
+    let Some(domain_start) = domain_start else {
+        return email_len(text);
+    };
+
+    let domain_len = domain_len(&text[domain_start..])?;
+    // the url also stops at a `]` so that a url in the text of a link doesn't run past it
+    let end = text[domain_start + domain_len..]
+        .find(|ch: char| ch.is_whitespace() || ch == '<' || ch == ']')
+        .map_or(text.len(), |index| domain_start + domain_len + index);
+
+    Some(trim_trailing_punctuation(&text[..end]))
+}
+
+/// `domain_len` returns the length of the domain at the start of `text`: segments of letters,
+/// digits, `_` and `-` separated by periods. there has to be at least one period and the last
+/// two segments can't have underscores.
+fn domain_len(text: &str) -> Option<usize> {
+    let len = text
+        .find(|ch: char| !(ch.is_alphanumeric() || "_-.".contains(ch)))
+        .unwrap_or(text.len());
+    let domain = text[..len].trim_end_matches('.');
+
+    let segments: Vec<&str> = domain.split('.').collect();
+    if segments.len() < 2 || segments.iter().any(|segment| segment.is_empty()) {
+        return None;
+    }
+    if segments[segments.len() - 2..]
+        .iter()
+        .any(|segment| segment.contains('_'))
+    {
+        return None;
+    }
+
+    Some(domain.len())
+}
+
+fn trim_trailing_punctuation(url: &str) -> usize {
+    let mut end = url.len();
+
+    loop {
+        let url = &url[..end];
+        let Some(last) = url.chars().next_back() else {
+            return end;
+        };
+
+        if "?!.,:*_~'\"".contains(last) {
+            end -= last.len_utf8();
+        } else if last == ')' && url.matches(')').count() > url.matches('(').count() {
+            // only the parentheses that don't close one opened in the url are removed
+            end -= 1;
+        } else if last == ';'
+            && let Some(ampersand) = url.rfind('&')
+            && url[ampersand + 1..end - 1]
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric())
+        {
+            // something that looks like an entity reference (`&amp;`) at the end isn't part of
+            // the url
+            end = ampersand;
+        } else {
+            return end;
+        }
+    }
+}
+
+/// `email_len` returns the length of the email address at the start of `text`, if there's one.
+fn email_len(text: &str) -> Option<usize> {
+    let local_len = text
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ".-_+".contains(ch)))
+        .unwrap_or(text.len());
+    if local_len == 0 || !text[local_len..].starts_with('@') {
+        return None;
+    }
+
+    let domain = &text[local_len + 1..];
+    let domain_len = domain
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || "-_.".contains(ch)))
+        .unwrap_or(domain.len());
+    let domain = domain[..domain_len].trim_end_matches('.');
+
+    // the last character can't be `-` or `_`, and there has to be at least one period
+    if !domain.contains('.')
+        || domain.split('.').any(|segment| segment.is_empty())
+        || domain.ends_with(['-', '_'])
+    {
+        return None;
+    }
+
+    Some(local_len + 1 + domain.len())
+}
@@ -0,0 +1,80 @@
+use crate::marc::ast::{Node, container::Container};
+use crate::marc::options::RenderOptions;
+use crate::marc::render::html::source_position;
+use crate::utils;
+
+/// `ContainerRenderer` writes out the containers (`:::name ... :::`) with one of its names,
+/// instead of the default `<div class="name">`. it is how components like tabs, columns or
+/// collapsible sections are added to marc.
+pub trait ContainerRenderer {
+    /// the names of the containers the renderer writes out
+    fn names(&self) -> &[&str];
+    /// `render` returns the html of `container`, given `content`, the html of the markdown in
+    /// it.
+    fn render(&self, container: &Container, content: &str, options: &RenderOptions) -> String;
+}
+
+/// `Containers` holds the renderers of named containers.
+///
+/// `Containers::default()` knows the renderers in this module. more renderers can be added with
+/// [`Containers::register`].
+pub struct Containers {
+    renderers: Vec<Box<dyn ContainerRenderer>>,
+}
+
+impl Containers {
+    /// `new` creates a registry without any renderer, ie. every container is a `<div>`
+    pub fn new() -> Self {
+        Containers { renderers: vec![] }
+    }
+
+    /// `register` adds a renderer. a renderer registered later takes precedence over earlier
+    /// ones that share a name with it.
+    pub fn register(&mut self, renderer: impl ContainerRenderer + 'static) {
+        self.renderers.push(Box::new(renderer));
+    }
+
+    pub fn renderer(&self, name: &str) -> Option<&dyn ContainerRenderer> {
+        self.renderers
+            .iter()
+            .rev()
+            .find(|renderer| renderer.names().contains(&name))
+            .map(|renderer| renderer.as_ref())
+    }
+}
+
+impl Default for Containers {
+    fn default() -> Self {
+        let mut containers = Containers::new();
+        containers.register(Details);
+        containers
+    }
+}
+
+/// `Details` writes a `:::details Summary` container out as a collapsible section, with its
+/// argument as the summary that is shown while it is closed.
+pub struct Details;
+
+impl ContainerRenderer for Details {
+    fn names(&self) -> &[&str] {
+        &["details"]
+    }
+
+    fn render(&self, container: &Container, content: &str, options: &RenderOptions) -> String {
+        let summary = match container.argument() {
+            "" => "Details",
+            argument => argument,
+        };
+        let attributes = container
+            .attributes()
+            .map(|attributes| attributes.html(&[]))
+            .unwrap_or_default();
+        format!(
+            "<details{}{}><summary>{}</summary>{}</details>",
+            attributes,
+            source_position(container.span(), options),
+            utils::escape_html(summary),
+            content
+        )
+    }
+}
@@ -0,0 +1,216 @@
+/// the named character references that are recognized, with the characters they stand for.
+/// it is the commonly used part of the html list; other names are left as they are.
+const NAMED_ENTITIES: &[(&str, &str)] = &[
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("quot", "\""),
+    ("apos", "'"),
+    ("nbsp", "\u{a0}"),
+    ("ensp", "\u{2002}"),
+    ("emsp", "\u{2003}"),
+    ("thinsp", "\u{2009}"),
+    ("zwnj", "\u{200c}"),
+    ("zwj", "\u{200d}"),
+    ("shy", "\u{ad}"),
+    ("copy", "©"),
+    ("reg", "®"),
+    ("trade", "™"),
+    ("sect", "§"),
+    ("para", "¶"),
+    ("deg", "°"),
+    ("plusmn", "±"),
+    ("micro", "µ"),
+    ("middot", "·"),
+    ("bull", "•"),
+    ("hellip", "…"),
+    ("prime", "′"),
+    ("Prime", "″"),
+    ("dagger", "†"),
+    ("Dagger", "‡"),
+    ("permil", "‰"),
+    ("ndash", "–"),
+    ("mdash", "—"),
+    ("lsquo", "‘"),
+    ("rsquo", "’"),
+    ("sbquo", "‚"),
+    ("ldquo", "“"),
+    ("rdquo", "”"),
+    ("bdquo", "„"),
+    ("laquo", "«"),
+    ("raquo", "»"),
+    ("lsaquo", "‹"),
+    ("rsaquo", "›"),
+    ("iexcl", "¡"),
+    ("iquest", "¿"),
+    ("cent", "¢"),
+    ("pound", "£"),
+    ("yen", "¥"),
+    ("euro", "€"),
+    ("curren", "¤"),
+    ("times", "×"),
+    ("divide", "÷"),
+    ("frac12", "½"),
+    ("frac14", "¼"),
+    ("frac34", "¾"),
+    ("sup1", "¹"),
+    ("sup2", "²"),
+    ("sup3", "³"),
+    ("not", "¬"),
+    ("minus", "−"),
+    ("le", "≤"),
+    ("ge", "≥"),
+    ("ne", "≠"),
+    ("asymp", "≈"),
+    ("equiv", "≡"),
+    ("infin", "∞"),
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("radic", "√"),
+    ("int", "∫"),
+    ("part", "∂"),
+    ("nabla", "∇"),
+    ("forall", "∀"),
+    ("exist", "∃"),
+    ("empty", "∅"),
+    ("isin", "∈"),
+    ("notin", "∉"),
+    ("cap", "∩"),
+    ("cup", "∪"),
+    ("sub", "⊂"),
+    ("sup", "⊃"),
+    ("and", "∧"),
+    ("or", "∨"),
+    ("larr", "←"),
+    ("uarr", "↑"),
+    ("rarr", "→"),
+    ("darr", "↓"),
+    ("harr", "↔"),
+    ("lArr", "⇐"),
+    ("rArr", "⇒"),
+    ("hArr", "⇔"),
+    ("spades", "♠"),
+    ("clubs", "♣"),
+    ("hearts", "♥"),
+    ("diams", "♦"),
+    ("check", "✓"),
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ε"),
+    ("theta", "θ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("pi", "π"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("phi", "φ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Phi", "Φ"),
+    ("Omega", "Ω"),
+    ("Agrave", "À"),
+    ("Aacute", "Á"),
+    ("Auml", "Ä"),
+    ("Aring", "Å"),
+    ("AElig", "Æ"),
+    ("Ccedil", "Ç"),
+    ("Eacute", "É"),
+    ("Ntilde", "Ñ"),
+    ("Ouml", "Ö"),
+    ("Oslash", "Ø"),
+    ("Uuml", "Ü"),
+    ("szlig", "ß"),
+    ("agrave", "à"),
+    ("aacute", "á"),
+    ("acirc", "â"),
+    ("auml", "ä"),
+    ("aring", "å"),
+    ("aelig", "æ"),
+    ("ccedil", "ç"),
+    ("egrave", "è"),
+    ("eacute", "é"),
+    ("ecirc", "ê"),
+    ("euml", "ë"),
+    ("iacute", "í"),
+    ("iuml", "ï"),
+    ("ntilde", "ñ"),
+    ("oacute", "ó"),
+    ("ocirc", "ô"),
+    ("ouml", "ö"),
+    ("oslash", "ø"),
+    ("uacute", "ú"),
+    ("uuml", "ü"),
+];
+
+/// `entity_len` returns the (byte) length of the entity reference (`&copy;`, `&#169;` or
+/// `&#xA9;`) at the start of `text`, if there's one.
+pub fn entity_len(text: &str) -> Option<usize> {
+    let end = text.find(|ch: char| ch == ';' || ch.is_whitespace())?;
+    decode(&text[..=end]).map(|_| end + 1)
+}
+
+/// `decode` returns the character(s) the entity reference `entity` stands for, or `None` if it
+/// isn't a valid (or known) entity reference. numeric references to characters that can't be
+/// written out (eg. `&#0;`) stand for the replacement character.
+pub fn decode(entity: &str) -> Option<String> {
+    let name = entity.strip_prefix('&')?.strip_suffix(';')?;
+
+    let number = if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        if hex.is_empty() || hex.len() > 6 || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(hex, 16).ok()?
+    } else if let Some(decimal) = name.strip_prefix('#') {
+        if decimal.is_empty() || decimal.len() > 7 || !decimal.chars().all(|ch| ch.is_ascii_digit())
+        {
+            return None;
+        }
+        decimal.parse().ok()?
+    } else {
+        return NAMED_ENTITIES
+            .iter()
+            .find(|(entity_name, _)| *entity_name == name)
+            .map(|(_, value)| value.to_string());
+    };
+
+    let ch = match number {
+        0 => char::REPLACEMENT_CHARACTER,
+        number => char::from_u32(number).unwrap_or(char::REPLACEMENT_CHARACTER),
+    };
+    Some(ch.to_string())
+}
+
+/// `unescape` replaces the backslash escapes (`\*`) and entity references (`&amp;`) in `text`
+/// with the characters they stand for. it is used for the parts of the document that aren't
+/// parsed into nodes, like the destination and title of a link.
+pub fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(ch) = rest.chars().next() {
+        if ch == '\\'
+            && let Some(escaped) = rest[1..].chars().next()
+            && escaped.is_ascii_punctuation()
+        {
+            unescaped.push(escaped);
+            rest = &rest[1 + escaped.len_utf8()..];
+        } else if ch == '&'
+            && let Some(len) = entity_len(rest)
+        {
+            unescaped.push_str(&decode(&rest[..len]).unwrap_or_default());
+            rest = &rest[len..];
+        } else {
+            unescaped.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+
+    unescaped
+}
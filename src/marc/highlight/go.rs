@@ -0,0 +1,82 @@
+use super::{
+    Grammar, Segment,
+    scanner::{self, Rules},
+};
+
+const RULES: Rules = Rules {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "type",
+        "var",
+    ],
+    types: &[
+        "any",
+        "bool",
+        "byte",
+        "complex64",
+        "complex128",
+        "error",
+        "float32",
+        "float64",
+        "int",
+        "int8",
+        "int16",
+        "int32",
+        "int64",
+        "rune",
+        "string",
+        "uint",
+        "uint8",
+        "uint16",
+        "uint32",
+        "uint64",
+        "uintptr",
+    ],
+    constants: &["true", "false", "nil", "iota"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    // backticks delimit raw strings
+    string_delimiters: &['"', '\'', '`'],
+    // capitalized names are exported in go, not types
+    capitalized_types: false,
+    macros: false,
+    char_literals: false,
+    variables: false,
+    commands: false,
+    attribute_prefix: None,
+    identifier_chars: &[],
+};
+
+pub struct Go;
+
+impl Grammar for Go {
+    fn names(&self) -> &[&str] {
+        &["go", "golang"]
+    }
+
+    fn tokenize<'a>(&self, code: &'a str) -> Vec<Segment<'a>> {
+        scanner::scan(&RULES, code)
+    }
+}
@@ -0,0 +1,99 @@
+use super::{
+    Grammar, Segment,
+    scanner::{self, Rules},
+};
+
+const RULES: Rules = Rules {
+    // typescript keywords are included so the same grammar works for both languages
+    keywords: &[
+        "abstract",
+        "as",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "debugger",
+        "declare",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "get",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "keyof",
+        "let",
+        "namespace",
+        "new",
+        "of",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "return",
+        "set",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "try",
+        "type",
+        "typeof",
+        "var",
+        "void",
+        "while",
+        "with",
+        "yield",
+    ],
+    types: &[
+        "any", "bigint", "boolean", "never", "number", "object", "string", "symbol", "unknown",
+    ],
+    constants: &["true", "false", "null", "undefined", "NaN", "Infinity"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    string_delimiters: &['"', '\'', '`'],
+    capitalized_types: true,
+    macros: false,
+    char_literals: false,
+    variables: false,
+    commands: false,
+    attribute_prefix: Some("@"),
+    identifier_chars: &['$'],
+};
+
+pub struct JavaScript;
+
+impl Grammar for JavaScript {
+    fn names(&self) -> &[&str] {
+        &[
+            "javascript",
+            "js",
+            "jsx",
+            "mjs",
+            "cjs",
+            "typescript",
+            "ts",
+            "tsx",
+        ]
+    }
+
+    fn tokenize<'a>(&self, code: &'a str) -> Vec<Segment<'a>> {
+        scanner::scan(&RULES, code)
+    }
+}
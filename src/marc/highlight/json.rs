@@ -0,0 +1,77 @@
+use super::{Grammar, Highlight, Segment};
+
+pub struct Json;
+
+impl Grammar for Json {
+    fn names(&self) -> &[&str] {
+        &["json", "jsonc", "json5"]
+    }
+
+    fn tokenize<'a>(&self, code: &'a str) -> Vec<Segment<'a>> {
+        let mut segments = vec![];
+        let mut position = 0;
+        let mut plain_start = 0;
+
+        while position < code.len() {
+            let rest = &code[position..];
+            let ch = rest.chars().next().unwrap();
+
+            let token = match ch {
+                '"' => {
+                    let len = string_len(rest);
+                    // a string followed by `:` is the key of an object member
+                    let is_key = rest[len..].trim_start().starts_with(':');
+                    let highlight = if is_key {
+                        Highlight::Property
+                    } else {
+                        Highlight::String
+                    };
+                    Some((highlight, len))
+                }
+                '-' | '0'..='9' => {
+                    let len = rest
+                        .char_indices()
+                        .skip(1)
+                        .find(|(_, ch)| !(ch.is_ascii_digit() || "eE+-.".contains(*ch)))
+                        .map_or(rest.len(), |(index, _)| index);
+                    Some((Highlight::Number, len))
+                }
+                _ => ["true", "false", "null"]
+                    .iter()
+                    .find(|word| rest.starts_with(**word))
+                    .map(|word| (Highlight::Constant, word.len())),
+            };
+
+            match token {
+                Some((highlight, len)) => {
+                    if plain_start < position {
+                        segments.push(Segment::new(Highlight::Plain, &code[plain_start..position]));
+                    }
+                    segments.push(Segment::new(highlight, &rest[..len]));
+                    position += len;
+                    plain_start = position;
+                }
+                None => position += ch.len_utf8(),
+            }
+        }
+
+        if plain_start < code.len() {
+            segments.push(Segment::new(Highlight::Plain, &code[plain_start..]));
+        }
+        segments
+    }
+}
+
+fn string_len(text: &str) -> usize {
+    let mut escaped = false;
+    for (index, ch) in text.char_indices().skip(1) {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return index + 1,
+            '\n' => return index,
+            _ => {}
+        }
+    }
+    text.len()
+}
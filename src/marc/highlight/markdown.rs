@@ -0,0 +1,150 @@
+use super::{Grammar, Highlight, Segment};
+
+/// `Markdown` highlights markdown (and marc) source: headings, block markers (list bullets,
+/// quotes), fenced code, and the common inline elements.
+pub struct Markdown;
+
+impl Grammar for Markdown {
+    fn names(&self) -> &[&str] {
+        &["markdown", "md", "marc"]
+    }
+
+    fn tokenize<'a>(&self, code: &'a str) -> Vec<Segment<'a>> {
+        let mut segments = vec![];
+        let mut fence: Option<&str> = None;
+
+        for line in code.split_inclusive('\n') {
+            let (content, ending) = match line.strip_suffix('\n') {
+                Some(content) => (content, &line[content.len()..]),
+                None => (line, ""),
+            };
+            let trimmed = content.trim_start();
+
+            if let Some(marker) = fence {
+                // everything inside a fenced code block is code, including the closing fence
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                segments.push(Segment::new(Highlight::Code, content));
+            } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fence = Some(&trimmed[..3]);
+                segments.push(Segment::new(Highlight::Code, content));
+            } else if is_heading(trimmed) {
+                segments.push(Segment::new(Highlight::Heading, content));
+            } else {
+                let marker_len = block_marker_len(content);
+                if marker_len > 0 {
+                    segments.push(Segment::new(Highlight::Keyword, &content[..marker_len]));
+                }
+                tokenize_inline(&content[marker_len..], &mut segments);
+            }
+
+            if !ending.is_empty() {
+                segments.push(Segment::new(Highlight::Plain, ending));
+            }
+        }
+
+        segments.retain(|segment| !segment.text.is_empty());
+        segments
+    }
+}
+
+fn is_heading(line: &str) -> bool {
+    let pounds = line.chars().take_while(|ch| *ch == '#').count();
+    (1..=6).contains(&pounds) && line[pounds..].chars().next().is_none_or(|ch| ch == ' ')
+}
+
+/// `block_marker_len` returns the length of the list bullet, ordered list number or quote
+/// marker (with the indentation before it) at the start of the line, or 0 if there isn't one.
+fn block_marker_len(line: &str) -> usize {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+
+    if rest.starts_with("> ") || rest == ">" {
+        return indent + 1;
+    }
+
+    if ["- ", "* ", "+ "]
+        .iter()
+        .any(|bullet| rest.starts_with(bullet))
+    {
+        return indent + 1;
+    }
+
+    let digits = rest.chars().take_while(|ch| ch.is_ascii_digit()).count();
+    if digits > 0 && (rest[digits..].starts_with(". ") || rest[digits..].starts_with(") ")) {
+        return indent + digits + 1;
+    }
+
+    0
+}
+
+fn tokenize_inline<'a>(text: &'a str, segments: &mut Vec<Segment<'a>>) {
+    let mut position = 0;
+    let mut plain_start = 0;
+
+    while position < text.len() {
+        let rest = &text[position..];
+        let ch = rest.chars().next().unwrap();
+
+        let token = match ch {
+            '`' => closing_len(rest, "`").map(|len| (Highlight::Code, len)),
+            '*' | '_' => {
+                let strong = if ch == '*' { "**" } else { "__" };
+                if rest.starts_with(strong) {
+                    closing_len(rest, strong).map(|len| (Highlight::Strong, len))
+                } else {
+                    closing_len(rest, &rest[..1]).map(|len| (Highlight::Emphasis, len))
+                }
+            }
+            '[' | '!' => link_len(rest).map(|len| (Highlight::Link, len)),
+            '<' => closing_len(rest, ">")
+                .filter(|len| rest[1..*len].contains(':'))
+                .map(|len| (Highlight::Link, len)),
+            _ => None,
+        };
+
+        match token {
+            Some((highlight, len)) => {
+                if plain_start < position {
+                    segments.push(Segment::new(Highlight::Plain, &text[plain_start..position]));
+                }
+                segments.push(Segment::new(highlight, &rest[..len]));
+                position += len;
+                plain_start = position;
+            }
+            None => position += ch.len_utf8(),
+        }
+    }
+
+    if plain_start < text.len() {
+        segments.push(Segment::new(Highlight::Plain, &text[plain_start..]));
+    }
+}
+
+/// `closing_len` returns the length of `text` up to and including the first `delimiter` after
+/// the opening one at its start, if there's anything between them.
+fn closing_len(text: &str, delimiter: &str) -> Option<usize> {
+    let open = if delimiter == ">" { 1 } else { delimiter.len() };
+    let close = text[open..].find(delimiter)?;
+    if close == 0 {
+        return None;
+    }
+    Some(open + close + delimiter.len())
+}
+
+/// `link_len` returns the length of an inline link or image (`[text](url)`, `![alt](src)`)
+/// at the start of `text`.
+fn link_len(text: &str) -> Option<usize> {
+    let start = if text.starts_with("![") {
+        2
+    } else if text.starts_with('[') {
+        1
+    } else {
+        return None;
+    };
+
+    let text_end = start + text[start..].find("](")?;
+    let url_end = text_end + 2 + text[text_end + 2..].find(')')?;
+    Some(url_end + 1)
+}
@@ -0,0 +1,169 @@
+use crate::utils;
+
+pub mod go;
+pub mod javascript;
+pub mod json;
+pub mod markdown;
+pub mod rust;
+pub mod scanner;
+pub mod shell;
+
+/// `Highlight` is the kind of a piece of highlighted code. every kind except `Plain` is written
+/// out as a `<span>` with the class returned by [`Highlight::class`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Highlight {
+    Plain,
+    Keyword,
+    Type,
+    Function,
+    Macro,
+    String,
+    Number,
+    Constant,
+    Comment,
+    Operator,
+    Variable,
+    Attribute,
+    Property,
+    Heading,
+    Emphasis,
+    Strong,
+    Code,
+    Link,
+}
+
+impl Highlight {
+    pub fn class(&self) -> Option<&'static str> {
+        match self {
+            Highlight::Plain => None,
+            Highlight::Keyword => Some("hl-keyword"),
+            Highlight::Type => Some("hl-type"),
+            Highlight::Function => Some("hl-function"),
+            Highlight::Macro => Some("hl-macro"),
+            Highlight::String => Some("hl-string"),
+            Highlight::Number => Some("hl-number"),
+            Highlight::Constant => Some("hl-constant"),
+            Highlight::Comment => Some("hl-comment"),
+            Highlight::Operator => Some("hl-operator"),
+            Highlight::Variable => Some("hl-variable"),
+            Highlight::Attribute => Some("hl-attribute"),
+            Highlight::Property => Some("hl-property"),
+            Highlight::Heading => Some("hl-heading"),
+            Highlight::Emphasis => Some("hl-emphasis"),
+            Highlight::Strong => Some("hl-strong"),
+            Highlight::Code => Some("hl-code"),
+            Highlight::Link => Some("hl-link"),
+        }
+    }
+}
+
+/// `Segment` is a slice of the source code and what kind of code it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment<'a> {
+    pub highlight: Highlight,
+    pub text: &'a str,
+}
+
+impl<'a> Segment<'a> {
+    pub fn new(highlight: Highlight, text: &'a str) -> Self {
+        Segment { highlight, text }
+    }
+}
+
+/// `Grammar` is implemented by every language the highlighter understands.
+///
+/// `tokenize` splits the code into segments. joining the text of the segments must give back
+/// the original code.
+pub trait Grammar {
+    /// the names (and aliases) used for the language in the info string of a fenced code block
+    fn names(&self) -> &[&str];
+    fn tokenize<'a>(&self, code: &'a str) -> Vec<Segment<'a>>;
+}
+
+/// `Highlighter` turns code into html with every segment wrapped in a classed `<span>`, using
+/// the grammar registered for the language of the code.
+///
+/// `Highlighter::default()` knows all the grammars in this module. more grammars can be added
+/// with [`Highlighter::register`].
+pub struct Highlighter {
+    grammars: Vec<Box<dyn Grammar>>,
+}
+
+impl Highlighter {
+    /// `new` creates a highlighter without any grammar, ie. one that doesn't highlight anything
+    pub fn new() -> Self {
+        Highlighter { grammars: vec![] }
+    }
+
+    /// `register` adds a grammar to the highlighter. a grammar registered later takes
+    /// precedence over earlier ones that share a name with it.
+    pub fn register(&mut self, grammar: impl Grammar + 'static) {
+        self.grammars.push(Box::new(grammar));
+    }
+
+    pub fn grammar(&self, language: &str) -> Option<&dyn Grammar> {
+        self.grammars
+            .iter()
+            .rev()
+            .find(|grammar| {
+                grammar
+                    .names()
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(language))
+            })
+            .map(|grammar| grammar.as_ref())
+    }
+
+    /// `highlight` returns the highlighted html for `code`, or `None` if there's no grammar for
+    /// `language`.
+    pub fn highlight(&self, language: &str, code: &str) -> Option<String> {
+        self.highlight_lines(language, code)
+            .map(|lines| lines.join("\n"))
+    }
+
+    /// `highlight_lines` is like `highlight`, but returns the html of each line separately.
+    /// segments that span several lines (eg. block comments) are closed at the end of each line
+    /// and reopened on the next, so every line is valid html on its own.
+    pub fn highlight_lines(&self, language: &str, code: &str) -> Option<Vec<String>> {
+        let grammar = self.grammar(language)?;
+        let mut lines = vec![String::new()];
+
+        for segment in grammar.tokenize(code) {
+            for (index, part) in segment.text.split('\n').enumerate() {
+                if index > 0 {
+                    lines.push(String::new());
+                }
+                if part.is_empty() {
+                    continue;
+                }
+
+                let line = lines.last_mut().unwrap();
+                match segment.highlight.class() {
+                    Some(class) => {
+                        line.push_str(&format!(
+                            "<span class=\"{}\">{}</span>",
+                            class,
+                            utils::escape_html(part)
+                        ));
+                    }
+                    None => line.push_str(&utils::escape_html(part)),
+                }
+            }
+        }
+
+        Some(lines)
+    }
+}
+
+impl Default for Highlighter {
+    fn default() -> Self {
+        let mut highlighter = Highlighter::new();
+        highlighter.register(rust::Rust);
+        highlighter.register(go::Go);
+        highlighter.register(javascript::JavaScript);
+        highlighter.register(shell::Shell);
+        highlighter.register(json::Json);
+        highlighter.register(markdown::Markdown);
+        highlighter
+    }
+}
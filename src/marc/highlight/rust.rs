@@ -0,0 +1,40 @@
+use super::{
+    Grammar, Segment,
+    scanner::{self, Rules},
+};
+
+const RULES: Rules = Rules {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while", "yield",
+    ],
+    types: &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64", "bool", "char", "str",
+    ],
+    constants: &["true", "false"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    string_delimiters: &['"', '\''],
+    capitalized_types: true,
+    macros: true,
+    char_literals: true,
+    variables: false,
+    commands: false,
+    attribute_prefix: Some("#["),
+    identifier_chars: &[],
+};
+
+pub struct Rust;
+
+impl Grammar for Rust {
+    fn names(&self) -> &[&str] {
+        &["rust", "rs"]
+    }
+
+    fn tokenize<'a>(&self, code: &'a str) -> Vec<Segment<'a>> {
+        scanner::scan(&RULES, code)
+    }
+}
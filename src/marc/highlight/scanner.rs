@@ -0,0 +1,303 @@
+use super::{Highlight, Segment};
+
+/// `Rules` describes the parts of a (mostly C-like) language that `scan` needs to know about to
+/// highlight it. most of the built-in grammars are just a set of rules.
+pub struct Rules {
+    pub keywords: &'static [&'static str],
+    pub types: &'static [&'static str],
+    pub constants: &'static [&'static str],
+    pub line_comments: &'static [&'static str],
+    pub block_comment: Option<(&'static str, &'static str)>,
+    pub string_delimiters: &'static [char],
+    /// identifiers starting with an uppercase letter are highlighted as types
+    pub capitalized_types: bool,
+    /// `name!` is a macro call, eg. `println!` in rust
+    pub macros: bool,
+    /// `'` only starts a char literal when it is closed right after the char (so rust lifetimes
+    /// like `'a` are left alone)
+    pub char_literals: bool,
+    /// `$name` and `${...}` are variables, eg. in shell scripts
+    pub variables: bool,
+    /// the first word of a command is highlighted as a function, eg. `cargo` in `cargo build`
+    pub commands: bool,
+    /// the prefix of attributes and decorators, eg. `#[` in rust or `@` in javascript
+    pub attribute_prefix: Option<&'static str>,
+    /// characters other than letters, digits and `_` that can be part of an identifier (eg. `$`
+    /// in javascript or `-` in shell commands like `apt-get`)
+    pub identifier_chars: &'static [char],
+}
+
+impl Rules {
+    fn is_identifier_start(&self, ch: char) -> bool {
+        ch.is_alphabetic() || ch == '_' || self.identifier_chars.contains(&ch)
+    }
+
+    fn is_identifier_char(&self, ch: char) -> bool {
+        ch.is_alphanumeric() || ch == '_' || self.identifier_chars.contains(&ch)
+    }
+}
+
+const OPERATORS: &str = "+-*/%=<>!&|^~?:";
+const DEFINITION_KEYWORDS: [&str; 3] = ["fn", "func", "function"];
+
+/// `scan` splits `code` into segments using `rules`.
+pub fn scan<'a>(rules: &Rules, code: &'a str) -> Vec<Segment<'a>> {
+    let mut segments: Vec<Segment<'a>> = vec![];
+    let mut position = 0;
+    let mut plain_start = 0;
+    let mut command_start = true;
+    let mut after_definition = false;
+
+    while position < code.len() {
+        let ch = code[position..].chars().next().unwrap();
+        let prev = code[..position].chars().next_back();
+
+        match next_token(rules, code, position, prev, command_start) {
+            Some((Highlight::Plain, len)) if after_definition => {
+                // the name after `fn` (or `func`, `function`) is the name of a function even
+                // when it isn't followed by `(`, eg. `fn parse<'a>(...)`
+                after_definition = false;
+                if plain_start < position {
+                    segments.push(Segment::new(Highlight::Plain, &code[plain_start..position]));
+                }
+                segments.push(Segment::new(
+                    Highlight::Function,
+                    &code[position..position + len],
+                ));
+                command_start = false;
+                position += len;
+                plain_start = position;
+            }
+            Some((Highlight::Plain, len)) => {
+                command_start = false;
+                after_definition = false;
+                position += len;
+            }
+            Some((highlight, len)) => {
+                after_definition = highlight == Highlight::Keyword
+                    && DEFINITION_KEYWORDS.contains(&&code[position..position + len]);
+                if plain_start < position {
+                    segments.push(Segment::new(Highlight::Plain, &code[plain_start..position]));
+                }
+                segments.push(Segment::new(highlight, &code[position..position + len]));
+
+                // a keyword (`then`, `do`) or a pipe/`&&` is followed by another command
+                command_start = highlight == Highlight::Keyword
+                    || (highlight == Highlight::Operator
+                        && ends_command(&code[position..position + len]));
+                position += len;
+                plain_start = position;
+            }
+            None => {
+                if !ch.is_whitespace() {
+                    after_definition = false;
+                }
+                if matches!(ch, '\n' | ';' | '|' | '&' | '(' | '{') {
+                    command_start = true;
+                } else if !ch.is_whitespace() {
+                    command_start = false;
+                }
+                position += ch.len_utf8();
+            }
+        }
+    }
+
+    if plain_start < code.len() {
+        segments.push(Segment::new(Highlight::Plain, &code[plain_start..]));
+    }
+
+    segments
+}
+
+fn ends_command(operator: &str) -> bool {
+    operator.contains('|') || operator.contains('&')
+}
+
+/// `next_token` returns the kind and (byte) length of the token starting at `position`, or
+/// `None` if the char at `position` isn't the start of any token worth highlighting.
+fn next_token(
+    rules: &Rules,
+    code: &str,
+    position: usize,
+    prev: Option<char>,
+    command_start: bool,
+) -> Option<(Highlight, usize)> {
+    let rest = &code[position..];
+    let ch = rest.chars().next()?;
+
+    for marker in rules.line_comments {
+        // `#` only starts a comment at the start of a word, so `a#b` in a shell script isn't one
+        let at_word_start = prev.is_none_or(|prev| prev.is_whitespace());
+        if rest.starts_with(marker) && (*marker != "#" || at_word_start) {
+            return Some((Highlight::Comment, rest.find('\n').unwrap_or(rest.len())));
+        }
+    }
+
+    if let Some((start, end)) = rules.block_comment
+        && let Some(comment) = rest.strip_prefix(start)
+    {
+        let len = match comment.find(end) {
+            Some(index) => start.len() + index + end.len(),
+            None => rest.len(),
+        };
+        return Some((Highlight::Comment, len));
+    }
+
+    if let Some(prefix) = rules.attribute_prefix
+        && rest.starts_with(prefix)
+    {
+        return Some((Highlight::Attribute, attribute_len(rules, rest, prefix)));
+    }
+
+    if rules.string_delimiters.contains(&ch) {
+        if ch == '\'' && rules.char_literals {
+            return char_literal_len(rest).map(|len| (Highlight::String, len));
+        }
+        return Some((Highlight::String, string_len(rest, ch)));
+    }
+
+    if rules.variables && ch == '$' {
+        return variable_len(rest).map(|len| (Highlight::Variable, len));
+    }
+
+    if ch.is_ascii_digit() {
+        return Some((Highlight::Number, number_len(rest)));
+    }
+
+    if rules.is_identifier_start(ch) {
+        let len = rest
+            .find(|ch| !rules.is_identifier_char(ch))
+            .unwrap_or(rest.len());
+        let word = &rest[..len];
+        let next = rest[len..].chars().next();
+
+        let highlight = if rules.keywords.contains(&word) {
+            Highlight::Keyword
+        } else if rules.constants.contains(&word) {
+            Highlight::Constant
+        } else if rules.types.contains(&word) {
+            Highlight::Type
+        } else if rules.macros && next == Some('!') {
+            return Some((Highlight::Macro, len + 1));
+        } else if next == Some('(') || (rules.commands && command_start) {
+            Highlight::Function
+        } else if rules.capitalized_types && word.starts_with(|ch: char| ch.is_uppercase()) {
+            Highlight::Type
+        } else {
+            Highlight::Plain
+        };
+
+        return Some((highlight, len));
+    }
+
+    if OPERATORS.contains(ch) {
+        let len = rest
+            .find(|ch| !OPERATORS.contains(ch))
+            .unwrap_or(rest.len());
+        return Some((Highlight::Operator, len));
+    }
+
+    None
+}
+
+/// `string_len` returns the length of the string starting at the beginning of `text` (with its
+/// quotes). a backslash escapes the char after it. unterminated strings run to the end.
+fn string_len(text: &str, delimiter: char) -> usize {
+    let mut escaped = false;
+    for (index, ch) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == delimiter {
+            return index + ch.len_utf8();
+        }
+    }
+    text.len()
+}
+
+fn char_literal_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1);
+    let (_, ch) = chars.next()?;
+    let (index, closing) = if ch == '\\' {
+        // an escaped char like '\n' or '\''. longer escapes ('\u{..}') are read to the quote
+        let end = text.get(3..)?.find('\'')? + 3;
+        (end, '\'')
+    } else {
+        chars.next()?
+    };
+
+    if closing == '\'' {
+        Some(index + 1)
+    } else {
+        None
+    }
+}
+
+fn variable_len(text: &str) -> Option<usize> {
+    let rest = &text[1..];
+    if rest.starts_with('{') {
+        return Some(rest.find('}').map_or(text.len(), |index| index + 2));
+    }
+
+    let len = rest
+        .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .unwrap_or(rest.len());
+    if len == 0 {
+        // special parameters like `$?`, `$#` and `$1`
+        return rest
+            .chars()
+            .next()
+            .filter(|ch| "?#@*!$-0123456789".contains(*ch))
+            .map(|_| 2);
+    }
+    Some(len + 1)
+}
+
+fn number_len(text: &str) -> usize {
+    let mut len = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        let continues = if ch == '.' {
+            // `0..10` is a range, not a number followed by `.10`
+            chars.peek().is_some_and(|(_, next)| next.is_ascii_digit())
+        } else {
+            ch.is_ascii_alphanumeric() || ch == '_'
+        };
+
+        if !continues {
+            break;
+        }
+        len = index + ch.len_utf8();
+    }
+    len
+}
+
+fn attribute_len(rules: &Rules, text: &str, prefix: &str) -> usize {
+    if prefix.ends_with('[') {
+        // rust style attributes run to the matching `]`
+        let mut depth = 0;
+        for (index, ch) in text.char_indices() {
+            match ch {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return index + 1;
+                    }
+                }
+                '\n' => return index,
+                _ => {}
+            }
+        }
+        return text.len();
+    }
+
+    // decorators are the prefix followed by a (possibly dotted) name
+    let rest = &text[prefix.len()..];
+    prefix.len()
+        + rest
+            .find(|ch| !(rules.is_identifier_char(ch) || ch == '.'))
+            .unwrap_or(rest.len())
+}
@@ -0,0 +1,36 @@
+use super::{
+    Grammar, Segment,
+    scanner::{self, Rules},
+};
+
+const RULES: Rules = Rules {
+    keywords: &[
+        "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done", "case", "esac",
+        "in", "function", "select", "return", "exit", "export", "local", "readonly", "declare",
+        "source", "alias", "unset", "shift", "break", "continue", "sudo",
+    ],
+    types: &[],
+    constants: &[],
+    line_comments: &["#"],
+    block_comment: None,
+    string_delimiters: &['"', '\'', '`'],
+    capitalized_types: false,
+    macros: false,
+    char_literals: false,
+    variables: true,
+    commands: true,
+    attribute_prefix: None,
+    identifier_chars: &['-', '.'],
+};
+
+pub struct Shell;
+
+impl Grammar for Shell {
+    fn names(&self) -> &[&str] {
+        &["shell", "sh", "bash", "zsh", "console", "shellscript"]
+    }
+
+    fn tokenize<'a>(&self, code: &'a str) -> Vec<Segment<'a>> {
+        scanner::scan(&RULES, code)
+    }
+}
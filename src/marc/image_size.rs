@@ -0,0 +1,86 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// the number of bytes of an image file that are read at a time, until its size is found. it
+/// holds the header of a png or gif image.
+const CHUNK_SIZE: u64 = 4096;
+
+/// the most bytes that are read of an image file. the size of a jpeg image comes after its
+/// metadata segments (eg. exif data with a thumbnail), which can take up to 64 KiB each.
+const MAX_HEADER_SIZE: u64 = 1024 * 1024;
+
+/// `read_image_size` returns the (width, height) in pixels of the png, jpeg or gif image at
+/// `path`, read from the header of the file. it returns `None` if the file can't be read or
+/// isn't an image in one of these formats.
+pub fn read_image_size(path: &Path) -> Option<(u32, u32)> {
+    let mut file = File::open(path).ok()?.take(MAX_HEADER_SIZE);
+    let mut bytes = Vec::new();
+    loop {
+        let read = (&mut file).take(CHUNK_SIZE).read_to_end(&mut bytes).ok()?;
+        if let Some(size) = image_size(&bytes) {
+            return Some(size);
+        }
+        // only the frame of a jpeg image can be further into the file
+        if read == 0 || !bytes.starts_with(b"\xff\xd8") {
+            return None;
+        }
+    }
+}
+
+/// `image_size` returns the (width, height) in pixels of a png, jpeg or gif image, read from
+/// its header.
+pub fn image_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        // the IHDR chunk always comes first: its length, its type and then the width and height
+        if bytes.get(12..16)? != b"IHDR" {
+            return None;
+        }
+        Some((be_u32(bytes, 16)?, be_u32(bytes, 20)?))
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some((le_u16(bytes, 6)? as u32, le_u16(bytes, 8)? as u32))
+    } else if bytes.starts_with(b"\xff\xd8") {
+        jpeg_size(bytes)
+    } else {
+        None
+    }
+}
+
+/// `jpeg_size` walks the segments of a jpeg image up to its start of frame segment, which holds
+/// the size of the image.
+fn jpeg_size(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut i = 2;
+    loop {
+        if *bytes.get(i)? != 0xff {
+            return None;
+        }
+        let marker = *bytes.get(i + 1)?;
+        match marker {
+            // padding before a marker
+            0xff => i += 1,
+            // markers without a length (and content)
+            0x01 | 0xd0..=0xd7 => i += 2,
+            // the start of frame markers (0xc4, 0xc8 and 0xcc are other segments in that range)
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                let height = be_u16(bytes, i + 5)?;
+                let width = be_u16(bytes, i + 7)?;
+                return Some((width as u32, height as u32));
+            }
+            // the image data starts before any frame was found
+            0xd9 | 0xda => return None,
+            _ => i += 2 + be_u16(bytes, i + 2)? as usize,
+        }
+    }
+}
+
+fn be_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
@@ -1,5 +1,8 @@
 use std::cmp::min;
 
+use crate::marc::autolink;
+use crate::marc::entity;
+use crate::marc::math;
 use crate::marc::token::{Token, TokenType};
 use crate::utils;
 
@@ -39,19 +42,32 @@ impl Lexer {
         self.col
     }
 
-    /// `clean_input` removes only space characters from both ends of each line of the provided
-    /// input.
+    /// `clean_input` normalizes the line endings of the provided input to `\n` and expands the
+    /// tabs in the indentation of each line to spaces (with tab stops every 4 columns).
     ///
-    /// `clean_input` only removes space(' ') characters because other white space
-    /// characters like \t or \n can be useful in parts of the input. eg, a \n character indicates
-    /// the end of a new line and should not be removed.
+    /// leading white space is kept because it is meaningful in markdown (eg. a line indented by
+    /// 4 spaces is code), but expanding the tabs means the lexer and parser only ever have to
+    /// count spaces to know how far a line is indented.
     pub fn clean_input(input: &str) -> String {
-        //let s: String = input
-        //    .lines()
-        //    .map(|line| line.trim_matches(' ').to_owned() + "\n")
-        //    .collect();
-        //s.trim_end().to_owned()
-        input.to_owned()
+        input
+            .replace("\r\n", "\n")
+            .split('\n')
+            .map(|line| {
+                let indentation_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+                let mut cleaned = String::with_capacity(line.len());
+                for ch in line[..indentation_len].chars() {
+                    if ch == '\t' {
+                        let width = 4 - cleaned.len() % 4;
+                        cleaned.push_str(&" ".repeat(width));
+                    } else {
+                        cleaned.push(ch);
+                    }
+                }
+                cleaned.push_str(&line[indentation_len..]);
+                cleaned
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 
     pub fn eat_whitespace(&mut self) {
@@ -88,7 +104,33 @@ impl Lexer {
     //}
 
     pub fn next_token(&mut self) -> Token {
+        if self.col == 1
+            && let Some(token) = self.read_break_or_underline()
+        {
+            return token;
+        }
+        if self.col == 1
+            && let Some(token) = self.read_list_marker()
+        {
+            return token;
+        }
+
         let token = match self.ch {
+            Some(' ') if self.col == 1 && self.indentation() >= 4 => {
+                // a line indented by at least 4 spaces. the parser decides if it is an indented
+                // code block or just the continuation of a paragraph or list item.
+                let start = self.position;
+                let start_col = self.col;
+                while self.ch == Some(' ') {
+                    self.read_char();
+                }
+                return Token::new(
+                    TokenType::Indent,
+                    self.src[start..self.position].to_string(),
+                    self.line,
+                    start_col,
+                );
+            }
             Some('#') => {
                 if self.col > 1 {
                     // if the # char is not the first
@@ -141,24 +183,6 @@ impl Lexer {
                     Token::new(TokenType::GreaterThan, ">".to_string(), self.line, self.col)
                 }
             }
-            Some('-') => {
-                let start_line = self.line;
-                let start_col = self.col;
-                let token: Token;
-
-                if self.peek_char(1) != ' ' {
-                    token = Token::new(TokenType::Text, "-".to_string(), start_line, start_col);
-                } else {
-                    token = Token::new(
-                        TokenType::UnorderedListItem,
-                        "-".to_string(),
-                        start_line,
-                        start_col,
-                    )
-                }
-                self.read_char();
-                token
-            }
             Some('*') => {
                 let next_char = self.peek_char(1);
 
@@ -180,13 +204,14 @@ impl Lexer {
             Some('`') => {
                 let next = self.peek_char(1);
                 if next == '`' && self.peek_char(2) == '`' {
-                    let tok_type = if self.col == 1 {
-                        TokenType::TripleBacktick
-                    } else {
-                        TokenType::Text
-                    };
+                    if self.col == 1 {
+                        // fences can be longer than three backticks so that a fence can wrap
+                        // another (shorter) fence. the whole run is kept in the literal so the
+                        // parser can match the closing fence against it.
+                        return self.read_fence('`', TokenType::TripleBacktick);
+                    }
 
-                    let tok = Token::new(tok_type, "```".to_string(), self.line, self.col);
+                    let tok = Token::new(TokenType::Text, "```".to_string(), self.line, self.col);
                     self.read_char();
                     self.read_char();
                     tok
@@ -194,6 +219,27 @@ impl Lexer {
                     Token::new(TokenType::Backtick, "`".to_string(), self.line, self.col)
                 }
             }
+            Some(':') if self.col == 1 && self.peek_char(1) == ':' && self.peek_char(2) == ':' => {
+                return self.read_fence(':', TokenType::ContainerFence);
+            }
+            Some(':') if self.col == 1 && self.peek_char(1) == ' ' => {
+                // the space after the marker is part of the token, like for list items
+                let token = Token::new(TokenType::DefinitionMarker, ": ".to_string(), self.line, 1);
+                self.read_char();
+                token
+            }
+            Some('~') if self.col == 1 && self.peek_char(1) == '~' && self.peek_char(2) == '~' => {
+                return self.read_fence('~', TokenType::TripleTilde);
+            }
+            Some('{') if self.peek_char(1) != '{' && self.peek_char(1) != '%' => {
+                match self.read_attributes() {
+                    Some(token) => return token,
+                    None => Token::new(TokenType::Text, "{".to_string(), self.line, self.col),
+                }
+            }
+            Some('[') if utils::footnote_label(&self.src[self.position..]).is_some() => {
+                return self.read_footnote();
+            }
             Some('[') => Token::new(TokenType::LeftBracket, "[".to_string(), self.line, self.col),
             Some(']') => Token::new(
                 TokenType::RightBracket,
@@ -211,26 +257,65 @@ impl Lexer {
                     Token::new(TokenType::Text, "!".to_string(), self.line, self.col)
                 }
             }
+            Some('\\') if self.peek_char(1).is_ascii_punctuation() => {
+                // a backslash escape. the parser turns it into the (literal) character after the
+                // backslash, except in code where the backslash is kept
+                let token = Token::new(
+                    TokenType::Escape,
+                    format!("\\{}", self.peek_char(1)),
+                    self.line,
+                    self.col,
+                );
+                self.read_char();
+                token
+            }
+            Some('&') if entity::entity_len(&self.src[self.position..]).is_some() => {
+                let len = entity::entity_len(&self.src[self.position..]).unwrap_or(1);
+                let token = Token::new(
+                    TokenType::Entity,
+                    self.src[self.position..self.position + len].to_string(),
+                    self.line,
+                    self.col,
+                );
+                for _ in 1..len {
+                    self.read_char();
+                }
+                token
+            }
+            Some('<') if autolink::autolink_len(&self.src[self.position..]).is_some() => {
+                return self.read_span(TokenType::Autolink);
+            }
+            Some('$') if self.col == 1 && self.at_math_fence() => {
+                let token = Token::new(TokenType::MathFence, "$$".to_string(), self.line, 1);
+                self.read_char();
+                token
+            }
+            Some('$') if math::math_len(&self.src[self.position..]).is_some() => {
+                return self.read_span(TokenType::Math);
+            }
             None => Token::new(TokenType::EOF, "".to_string(), self.line, self.col),
             _ => {
                 let start_col = self.col;
                 let start_line = self.line;
 
-                if utils::is_numeric(self.ch)
-                    && self.peek_char(1) == '.'
-                    && self.peek_char(2) == ' '
-                {
-                    let literal = format!("{}. ", self.ch.unwrap());
-                    let token =
-                        Token::new(TokenType::OrderedListItem, literal, start_line, start_col);
-                    self.read_char();
-                    self.read_char();
-                    self.read_char();
+                if self.at_extended_autolink() {
+                    return self.read_span(TokenType::Url);
+                }
 
+                let text = self.read_until_newline_or_inline_token();
+                if text.is_empty() {
+                    // the current char is an inline token without a dedicated token type (eg.
+                    // `{`), so it is returned as text instead of looping on it forever.
+                    let token = Token::new(
+                        TokenType::Text,
+                        self.ch.unwrap().to_string(),
+                        start_line,
+                        start_col,
+                    );
+                    self.read_char();
                     return token;
                 }
 
-                let text = self.read_until_newline_or_inline_token();
                 //let token = match text.as_str() {
                 //    "if" => Token::new(TokenType::If, text, start_line, start_col),
                 //    "endif" => Token::new(TokenType::EndIf, text, start_line, start_col),
@@ -254,10 +339,23 @@ impl Lexer {
         let start = self.position;
 
         while !utils::is_inline_token(self.ch) && self.ch != Some('\n') && self.ch != None {
+            if self.position > start
+                && (self.at_extended_autolink()
+                    || (self.ch == Some('<')
+                        && autolink::autolink_len(&self.src[self.position..]).is_some())
+                    || (self.ch == Some('\\') && self.peek_char(1).is_ascii_punctuation())
+                    || (self.ch == Some('&')
+                        && entity::entity_len(&self.src[self.position..]).is_some())
+                    || (self.ch == Some('$')
+                        && math::math_len(&self.src[self.position..]).is_some()))
+            {
+                break;
+            }
             if self.ch.is_some() && self.ch.unwrap() == ' ' {
                 let word = self.src[start..self.position].trim().to_string();
                 if utils::is_keyword(&word) {
-                    return word;
+                    // the untrimmed text is returned so indentation before the keyword isn't lost
+                    return self.src[start..self.position].to_string();
                 }
             }
             self.read_char();
@@ -266,6 +364,73 @@ impl Lexer {
         self.src[start..self.position].to_string()
     }
 
+    /// `read_fence` reads a run of at least three `fence_char`s at the start of a line and
+    /// returns it as a single token whose literal is the whole run.
+    fn read_fence(&mut self, fence_char: char, token_type: TokenType) -> Token {
+        let start_line = self.line;
+        let start_col = self.col;
+        let start = self.position;
+
+        while self.ch == Some(fence_char) {
+            self.read_char();
+        }
+
+        Token::new(
+            token_type,
+            self.src[start..self.position].to_string(),
+            start_line,
+            start_col,
+        )
+    }
+
+    /// `read_footnote` reads a footnote reference (`[^label]`), or a footnote definition marker
+    /// (`[^label]:`) at the start of a line.
+    fn read_footnote(&mut self) -> Token {
+        let start_line = self.line;
+        let start_col = self.col;
+
+        let label = utils::footnote_label(&self.src[self.position..]).unwrap();
+        let mut len = label.len() + 3;
+        let mut token_type = TokenType::FootnoteReference;
+        if start_col == 1 && self.src[self.position + len..].starts_with(':') {
+            len += 1;
+            token_type = TokenType::FootnoteDefinition;
+        }
+
+        let literal = self.src[self.position..self.position + len].to_string();
+        for _ in 0..literal.chars().count() {
+            self.read_char();
+        }
+
+        Token::new(token_type, literal, start_line, start_col)
+    }
+
+    /// `read_attributes` reads an attribute block like `{#custom-id}` at the end of a line (only
+    /// white space can come after it) and returns it as a single token. `None` is returned,
+    /// without reading anything, for any other `{`.
+    fn read_attributes(&mut self) -> Option<Token> {
+        let rest = &self.src[self.position..];
+        let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+        let end = line.find('}')?;
+
+        let content = &line[1..end];
+        if content.trim().is_empty() || content.contains('{') || !line[end + 1..].trim().is_empty()
+        {
+            return None;
+        }
+
+        let token = Token::new(
+            TokenType::Attributes,
+            line[..=end].to_string(),
+            self.line,
+            self.col,
+        );
+        for _ in 0..line[..=end].chars().count() {
+            self.read_char();
+        }
+        Some(token)
+    }
+
     //fn prev_char(&self) -> char {
     //    println!("calling prev_char {self:?}");
     //    if self.position == 0 {
@@ -277,6 +442,122 @@ impl Lexer {
     //    }
     //}
 
+    /// `read_break_or_underline` reads the current line into a single token if it is a thematic
+    /// break (`---`, `***` or `___`) or the underline of a setext heading (`===` or `---`).
+    ///
+    /// a `---` line right under a line of a paragraph is the underline of a heading, otherwise
+    /// it is a thematic break. the parser still checks that the line above is a paragraph; if
+    /// it isn't, it treats the underline as a thematic break (or text, for `===`).
+    fn read_break_or_underline(&mut self) -> Option<Token> {
+        let line = self.src[self.position..].split('\n').next()?;
+
+        let token_type = if (utils::is_setext_underline(line, '=')
+            || utils::is_setext_underline(line, '-'))
+            && self.follows_paragraph_line()
+        {
+            TokenType::SetextUnderline
+        } else if utils::is_thematic_break(line) {
+            TokenType::ThematicBreak
+        } else {
+            return None;
+        };
+
+        let start_line = self.line;
+        let literal = line.to_string();
+        for _ in 0..literal.chars().count() {
+            self.read_char();
+        }
+
+        Some(Token::new(token_type, literal, start_line, 1))
+    }
+
+    /// `follows_paragraph_line` reports whether the line before the current one could be a line
+    /// of a paragraph, as opposed to a blank line or one that starts another kind of block.
+    fn follows_paragraph_line(&self) -> bool {
+        let Some(before) = self.src[..self.position].strip_suffix('\n') else {
+            return false;
+        };
+        utils::is_paragraph_line(before.rsplit('\n').next().unwrap_or_default())
+    }
+
+    /// `read_list_marker` reads the marker of the list item the current line starts with (see
+    /// `utils::list_marker`), with its indentation and the space after it. a list item only
+    /// starts at the beginning of a line: any other dash (like the second one of `-- x`) or
+    /// number is text.
+    fn read_list_marker(&mut self) -> Option<Token> {
+        let line = self.src[self.position..].split('\n').next()?;
+        let (marker, ordered) = utils::list_marker(line)?;
+        let token_type = if ordered {
+            TokenType::OrderedListItem
+        } else {
+            TokenType::UnorderedListItem
+        };
+        let token = Token::new(token_type, marker.to_string(), self.line, 1);
+        for _ in 0..token.literal.len() {
+            self.read_char();
+        }
+        Some(token)
+    }
+
+    /// `at_extended_autolink` reports whether a bare url or email address starts at the current
+    /// char. they only start at the beginning of a line, after whitespace or after one of the
+    /// delimiters `*`, `_`, `~` and `(` (unless it opens the destination of a link).
+    fn at_extended_autolink(&self) -> bool {
+        let before = &self.src[..self.position];
+        let at_boundary = match before.chars().next_back() {
+            Some(prev) => {
+                (prev.is_whitespace() || "*_~(".contains(prev)) && !before.ends_with("](")
+            }
+            None => true,
+        };
+
+        at_boundary && autolink::extended_autolink_len(&self.src[self.position..]).is_some()
+    }
+
+    /// `at_math_fence` reports whether the current line is a `$$` line opening or closing a
+    /// math block.
+    fn at_math_fence(&self) -> bool {
+        self.src[self.position..]
+            .split('\n')
+            .next()
+            .is_some_and(|line| line.trim_end() == "$$")
+    }
+
+    /// `read_span` reads an autolink (`<...>`), a bare url/email address or a math span (`$...$`)
+    /// starting at the current char into a single token of the given type.
+    fn read_span(&mut self, token_type: TokenType) -> Token {
+        let rest = &self.src[self.position..];
+        let len = match token_type {
+            TokenType::Autolink => autolink::autolink_len(rest),
+            TokenType::Math => math::math_len(rest),
+            _ => autolink::extended_autolink_len(rest),
+        }
+        .unwrap_or(0);
+
+        let start = self.position;
+        let start_line = self.line;
+        let start_col = self.col;
+        while self.position < start + len {
+            self.read_char();
+        }
+
+        Token::new(
+            token_type,
+            self.src[start..self.position].to_string(),
+            start_line,
+            start_col,
+        )
+    }
+
+    /// `indentation` returns the number of spaces from the current char to the first
+    /// non-space char on the line.
+    fn indentation(&self) -> usize {
+        self.src[self.position..]
+            .chars()
+            .take_while(|ch| *ch == ' ')
+            .count()
+    }
+
     fn peek_char(&self, distance: usize) -> char {
         let char_index = self.position + distance;
         if char_index >= self.src.len() {
@@ -0,0 +1,434 @@
+use crate::utils;
+
+/// the greek letters that are recognized, with the characters they stand for. the upper case
+/// letters are written upright, like TeX does.
+const GREEK_LETTERS: &[(&str, &str)] = &[
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ϵ"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("vartheta", "ϑ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "ϕ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Upsilon", "Υ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+];
+
+/// the symbols that are recognized, with the characters they stand for and whether they are
+/// written as an operator (`<mo>`) rather than an identifier (`<mi>`).
+const SYMBOLS: &[(&str, &str, bool)] = &[
+    ("sum", "∑", true),
+    ("prod", "∏", true),
+    ("int", "∫", true),
+    ("oint", "∮", true),
+    ("cdot", "⋅", true),
+    ("times", "×", true),
+    ("div", "÷", true),
+    ("pm", "±", true),
+    ("mp", "∓", true),
+    ("le", "≤", true),
+    ("leq", "≤", true),
+    ("ge", "≥", true),
+    ("geq", "≥", true),
+    ("ne", "≠", true),
+    ("neq", "≠", true),
+    ("approx", "≈", true),
+    ("equiv", "≡", true),
+    ("sim", "∼", true),
+    ("to", "→", true),
+    ("rightarrow", "→", true),
+    ("leftarrow", "←", true),
+    ("Rightarrow", "⇒", true),
+    ("Leftrightarrow", "⇔", true),
+    ("in", "∈", true),
+    ("notin", "∉", true),
+    ("subset", "⊂", true),
+    ("subseteq", "⊆", true),
+    ("cup", "∪", true),
+    ("cap", "∩", true),
+    ("forall", "∀", true),
+    ("exists", "∃", true),
+    ("ldots", "…", true),
+    ("cdots", "⋯", true),
+    ("circ", "∘", true),
+    ("langle", "⟨", true),
+    ("rangle", "⟩", true),
+    ("infty", "∞", false),
+    ("partial", "∂", false),
+    ("nabla", "∇", false),
+    ("emptyset", "∅", false),
+];
+
+/// the functions written upright, like `\sin`. `lim`, `max` and `min` take their subscript
+/// under them in display math, like sums do.
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "log", "ln", "exp", "det", "gcd", "lim", "max", "min", "sup", "inf",
+];
+
+/// `math_len` returns the (byte) length of the math span (`$x^2$`, or `$$x^2$$` for display
+/// math, with its dollar signs) at the start of `text`, if there's one.
+///
+/// like pandoc, the content of a `$...$` span can't start or end with white space and the
+/// closing `$` can't be followed by a digit, so that prices like `$5 and $10` stay text. a
+/// `$...$` span can't go over several lines, a `$$...$$` one can but not over a blank line, and
+/// a backslash escapes the char after it (eg. `\$`).
+pub fn math_len(text: &str) -> Option<usize> {
+    let delimiter = if text.starts_with("$$") { "$$" } else { "$" };
+    let content = text.strip_prefix(delimiter)?;
+    let inline = delimiter == "$";
+    if content.is_empty()
+        || content.starts_with('$')
+        || (inline && content.starts_with(char::is_whitespace))
+    {
+        return None;
+    }
+
+    let mut prev = ' ';
+    let mut chars = content.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\n' if inline || content[i + 1..].split('\n').next()?.trim().is_empty() => {
+                return None;
+            }
+            '\\' => {
+                chars.next();
+                prev = '\\';
+                continue;
+            }
+            '$' if content[i..].starts_with(delimiter) && i > 0 => {
+                let end = delimiter.len() + i + delimiter.len();
+                let closes = !inline
+                    || (!prev.is_whitespace()
+                        && !text[end..].starts_with(|ch: char| ch.is_ascii_digit()));
+                if closes {
+                    return Some(end);
+                }
+            }
+            _ => {}
+        }
+        prev = ch;
+    }
+    None
+}
+
+/// `to_mathml` converts the TeX math `tex` to MathML, written as a display (block) equation
+/// when `display` is set.
+///
+/// only a subset of TeX is understood: letters, numbers and operators, groups (`{...}`),
+/// sub- and superscripts (`x_i^2`), `\frac`, `\sqrt`, `\text`, `\left`/`\right`, spacing
+/// commands, greek letters, common symbols (`\sum`, `\int`, `\leq`, `\infty`, ...) and
+/// functions (`\sin`, `\log`, `\lim`, ...). other commands are written out as errors.
+pub fn to_mathml(tex: &str, display: bool) -> String {
+    let mut parser = MathParser {
+        chars: tex.chars().collect(),
+        position: 0,
+        display,
+    };
+    let atoms = parser.parse_row(None);
+    let content = row(atoms.into_iter().map(|atom| atom.markup).collect());
+
+    if display {
+        format!("<math display=\"block\">{}</math>", content)
+    } else {
+        format!("<math>{}</math>", content)
+    }
+}
+
+/// `row` wraps `items` in an `<mrow>` unless there's only one of them.
+fn row(items: Vec<String>) -> String {
+    if items.len() == 1 {
+        items.into_iter().next().unwrap()
+    } else {
+        format!("<mrow>{}</mrow>", items.concat())
+    }
+}
+
+/// `Atom` is a piece of MathML that sub- and superscripts can be attached to.
+struct Atom {
+    markup: String,
+    /// whether the atom is an operator like `\sum` whose scripts are written under and over it
+    /// in display math.
+    limits: bool,
+}
+
+impl Atom {
+    fn new(markup: String) -> Self {
+        Atom {
+            markup,
+            limits: false,
+        }
+    }
+}
+
+struct MathParser {
+    chars: Vec<char>,
+    position: usize,
+    display: bool,
+}
+
+impl MathParser {
+    /// `parse_row` parses atoms up to the end of the math, or up to (and including) `closing`.
+    fn parse_row(&mut self, closing: Option<char>) -> Vec<Atom> {
+        let mut atoms = vec![];
+        loop {
+            self.skip_white_space();
+            let Some(ch) = self.peek() else {
+                break;
+            };
+            if Some(ch) == closing {
+                self.position += 1;
+                break;
+            }
+
+            if ch == '^' || ch == '_' {
+                let base = atoms
+                    .pop()
+                    .unwrap_or_else(|| Atom::new("<mrow></mrow>".to_string()));
+                atoms.push(self.parse_scripts(base));
+            } else if let Some(atom) = self.parse_atom() {
+                atoms.push(atom);
+            }
+        }
+        atoms
+    }
+
+    /// `parse_scripts` attaches the sub- and/or superscript that follow to `base`.
+    fn parse_scripts(&mut self, base: Atom) -> Atom {
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_white_space();
+            match self.peek() {
+                Some('_') => {
+                    self.position += 1;
+                    sub = Some(self.parse_argument());
+                }
+                Some('^') => {
+                    self.position += 1;
+                    sup = Some(self.parse_argument());
+                }
+                _ => break,
+            }
+        }
+
+        let limits = base.limits && self.display;
+        let (tag, scripts) = match (sub, sup) {
+            (Some(sub), Some(sup)) if limits => ("munderover", sub + &sup),
+            (Some(sub), Some(sup)) => ("msubsup", sub + &sup),
+            (Some(sub), None) if limits => ("munder", sub),
+            (Some(sub), None) => ("msub", sub),
+            (None, Some(sup)) if limits => ("mover", sup),
+            (None, Some(sup)) => ("msup", sup),
+            (None, None) => return base,
+        };
+        Atom::new(format!("<{0}>{1}{2}</{0}>", tag, base.markup, scripts))
+    }
+
+    /// `parse_argument` parses the argument of a command or script: a single atom or a group.
+    fn parse_argument(&mut self) -> String {
+        self.skip_white_space();
+        match self.parse_atom() {
+            Some(atom) => atom.markup,
+            None => "<mrow></mrow>".to_string(),
+        }
+    }
+
+    fn parse_atom(&mut self) -> Option<Atom> {
+        let ch = self.peek()?;
+        self.position += 1;
+
+        let markup = match ch {
+            '{' => {
+                let atoms = self.parse_row(Some('}'));
+                row(atoms.into_iter().map(|atom| atom.markup).collect())
+            }
+            '\\' => return Some(self.parse_command()),
+            '0'..='9' => {
+                let mut number = ch.to_string();
+                while let Some(next) = self.peek() {
+                    let decimal_point = next == '.'
+                        && self
+                            .chars
+                            .get(self.position + 1)
+                            .is_some_and(|ch| ch.is_ascii_digit());
+                    if !next.is_ascii_digit() && !decimal_point {
+                        break;
+                    }
+                    number.push(next);
+                    self.position += 1;
+                }
+                format!("<mn>{}</mn>", number)
+            }
+            '\'' => "<mo>′</mo>".to_string(),
+            '-' => "<mo>−</mo>".to_string(),
+            ch if ch.is_alphabetic() => format!("<mi>{}</mi>", ch),
+            ch => format!("<mo>{}</mo>", utils::escape_html(&ch.to_string())),
+        };
+        Some(Atom::new(markup))
+    }
+
+    /// `parse_command` parses the command (`\name`, or a backslash followed by a single symbol)
+    /// after a backslash.
+    fn parse_command(&mut self) -> Atom {
+        let start = self.position;
+        while self.peek().is_some_and(|ch| ch.is_ascii_alphabetic()) {
+            self.position += 1;
+        }
+        if self.position == start && self.peek().is_some() {
+            self.position += 1;
+        }
+        let name: String = self.chars[start..self.position].iter().collect();
+
+        let markup = match name.as_str() {
+            "frac" => {
+                let numerator = self.parse_argument();
+                let denominator = self.parse_argument();
+                format!("<mfrac>{}{}</mfrac>", numerator, denominator)
+            }
+            "sqrt" => format!("<msqrt>{}</msqrt>", self.parse_argument()),
+            "text" | "mathrm" | "operatorname" => {
+                let text = self.read_group();
+                if name == "text" {
+                    format!("<mtext>{}</mtext>", utils::escape_html(&text))
+                } else {
+                    format!(
+                        "<mi mathvariant=\"normal\">{}</mi>",
+                        utils::escape_html(&text)
+                    )
+                }
+            }
+            "left" | "right" => {
+                self.skip_white_space();
+                if self.peek() == Some('.') {
+                    // `\left.` is an invisible delimiter
+                    self.position += 1;
+                    return Atom::new(String::new());
+                }
+                return self
+                    .parse_atom()
+                    .unwrap_or_else(|| Atom::new(String::new()));
+            }
+            "," | ":" | ";" | " " | "quad" | "qquad" => {
+                let width = match name.as_str() {
+                    "," => "0.167em",
+                    ":" => "0.222em",
+                    ";" => "0.278em",
+                    " " => "0.333em",
+                    "quad" => "1em",
+                    _ => "2em",
+                };
+                format!("<mspace width=\"{}\"/>", width)
+            }
+            "{" | "}" | "$" | "%" | "#" | "&" | "_" | "|" => {
+                format!("<mo>{}</mo>", utils::escape_html(&name))
+            }
+            name => {
+                if let Some((_, letter)) = GREEK_LETTERS.iter().find(|(n, _)| *n == name) {
+                    if name.starts_with(|ch: char| ch.is_uppercase()) {
+                        format!("<mi mathvariant=\"normal\">{}</mi>", letter)
+                    } else {
+                        format!("<mi>{}</mi>", letter)
+                    }
+                } else if let Some((_, symbol, operator)) =
+                    SYMBOLS.iter().find(|(n, _, _)| *n == name)
+                {
+                    let markup = if *operator {
+                        format!("<mo>{}</mo>", symbol)
+                    } else {
+                        format!("<mi>{}</mi>", symbol)
+                    };
+                    // integrals keep their limits on the side, even in display math
+                    return Atom {
+                        markup,
+                        limits: matches!(name, "sum" | "prod"),
+                    };
+                } else if FUNCTIONS.contains(&name) {
+                    return Atom {
+                        markup: format!("<mi>{}</mi>", name),
+                        limits: matches!(name, "lim" | "max" | "min" | "sup" | "inf"),
+                    };
+                } else {
+                    format!(
+                        "<merror><mtext>\\{}</mtext></merror>",
+                        utils::escape_html(name)
+                    )
+                }
+            }
+        };
+        Atom::new(markup)
+    }
+
+    /// `read_group` returns the raw text of the `{...}` group that follows (or of the next char
+    /// when there's no group).
+    fn read_group(&mut self) -> String {
+        self.skip_white_space();
+        if self.peek() != Some('{') {
+            return self
+                .peek()
+                .map(|ch| {
+                    self.position += 1;
+                    ch.to_string()
+                })
+                .unwrap_or_default();
+        }
+
+        self.position += 1;
+        let start = self.position;
+        let mut depth = 1;
+        while let Some(ch) = self.peek() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            self.position += 1;
+        }
+        let text = self.chars[start..self.position].iter().collect();
+        self.position += 1; // skip the closing brace
+        text
+    }
+
+    fn skip_white_space(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+}
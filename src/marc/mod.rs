@@ -1,4 +1,15 @@
 pub mod ast;
+pub mod autolink;
+pub mod container;
+pub mod entity;
+pub mod highlight;
+pub mod image_size;
 pub mod lexer;
+pub mod math;
+pub mod options;
 pub mod parser;
+pub mod reference;
+pub mod render;
 pub mod token;
+pub mod typography;
+pub mod warning;
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use crate::marc::container::Containers;
+use crate::marc::highlight::Highlighter;
+use crate::marc::typography::TextTransform;
+
+/// `RenderOptions` configures how a marc document is turned into html. every `Renderer` has
+/// them (see `Renderer::options`), for the nodes it writes out as html.
+pub struct RenderOptions {
+    /// the grammars used to highlight fenced code blocks. code in a language without a grammar
+    /// is written out as (escaped) plain text.
+    pub highlighter: Highlighter,
+    /// when set, every heading with an id ends with a link to itself showing this symbol (eg.
+    /// `¶`), which the stylesheet only reveals when the heading is hovered.
+    pub heading_anchor: Option<String>,
+    /// the levels of the headings listed in the table of contents. defaults to all of them
+    /// (1 to 6).
+    pub toc_min_level: i8,
+    pub toc_max_level: i8,
+    /// what the end of a line inside a paragraph (a soft line break) is written out as. it is
+    /// a new line by default, but can be a space for output that is sensitive to new lines.
+    pub soft_break: String,
+    /// how TeX math (`$...$`, `$$...$$`) is written out: by default it keeps its `\(...\)` or
+    /// `\[...\]` delimiters for a client side renderer like KaTeX or MathJax.
+    pub math: MathOutput,
+    /// the transforms run over the text of the document as it is written out, in order (eg.
+    /// `typography::Emoji` and `typography::SmartPunctuation`). there are none by default.
+    pub text_transforms: Vec<Box<dyn TextTransform>>,
+    /// the directory local images (with a relative or `/...` src) are looked up in to give them
+    /// their width and height, so the page doesn't move around as they load. images aren't read
+    /// when it isn't set.
+    pub image_root: Option<PathBuf>,
+    /// the renderers of named containers (`:::name ... :::`). containers without a renderer are
+    /// written out as a `<div>` with their name as its class.
+    pub containers: Containers,
+    /// when set, the block elements get a `data-sourcepos="line:col-line:col"` attribute with
+    /// where their node is in the source, eg. to keep an editor and a preview of the document
+    /// scrolled to the same place.
+    pub source_positions: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            highlighter: Highlighter::default(),
+            heading_anchor: None,
+            toc_min_level: 1,
+            toc_max_level: 6,
+            soft_break: "\n".to_string(),
+            math: MathOutput::default(),
+            text_transforms: vec![],
+            image_root: None,
+            containers: Containers::default(),
+            source_positions: false,
+        }
+    }
+}
+
+/// `MathOutput` is the way TeX math is written out in the html.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MathOutput {
+    /// `<span class="math inline">\(...\)</span>` (or `math display` and `\[...\]`), with the
+    /// TeX left for a script to render.
+    #[default]
+    Delimiters,
+    /// MathML, which browsers render without any script. only a subset of TeX is converted (see
+    /// `marc::math::to_mathml`).
+    MathML,
+}
+
+/// `ParseOptions` configures the syntax the marc parser understands on top of the one it always
+/// does.
+#[derive(Clone, Copy, Default)]
+pub struct ParseOptions {
+    /// turn bare urls (`https://...`, `www....`) and email addresses in text into links, like
+    /// github flavored markdown does. autolinks in angle brackets (`<https://...>`) are always
+    /// links.
+    pub autolinks: bool,
+}
@@ -1,29 +1,169 @@
+use std::collections::{HashMap, HashSet};
+
 use crate::marc::ast::{
     Block, Inline, Node, Program,
-    block_quote::BlockQuote,
+    attributes::Attributes,
+    block_quote::{Alert, AlertKind, BlockQuote},
     code::{CodeBlock, InlineCode},
+    container::Container,
+    definition_list::{Definition, DefinitionItem, DefinitionList},
+    footnote::{Footnote, FootnoteReference, FootnoteSection},
     heading::Heading,
-    image::Image,
+    image::{Figure, Image},
     inline_container::InlineContainer,
     link::Link,
-    list::{ListItem, OrderedList, UnorderedList},
-    text::{BoldText, ItalicizedText, ParagraphText, Text},
+    list::{ListItem, OrderedList, TaskProgress, UnorderedList},
+    math::{InlineMath, MathBlock},
+    text::{BoldText, EscapedText, HardBreak, ItalicizedText, ParagraphText, SoftBreak, Text},
+    thematic_break::ThematicBreak,
+    toc::{TableOfContents, TocEntry},
+    visit::{self, Visitor},
 };
+use crate::marc::autolink;
+use crate::marc::entity;
 use crate::marc::lexer::Lexer;
+use crate::marc::options::ParseOptions;
+use crate::marc::reference::{self, LinkReference};
 use crate::marc::token::{Token, TokenType};
+use crate::marc::warning::Warning;
+use crate::span::{LineIndex, Span};
+use crate::utils;
+
+// the content of a list item (or definition), parsed as a document of its own: its text, the
+// line it starts on and its span
+type NestedContent = (String, usize, Span);
+
+/// `DocumentState` is what the parser knows about the whole document, as opposed to the block it
+/// is parsing. it is handed over to the parsers of nested content (eg. list items) so they can
+/// use and add to it.
+#[derive(Default)]
+struct DocumentState {
+    // the ids given to the headings parsed so far, so no two headings get the same one
+    heading_ids: HashSet<String>,
+    // every heading with an id, in the order they appear in the document
+    headings: Vec<TocEntry>,
+    // the labels of all the footnotes defined in the document. they are collected before the
+    // document is parsed so that references can come before the definitions.
+    footnote_labels: HashSet<String>,
+    // the labels of the referenced footnotes in the order they are first referenced (which is
+    // their number), with the number of references to each of them
+    footnote_references: Vec<(String, usize)>,
+    footnote_definitions: HashMap<String, (Span, Vec<Block>)>,
+    // the link reference definitions of the document by their (normalized) label. like footnote
+    // labels, they are collected before the document is parsed.
+    link_references: HashMap<String, LinkReference>,
+    warnings: Vec<Warning>,
+    // the source of the whole document. the spans of the nodes are positions in it, including
+    // the ones of nested content, which is parsed from parts of it.
+    source: LineIndex,
+}
+
+impl DocumentState {
+    /// `collect_definitions` records the footnote labels and link reference definitions in
+    /// `source`, including the ones in block quotes and list items, except for the ones in code
+    /// blocks. if a label is defined more than once, the first definition is used.
+    fn collect_definitions(&mut self, source: &str) {
+        // the fence of the code block the lines are in: its character, length and indentation
+        let mut fence: Option<(char, usize, usize)> = None;
+
+        for line in source.lines() {
+            let line = strip_container_markers(line);
+            let indentation = utils::indentation(line);
+            let line = line.trim_start();
+            if let Some((fence_char, fence_len, fence_indentation)) = fence {
+                if is_closing_fence(line, fence_char, fence_len) && indentation <= fence_indentation
+                {
+                    fence = None;
+                }
+                continue;
+            }
+
+            if let Some((fence_char, fence_len)) = opening_fence(line) {
+                fence = Some((fence_char, fence_len, indentation));
+            } else if indentation >= 4 {
+                // indented code (or the continuation of a paragraph), which can't hold
+                // definitions
+                continue;
+            } else if let Some(label) = utils::footnote_label(line)
+                && line[label.len() + 3..].starts_with(':')
+            {
+                self.footnote_labels.insert(label.to_string());
+            } else if let Some((label, link_reference)) = reference::parse_definition(line) {
+                self.link_references.entry(label).or_insert(link_reference);
+            }
+        }
+    }
+}
+
+/// `strip_container_markers` returns `line` without the block quote and list item markers it
+/// starts with (eg. `> - [a]: /url` is `[a]: /url`).
+fn strip_container_markers(mut line: &str) -> &str {
+    loop {
+        if let Some((marker, _)) = utils::list_marker(line) {
+            line = &line[marker.len()..];
+        } else if let Some(rest) = line.trim_start().strip_prefix('>')
+            && utils::indentation(line) < 4
+        {
+            line = rest.strip_prefix(' ').unwrap_or(rest);
+        } else {
+            return line;
+        }
+    }
+}
+
+/// `opening_fence` returns the character and length of the code fence `line` starts with, if it
+/// starts with one. the info string of a backtick fence can't contain backticks.
+fn opening_fence(line: &str) -> Option<(char, usize)> {
+    let fence_char = line.chars().next().filter(|ch| *ch == '`' || *ch == '~')?;
+    let len = line.chars().take_while(|ch| *ch == fence_char).count();
+    if len < 3 || (fence_char == '`' && line[len..].contains('`')) {
+        return None;
+    }
+    Some((fence_char, len))
+}
+
+/// `is_closing_fence` reports whether `line` closes a code block whose fence is `len`
+/// `fence_char`s: it is a run of at least as many of them with only white space after it.
+fn is_closing_fence(line: &str, fence_char: char, len: usize) -> bool {
+    let run = line.chars().take_while(|ch| *ch == fence_char).count();
+    run >= len && line[run..].trim().is_empty()
+}
+
+/// the marker that is replaced by the number of done and total task list items of the document
+const PROGRESS_MARKER: &str = "[[progress]]";
 
 pub struct Parser {
+    prev_token: Option<Token>,
     curr_token: Option<Token>,
     peek_token: Option<Token>,
     lexer: Lexer,
+    // the offset in the document of every line of the source being parsed. the lines of nested
+    // content (eg. a list item) are the ends of lines of the document.
+    line_offsets: Vec<usize>,
+    state: DocumentState,
+    options: ParseOptions,
+    // links can't be nested, so autolinks in the text of a link are plain text
+    in_link_text: bool,
+    // the lines after the first one of a paragraph are part of it, even though they start at
+    // the beginning of a line (where text would otherwise start a new paragraph)
+    in_paragraph: bool,
 }
 
 impl Parser {
     pub fn new(lexer: Lexer) -> Parser {
+        let line_offsets = std::iter::once(0)
+            .chain(lexer.src.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
         let mut p = Parser {
+            prev_token: None,
             curr_token: None,
             peek_token: None,
             lexer: lexer,
+            line_offsets,
+            state: DocumentState::default(),
+            options: ParseOptions::default(),
+            in_link_text: false,
+            in_paragraph: false,
         };
 
         // we're calling this twice so it can set both curr_token and next_token
@@ -33,7 +173,19 @@ impl Parser {
         p
     }
 
+    pub fn set_options(&mut self, options: ParseOptions) {
+        self.options = options;
+    }
+
     fn advance_token(&mut self) {
+        // advancing past the end of the document keeps the last token before EOF
+        if self
+            .curr_token
+            .as_ref()
+            .is_some_and(|token| token.token_type != TokenType::EOF)
+        {
+            self.prev_token = self.curr_token.take();
+        }
         self.curr_token = self.peek_token.clone();
         self.peek_token = Some(self.lexer.next_token());
     }
@@ -43,69 +195,117 @@ impl Parser {
     //    self.peek_token = Some(self.lexer.next_word());
     //}
 
-    fn parse(&mut self, end_token: TokenType, parse_inline: bool) -> Option<Box<dyn Node>> {
-        let mut inline_container = InlineContainer::new();
+    /// `parse_node` parses the node that starts at curr_token, which is left on the last token
+    /// of the node. it returns `None` at `end_token` or at the end of the document. an inline
+    /// node is returned as `Block::Inline` if it is in a paragraph already (or doesn't start its
+    /// line), so the caller can add it to its container; otherwise it starts a paragraph.
+    fn parse_node(&mut self, end_token: &TokenType) -> Option<Block> {
+        let token = self.curr_token.clone()?;
+        if token.token_type == *end_token {
+            return None;
+        }
 
-        while let Some(token) = self.curr_token.clone() {
-            if token.token_type == end_token {
-                return None;
+        let mut node: Block = match token.token_type {
+            TokenType::H1 => self.parse_heading(1),
+            TokenType::H2 => self.parse_heading(2),
+            TokenType::H3 => self.parse_heading(3),
+            TokenType::H4 => self.parse_heading(4),
+            TokenType::H5 => self.parse_heading(5),
+            TokenType::H6 => self.parse_heading(6),
+            TokenType::Asterisk => {
+                let italics = self.parse_italics();
+                self.paragraph_or_inline(&token, italics)
             }
-            let block: Box<dyn Node> = match token.token_type {
-                TokenType::H1 => self.parse_heading(1),
-                TokenType::H2 => self.parse_heading(2),
-                TokenType::H3 => self.parse_heading(3),
-                TokenType::H4 => self.parse_heading(4),
-                TokenType::H5 => self.parse_heading(5),
-                TokenType::H6 => self.parse_heading(6),
-                TokenType::Asterisk => self.parse_italics(),
-                TokenType::DoubleAsterisk => self.parse_bold_text(),
-                TokenType::GreaterThan => self.parse_blockquote(),
-                TokenType::Backtick => self.parse_inline_code(),
-                TokenType::TripleBacktick => self.parse_code_block(),
-                TokenType::LeftBracket => self.parse_link_start(),
-                TokenType::RightBracket => self.parse_text(),
-                TokenType::LeftParen => self.parse_text(),
-                TokenType::RightParen => self.parse_text(),
-                TokenType::Exclamation => self.parse_image(),
-                TokenType::Text => self.parse_text(),
-                TokenType::UnorderedListItem => {
-                    self.advance_token();
-                    let mut list = Box::new(UnorderedList::new());
-                    self.parse_unordered_list_item(&mut list);
-                    list
+            TokenType::DoubleAsterisk => {
+                let bold = self.parse_bold_text();
+                self.paragraph_or_inline(&token, bold)
+            }
+            TokenType::GreaterThan => self.parse_blockquote(),
+            TokenType::Backtick => {
+                let code = self.parse_inline_code();
+                self.paragraph_or_inline(&token, code)
+            }
+            TokenType::TripleBacktick | TokenType::TripleTilde => self.parse_code_block(),
+            TokenType::LeftBracket => {
+                let link = self.parse_link_start();
+                self.paragraph_or_inline(&token, link)
+            }
+            TokenType::RightBracket => self.parse_text(),
+            TokenType::LeftParen => self.parse_text(),
+            TokenType::RightParen => self.parse_text(),
+            TokenType::Exclamation => self.parse_image(),
+            TokenType::FootnoteReference => {
+                let reference = self.parse_footnote_reference();
+                self.paragraph_or_inline(&token, reference)
+            }
+            TokenType::FootnoteDefinition => self.parse_text(),
+            TokenType::Escape | TokenType::Entity => self.parse_escaped_text(),
+            TokenType::Autolink => self.parse_autolink(),
+            TokenType::Math => self.parse_math(),
+            TokenType::MathFence => self.parse_math_block(),
+            TokenType::ContainerFence => self.parse_container(),
+            TokenType::Url if self.options.autolinks => self.parse_autolink(),
+            TokenType::Url => self.parse_escaped_text(),
+            TokenType::Attributes => match Attributes::parse(&token.literal) {
+                Some(attributes) => Inline::Attributes(attributes).into(),
+                None => self.parse_text(),
+            },
+            TokenType::Text => self.parse_text(),
+            TokenType::UnorderedListItem => {
+                let mut list = UnorderedList::new();
+                for item in self.parse_list_items(TokenType::UnorderedListItem) {
+                    list.add_list_item(item);
                 }
-                TokenType::OrderedListItem => {
-                    self.advance_token();
-                    let mut list = Box::new(OrderedList::new());
-                    self.parse_ordered_list_item(&mut list);
-                    list
+                list.into()
+            }
+            TokenType::OrderedListItem => {
+                let mut list = OrderedList::new();
+                if let Ok(start) = token.literal.trim().trim_end_matches('.').parse() {
+                    list.set_start(start);
                 }
-                TokenType::NewLine => Box::new(Text::new(token.literal)),
-                TokenType::EOF => {
-                    break;
-                } //TokenType::LeftDoubleBrace => self.parse_expression(),
-                  //TokenType::RightDoubleBrace => todo!(),
-                  //TokenType::KeywordStart => self.parse_keyword_block(),
-                  //TokenType::KeywordEnd => self.parse_text(),
-            };
-            println!("done parsing {}", block.token_literal());
-
-            if parse_inline {
-                inline_container.add_child(block);
-                if self.peek_token.clone().unwrap().token_type == end_token {
-                    break;
+                for item in self.parse_list_items(TokenType::OrderedListItem) {
+                    list.add_list_item(item);
                 }
-                self.advance_token();
-            } else {
-                return Some(block);
+                list.into()
             }
-        }
+            TokenType::Indent => self.parse_indented_code_block(),
+            TokenType::ThematicBreak => self.parse_thematic_break(),
+            // an underline that isn't under a paragraph: `---` is then a thematic break and
+            // `===` is just text
+            TokenType::SetextUnderline if token.literal.contains('-') => {
+                self.parse_thematic_break()
+            }
+            TokenType::SetextUnderline => self.parse_text(),
+            // a definition without a term is just text
+            TokenType::DefinitionMarker => self.parse_text(),
+            TokenType::NewLine => Inline::Text(Text::new(token.literal.clone())).into(),
+            TokenType::EOF => {
+                return None;
+            } //TokenType::LeftDoubleBrace => self.parse_expression(),
+              //TokenType::RightDoubleBrace => todo!(),
+              //TokenType::KeywordStart => self.parse_keyword_block(),
+              //TokenType::KeywordEnd => self.parse_text(),
+        };
+        self.take_inline_attributes(&mut node);
+        node.set_span(self.span_from(&token));
+        println!("done parsing {}", node.token_literal());
+        Some(node)
+    }
 
-        if parse_inline {
-            Some(Box::new(inline_container))
-        } else {
-            None
+    /// `parse_inline` parses the nodes from curr_token up to `end_token` (which isn't part of
+    /// them) or the end of the document, leaving curr_token on the last of them.
+    fn parse_inline(&mut self, end_token: TokenType) -> InlineContainer {
+        let mut inline_container = InlineContainer::new();
+
+        while let Some(node) = self.parse_node(&end_token) {
+            inline_container.add_child(node);
+            if self.peek_token.clone().unwrap().token_type == end_token {
+                break;
+            }
+            self.advance_token();
         }
+
+        inline_container
     }
 
     fn expect_peek(&mut self, token_type: TokenType) -> bool {
@@ -154,120 +354,304 @@ impl Parser {
     //    false
     //}
 
-    fn parse_text(&mut self) -> Box<dyn Node> {
+    fn parse_text(&mut self) -> Block {
         println!("parsing text...");
         let token = self.curr_token.clone().unwrap();
         println!("{token:#?}");
 
-        if token.start_col > 1 {
-            return Box::new(Text::new(token.literal));
+        if token.start_col > 1 || self.in_paragraph {
+            return Inline::Text(Text::new(token.literal)).into();
         }
 
-        let mut inline_container = Box::new(InlineContainer::new());
-        inline_container.add_child(Box::new(Text::new(token.literal)));
+        // the white space at the start of a paragraph isn't part of it
+        let text = Text::new(token.literal.trim_start().to_string());
+        self.parse_paragraph(&token, text.into())
+    }
 
-        self.advance_token();
-        let mut paragraph_text = ParagraphText::new();
+    /// `parse_escaped_text` parses a backslash escape or an entity reference into the character
+    /// it stands for, or a url that isn't a link into the url.
+    fn parse_escaped_text(&mut self) -> Block {
+        let token = self.curr_token.clone().unwrap();
+        let value = match token.token_type {
+            TokenType::Escape => token.literal[1..].to_string(),
+            TokenType::Url => token.literal.clone(),
+            _ => entity::decode(&token.literal).unwrap_or(token.literal.clone()),
+        };
 
-        if self.curr_token.clone().unwrap().token_type == TokenType::NewLine {
-            paragraph_text.set_inner(inline_container);
-            return Box::new(paragraph_text);
+        self.paragraph_or_inline(&token, EscapedText::new(value).into())
+    }
+
+    /// `paragraph_or_inline` returns `inline`, the node parsed from `start` to curr_token, as it
+    /// is if it is in a paragraph already (or doesn't start its line), or else the paragraph it
+    /// starts.
+    fn paragraph_or_inline(&mut self, start: &Token, inline: Inline) -> Block {
+        if start.start_col > 1 || self.in_paragraph {
+            return inline.into();
         }
+        self.parse_paragraph(start, inline)
+    }
 
-        let block = self.parse(TokenType::NewLine, true);
-        if let Some(block) = block {
-            match block.as_any().downcast::<InlineContainer>() {
-                Ok(b) => {
-                    inline_container.extend(*b);
-                }
-                Err(_) => {}
+    /// `parse_paragraph` parses the paragraph that starts with `first`, the node parsed from
+    /// `start` (the first token of the line) to curr_token.
+    fn parse_paragraph(&mut self, start: &Token, first: Inline) -> Block {
+        let mut first = Block::Inline(first);
+        self.take_inline_attributes(&mut first);
+        first.set_span(self.span_from(start));
+        let mut inline_container = InlineContainer::new();
+        inline_container.add_child(first);
+
+        self.advance_token();
+        self.parse_rest_of_line(&mut inline_container);
+
+        while self.is_paragraph_continuation() {
+            let mut line_break: Inline = if inline_container.trim_line_end() {
+                HardBreak::new().into()
+            } else {
+                SoftBreak::new().into()
+            };
+            line_break.set_span(self.token_span(&self.curr_token.clone().unwrap()));
+            inline_container.add_child(line_break);
+
+            self.advance_token(); // move to the first token of the next line
+            if self.curr_token.clone().unwrap().token_type == TokenType::Indent {
+                self.advance_token();
             }
-            paragraph_text.set_inner(inline_container);
-            println!("after parsing paragraph text {:#?}", self.curr_token);
+            // the white space at the start of the line isn't part of the paragraph
+            self.trim_line_start();
+
+            let in_paragraph = std::mem::replace(&mut self.in_paragraph, true);
+            self.parse_rest_of_line(&mut inline_container);
+            self.in_paragraph = in_paragraph;
+        }
+        // a hard line break at the end of a paragraph is ignored
+        inline_container.trim_line_end();
+
+        // a paragraph followed by a `===` or `---` line is a (setext) heading
+        if self.peek_token.clone().unwrap().token_type == TokenType::SetextUnderline {
+            self.advance_token(); // move to the underline
+            let underline = self.curr_token.clone().unwrap();
+            self.advance_token(); // move past the underline
+            let level = if underline.literal.contains('=') {
+                1
+            } else {
+                2
+            };
+            return self.heading(level, inline_container).into();
+        }
+
+        // a paragraph followed by a `: definition` line (right after it or after blank lines) is
+        // the first term of a definition list
+        if let Some(line) = self.definition_after_term() {
+            return self.parse_definition_list(inline_container, line);
+        }
+
+        let mut paragraph_text = ParagraphText::new();
+        paragraph_text.set_inner(inline_container);
+        paragraph_text.into()
+    }
+
+    /// `parse_rest_of_line` parses the inline tokens from curr_token up to the end of the line
+    /// into `inline_container`. curr_token is the new line character (or EOF) afterwards.
+    fn parse_rest_of_line(&mut self, inline_container: &mut InlineContainer) {
+        let curr_token = self.curr_token.clone().unwrap();
+        if curr_token.token_type == TokenType::NewLine || curr_token.token_type == TokenType::EOF {
+            return;
         }
 
+        inline_container.extend(self.parse_inline(TokenType::NewLine));
+
         self.advance_token();
-        Box::new(paragraph_text)
     }
 
-    fn parse_link(&mut self) -> Result<(String, String), String> {
-        let mut text = String::new(); // this is to keep track of the token literal seen so far so
-        // we can return textual version if we encounter an
-        // unexpected token before successfully parsing our image
+    /// `is_paragraph_continuation` reports whether the line after curr_token (a new line
+    /// character) goes on with the paragraph before it, ie. it isn't blank and doesn't start a
+    /// block that can interrupt a paragraph (a heading, a list, a code fence, etc.). an indented
+    /// line can't interrupt a paragraph to start a code block, so it is part of it too.
+    fn is_paragraph_continuation(&self) -> bool {
+        let curr_token = self.curr_token.clone().unwrap();
+        let peek_token = self.peek_token.clone().unwrap();
 
-        let curr_token = self.curr_token.clone().unwrap(); // this is expected to be the '[' token
-        text.push_str(&curr_token.literal);
+        if curr_token.token_type != TokenType::NewLine || self.is_blank_line(peek_token.start_line)
+        {
+            return false;
+        }
 
-        if curr_token.token_type != TokenType::LeftBracket {
-            return Err(text);
+        match peek_token.token_type {
+            TokenType::H1
+            | TokenType::H2
+            | TokenType::H3
+            | TokenType::H4
+            | TokenType::H5
+            | TokenType::H6
+            | TokenType::GreaterThan
+            | TokenType::TripleBacktick
+            | TokenType::TripleTilde
+            | TokenType::ThematicBreak
+            | TokenType::SetextUnderline
+            | TokenType::UnorderedListItem
+            | TokenType::OrderedListItem
+            | TokenType::DefinitionMarker
+            | TokenType::MathFence
+            | TokenType::ContainerFence
+            | TokenType::FootnoteDefinition
+            | TokenType::NewLine
+            | TokenType::EOF => false,
+            // the attributes of the paragraph
+            TokenType::Attributes if peek_token.start_col == 1 => !self
+                .source_line(peek_token.start_line)
+                .is_some_and(utils::is_attribute_line),
+            _ => !self.is_toc_marker_line(peek_token.start_line),
         }
+    }
 
+    fn parse_image(&mut self) -> Block {
+        let exclamation = self.curr_token.clone().unwrap();
         self.advance_token(); // update curr_token
-        let curr_token = self.curr_token.clone().unwrap(); // this is expected to be a text token
-        // containing the alt description of the image
-        text.push_str(&curr_token.literal);
+        let token = self.curr_token.clone().unwrap(); // this is expected to be the '[' token
 
-        if curr_token.token_type != TokenType::Text {
-            return Err(text);
-        }
+        let Some((target, text_end, mut end)) = self.scan_link(&token) else {
+            return self.paragraph_or_inline(&exclamation, Text::new("![".to_string()).into());
+        };
 
-        let link_text = curr_token.literal;
+        // images can't hold other nodes, so the alt text is the text of the nodes in the brackets
+        let alt = self.parse_link_text(&token, text_end).plain_text();
+        self.skip_to_col(token.start_line, end);
 
-        self.advance_token();
-        let curr_token = self.curr_token.clone().unwrap(); // this is expected to be the ']' token
-        text.push_str(&curr_token.literal);
+        let mut image = Image::new(target.destination, alt);
+        if let Some(title) = target.title {
+            image.set_title(title);
+        }
 
-        if curr_token.token_type != TokenType::RightBracket {
-            return Err(text);
+        // an attribute block right after the image sets its attributes
+        let rest = self.rest_of_line(token.start_line, end);
+        if rest.starts_with('{')
+            && let Some(close) = rest.find('}')
+            && let Some(attributes) = Attributes::parse(&rest[..=close])
+        {
+            image.set_attributes(attributes);
+            end += rest[..=close].chars().count();
+            self.skip_to_col(token.start_line, end);
         }
 
-        self.advance_token();
-        let curr_token = self.curr_token.clone().unwrap(); // this is expected to be the '(' token
-        text.push_str(&curr_token.literal);
+        image.set_span(self.span_from(&exclamation));
 
-        if curr_token.token_type != TokenType::LeftParen {
-            return Err(text);
+        // an image with a title on a line of its own is a figure, with the title as its caption
+        let standalone = exclamation.start_col == 1
+            && !self.in_paragraph
+            && !self.in_link_text
+            && self.rest_of_line(token.start_line, end).trim().is_empty();
+        if standalone && image.title().is_some() {
+            return Figure::new(image).into();
         }
+        self.paragraph_or_inline(&exclamation, image.into())
+    }
 
-        self.advance_token();
-        let curr_token = self.curr_token.clone().unwrap(); // this is expected to be a text token
-        // containing the src of the image
-        text.push_str(&curr_token.literal);
-
-        if curr_token.token_type != TokenType::Text {
-            return Err(text);
+    /// `take_inline_attributes` gives `node`, the inline element that ends with curr_token, the
+    /// attribute block written right after it (`*text*{.note}`), if there's one.
+    fn take_inline_attributes(&mut self, node: &mut Block) {
+        let curr_token = self.curr_token.clone().unwrap();
+        let peek_token = self.peek_token.clone().unwrap();
+        if node.attributes().is_none()
+            || curr_token.token_type == TokenType::NewLine
+            || curr_token.token_type == TokenType::EOF
+            || peek_token.start_line != curr_token.start_line
+            || peek_token.start_col != curr_token.start_col + curr_token.literal.chars().count()
+        {
+            return;
         }
 
-        let image_src = curr_token.literal;
+        let Some(rest) = self.source_from(&peek_token) else {
+            return;
+        };
+        let Some(block) = rest
+            .find('}')
+            .map(|close| rest[..=close].to_string())
+            .filter(|block| block.starts_with('{'))
+        else {
+            return;
+        };
+        let Some(attributes) = Attributes::parse(&block) else {
+            return;
+        };
 
-        self.advance_token();
-        let curr_token = self.curr_token.clone().unwrap(); // this is expected to be a text token
-        // containing the src of the image
-        text.push_str(&curr_token.literal);
+        if let Some(node_attributes) = node.attributes_mut() {
+            node_attributes.merge(attributes);
+        }
+        self.skip_to_col(
+            peek_token.start_line,
+            peek_token.start_col + block.chars().count() - 1,
+        );
+    }
 
-        if curr_token.token_type != TokenType::RightParen {
-            return Err(text);
+    /// `take_block_attributes` gives `block` the attribute block on the line after it (`{.note}`),
+    /// if there's one. curr_token is the new line that ends the block.
+    fn take_block_attributes(&mut self, block: &mut Block) {
+        let curr_token = self.curr_token.clone().unwrap();
+        let peek_token = self.peek_token.clone().unwrap();
+        if curr_token.token_type != TokenType::NewLine
+            || peek_token.token_type != TokenType::Attributes
+            || peek_token.start_col != 1
+        {
+            return;
         }
 
-        Ok((link_text, image_src))
+        let Some(attributes) = Attributes::parse(&peek_token.literal) else {
+            return;
+        };
+        let Some(block_attributes) = block.attributes_mut() else {
+            return;
+        };
+        block_attributes.merge(attributes);
+        block.set_span(block.span().to(self.token_span(&peek_token)));
+        self.advance_token(); // move to the attribute block
+        self.advance_token(); // move to the new line ending it
     }
 
-    fn parse_image(&mut self) -> Box<dyn Node> {
-        self.advance_token(); // update curr_token
-        let res = self.parse_link();
-        if res.is_ok() {
-            let content = res.unwrap();
-            Box::new(Image::new(content.1, content.0))
-        } else {
-            Box::new(Text::new("!".to_string() + &res.err().unwrap()))
-        }
+    /// `rest_of_line` returns the source of `line` after its first `col` characters.
+    fn rest_of_line(&self, line: usize, col: usize) -> String {
+        self.source_line(line)
+            .unwrap_or_default()
+            .chars()
+            .skip(col)
+            .collect()
     }
 
     pub fn parse_program(&mut self) -> Program {
         println!("parsing program");
         let mut program = Program::new();
+        let source = self.lexer.src.clone();
+        self.state.source = LineIndex::new(&source);
+        self.state.collect_definitions(&source);
+        program.set_span(self.state.source.span(0, source.len()));
+
+        for block in self.parse_blocks() {
+            program.add_block(block);
+        }
+
+        if let Some(footnotes) = self.footnote_section() {
+            program.add_block(footnotes);
+        }
+
+        // the table of contents and the task progress can come before some of the headings and
+        // tasks they are made of, so they are only filled in once the whole document has been
+        // parsed
+        program.set_headings(self.state.headings.clone());
+        let mut tasks = TaskCounter::default();
+        tasks.visit_program(&program);
+        program.set_task_progress(tasks.progress);
+
+        program
+    }
+
+    /// `warnings` returns the problems found in the document while parsing it.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.state.warnings
+    }
+
+    fn parse_blocks(&mut self) -> Vec<Block> {
+        let mut blocks: Vec<Block> = vec![];
         if self.curr_token.is_none() {
-            return program;
+            return blocks;
         }
 
         while let Some(token) = self.curr_token.clone() {
@@ -275,83 +659,392 @@ impl Parser {
                 break;
             }
 
-            let block = self.parse(TokenType::NewLine, false);
-            if let Some(block) = block {
+            if token.token_type == TokenType::Indent && self.is_blank_line(token.start_line) {
+                // a line with nothing but white space, it doesn't start an indented code block
+                self.advance_token();
+                continue;
+            }
+
+            if self.is_toc_marker_line(token.start_line) {
+                // curr_token can be the new line before the marker, so the span is the one of
+                // the marker on its line
+                let text = self.source_line(token.start_line).unwrap_or_default();
+                let start =
+                    self.line_offset(token.start_line) + text.len() - text.trim_start().len();
+                let mut toc = TableOfContents::new();
+                toc.set_span(self.state.source.span(start, start + text.trim().len()));
+                blocks.push(toc.into());
+                self.skip_to_end_of_line(token.start_line);
+                self.advance_token();
+                continue;
+            }
+
+            if let Some((label, link_reference)) = self.link_reference_definition(token.start_line)
+            {
+                // the definitions of the document were collected before parsing, the ones in
+                // nested content are recorded here. they aren't written out.
+                self.state
+                    .link_references
+                    .entry(label)
+                    .or_insert(link_reference);
+                self.skip_to_end_of_line(token.start_line);
+                self.advance_token();
+                continue;
+            }
+
+            if token.token_type == TokenType::FootnoteDefinition {
+                // definitions aren't written where they are, but in the footnotes section
+                self.parse_footnote_definition();
+                self.advance_token();
+                continue;
+            }
+
+            let block = self.parse_node(&TokenType::NewLine);
+            if let Some(mut block) = block {
+                self.take_block_attributes(&mut block);
                 println!("adding a new block to program {:?}", block.token_literal());
-                program.add_block(block);
+                blocks.push(block);
             }
             self.advance_token();
         }
 
-        program
+        blocks
+    }
+
+    /// `parse_autolink` parses an autolink (`<https://example.com>`) or a bare url or email
+    /// address into a link showing the url (or address) itself.
+    fn parse_autolink(&mut self) -> Block {
+        let token = self.curr_token.clone().unwrap();
+        if self.in_link_text {
+            return Inline::EscapedText(EscapedText::new(token.literal)).into();
+        }
+
+        let text = match token.token_type {
+            TokenType::Autolink => &token.literal[1..token.literal.len() - 1],
+            _ => &token.literal,
+        };
+        let mut link = Link::new(autolink::autolink_destination(text));
+        link.children
+            .push(EscapedText::new(text.to_string()).into());
+
+        self.paragraph_or_inline(&token, link.into())
     }
 
-    fn parse_bold_text(&mut self) -> Box<dyn Node> {
+    /// `parse_math` parses a `$...$` (or `$$...$$`) math span. the TeX in it is kept as it is:
+    /// emphasis, escapes and the like aren't parsed in math.
+    fn parse_math(&mut self) -> Block {
+        let token = self.curr_token.clone().unwrap();
+        let display = token.literal.starts_with("$$");
+        let delimiter = if display { 2 } else { 1 };
+        let tex = &token.literal[delimiter..token.literal.len() - delimiter];
+
+        self.paragraph_or_inline(&token, InlineMath::new(tex.to_string(), display).into())
+    }
+
+    /// `parse_container` parses the container opened by the `:::name` fence that is curr_token,
+    /// up to the `:::` line that closes it (or the end of the document). containers can be
+    /// nested: every `:::name` line in the container opens another one, which the next `:::`
+    /// line closes. the content is parsed on its own, so a container can hold any block.
+    fn parse_container(&mut self) -> Block {
+        let fence = self.curr_token.clone().unwrap();
+        let mut info = self
+            .rest_of_line(fence.start_line, fence.literal.len())
+            .trim()
+            .to_string();
+
+        // an attribute block at the end of the fence line sets the attributes of the container
+        let mut attributes = None;
+        if info.ends_with('}')
+            && let Some(open) = info.rfind('{')
+            && let Some(parsed) = Attributes::parse(&info[open..])
+        {
+            attributes = Some(parsed);
+            info.truncate(open);
+        }
+        let (name, argument) = info.trim().split_once(' ').unwrap_or((info.trim(), ""));
+        let mut container = Container::new(name.to_string(), argument.trim().to_string());
+        if let Some(attributes) = attributes {
+            container.attributes.merge(attributes);
+        }
+
+        let mut content = String::new();
+        let mut depth = 1;
+        // the fence of the code block the line is in: the `:::` lines in it are code
+        let mut code_fence: Option<(char, usize)> = None;
+        let mut line = fence.start_line + 1;
+        while let Some(text) = self.source_line(line) {
+            if let Some((fence_char, fence_len)) = code_fence {
+                if is_closing_fence(text.trim_start(), fence_char, fence_len) {
+                    code_fence = None;
+                }
+            } else if utils::indentation(text) < 4
+                && let Some(opening) = opening_fence(text.trim_start())
+            {
+                code_fence = Some(opening);
+            } else if text.starts_with(":::") {
+                if text.trim_start_matches(':').trim().is_empty() {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    depth += 1;
+                }
+            }
+            content.push_str(text);
+            content.push('\n');
+            line += 1;
+        }
+        self.skip_to_end_of_line(line);
+
+        for block in self.parse_nested(&content, fence.start_line + 1) {
+            container.add_child(block);
+        }
+        container.into()
+    }
+
+    /// `parse_math_block` parses the display math between a `$$` line and the next one (or the
+    /// end of the document).
+    fn parse_math_block(&mut self) -> Block {
+        let fence = self.curr_token.clone().unwrap();
+        let mut tex = String::new();
+
+        let mut line = fence.start_line + 1;
+        while let Some(text) = self.source_line(line) {
+            if text.trim_end() == "$$" {
+                break;
+            }
+            tex.push_str(text);
+            tex.push('\n');
+            line += 1;
+        }
+
+        self.skip_to_end_of_line(line);
+        MathBlock::new(tex).into()
+    }
+
+    fn parse_footnote_reference(&mut self) -> Inline {
+        let token = self.curr_token.clone().unwrap();
+        let label = &token.literal[2..token.literal.len() - 1];
+
+        if !self.state.footnote_labels.contains(label) {
+            self.state.warnings.push(Warning::new(
+                token.start_line,
+                token.start_col,
+                format!(
+                    "footnote `{}` is referenced but never defined",
+                    token.literal
+                ),
+            ));
+            return Text::new(token.literal).into();
+        }
+
+        let references = &mut self.state.footnote_references;
+        let number = match references.iter().position(|(l, _)| l == label) {
+            Some(index) => index + 1,
+            None => {
+                references.push((label.to_string(), 0));
+                references.len()
+            }
+        };
+        references[number - 1].1 += 1;
+
+        FootnoteReference::new(number, references[number - 1].1).into()
+    }
+
+    /// `parse_footnote_definition` parses the content of the footnote definition starting at
+    /// curr_token: the rest of its line and the lines indented by 4 spaces after it.
+    fn parse_footnote_definition(&mut self) {
+        let marker = self.curr_token.clone().unwrap();
+        let label = marker.literal[2..marker.literal.len() - 2].to_string();
+
+        let (content, last_line, _) =
+            self.read_nested_content(marker.start_line, marker.literal.len(), 4);
+        self.skip_to_end_of_line(last_line);
+        let span = self.span_from(&marker);
+
+        let blocks = self.parse_nested(&content, marker.start_line);
+        // if a footnote is defined more than once, the first definition is used
+        self.state
+            .footnote_definitions
+            .entry(label)
+            .or_insert((span, blocks));
+    }
+
+    /// `footnote_section` takes the definitions of the referenced footnotes, in order, and puts
+    /// them in a footnotes section. it returns `None` if no footnote was referenced.
+    fn footnote_section(&mut self) -> Option<FootnoteSection> {
+        if self.state.footnote_references.is_empty() {
+            return None;
+        }
+
+        let mut section = FootnoteSection::new();
+        for (index, (label, references)) in self.state.footnote_references.iter().enumerate() {
+            let (span, children) = self
+                .state
+                .footnote_definitions
+                .remove(label)
+                .unwrap_or_default();
+            let mut footnote = Footnote::new(index + 1, *references, children);
+            footnote.set_span(span);
+            section.add_footnote(footnote);
+        }
+        Some(section)
+    }
+
+    /// `link_reference_definition` returns the link reference definition on `line`, if it is
+    /// one. a line indented by 4 spaces or more is code, not a definition.
+    fn link_reference_definition(&self, line: usize) -> Option<(String, LinkReference)> {
+        self.source_line(line)
+            .filter(|line| utils::indentation(line) < 4)
+            .and_then(reference::parse_definition)
+    }
+
+    /// `is_toc_marker_line` reports whether `line` is a `[[toc]]` marker on its own.
+    fn is_toc_marker_line(&self, line: usize) -> bool {
+        self.source_line(line)
+            .is_some_and(|line| line.trim() == "[[toc]]")
+    }
+
+    fn parse_bold_text(&mut self) -> Inline {
         println!("parsing bold text");
         let mut bold_text = BoldText::new();
         self.advance_token();
 
-        let block = self.parse(TokenType::DoubleAsterisk, true);
-        if let Some(block) = block {
-            bold_text.set_inner(block);
-            println!("after parsing bold text {:#?}", self.curr_token);
-        }
+        bold_text.set_inner(self.parse_inline(TokenType::DoubleAsterisk));
+        println!("after parsing bold text {:#?}", self.curr_token);
 
         self.advance_token();
-        Box::new(bold_text)
+        bold_text.into()
     }
 
-    fn parse_heading(&mut self, level: i8) -> Box<dyn Block> {
+    fn parse_heading(&mut self, level: i8) -> Block {
         println!("parsing heaeding {}", level);
         self.advance_token();
-        let mut h1 = Heading::new(level);
 
-        let block = self.parse(TokenType::NewLine, true);
-        if let Some(block) = block {
-            h1.set_inner(block);
-            println!("after parsing bold text {:#?}", self.curr_token);
+        let inner = self.parse_inline(TokenType::NewLine);
+        let heading = self.heading(level, inner);
+
+        self.advance_token();
+        heading.into()
+    }
+
+    /// `heading` makes a heading of the given level out of its content, giving it the custom id
+    /// from a trailing `{#id}` (and the other attributes of the block) or one made from its text. it is recorded as one of the headings
+    /// of the document.
+    fn heading(&mut self, level: i8, mut inner: InlineContainer) -> Heading {
+        let mut heading = Heading::new(level);
+
+        let mut attributes = inner.take_attributes().unwrap_or_default();
+        let id = match attributes.take_id() {
+            Some(id) => Some(id),
+            None => self.unique_heading_id(&utils::slugify(&inner.plain_text())),
+        };
+
+        if let Some(id) = id {
+            self.state.heading_ids.insert(id.clone());
+            self.state
+                .headings
+                .push(TocEntry::new(level, id.clone(), inner.plain_text()));
+            heading.set_id(id);
         }
+        heading.attributes.merge(attributes);
+        heading.set_inner(inner);
+        heading
+    }
 
+    fn parse_thematic_break(&mut self) -> Block {
         self.advance_token();
-        Box::new(h1)
+        ThematicBreak::new().into()
+    }
+
+    /// `unique_heading_id` returns `slug` if no heading has it as its id yet, otherwise `slug`
+    /// with the first free `-1`, `-2`, ... suffix.
+    fn unique_heading_id(&self, slug: &str) -> Option<String> {
+        if slug.is_empty() {
+            return None;
+        }
+        if !self.state.heading_ids.contains(slug) {
+            return Some(slug.to_string());
+        }
+
+        (1..)
+            .map(|suffix| format!("{}-{}", slug, suffix))
+            .find(|id| !self.state.heading_ids.contains(id))
     }
 
-    fn parse_italics(&mut self) -> Box<dyn Node> {
+    fn parse_italics(&mut self) -> Inline {
         println!("parsing italicized text");
         let mut italicized_text = ItalicizedText::new();
         self.advance_token();
 
-        let block = self.parse(TokenType::Asterisk, true);
-        if let Some(block) = block {
-            italicized_text.set_inner(block);
-            println!("after parsing italicized text {:#?}", self.curr_token);
-        }
+        italicized_text.set_inner(self.parse_inline(TokenType::Asterisk));
+        println!("after parsing italicized text {:#?}", self.curr_token);
 
         self.advance_token();
-        Box::new(italicized_text)
+        italicized_text.into()
     }
 
-    fn parse_blockquote(&mut self) -> Box<dyn Node> {
-        self.advance_token();
-
-        let curr_token = self.curr_token.clone().unwrap();
-        if curr_token.token_type == TokenType::NewLine {
-            return Box::new(Text::new(">".to_string()));
+    /// `parse_blockquote` parses the block quote whose first `>` is curr_token: the lines
+    /// starting with `>` and the lazy continuation lines of a paragraph in it. the text after
+    /// the `>`s is parsed on its own, so a quote can hold any block. a quote whose first line is
+    /// an alert marker (`[!NOTE]`, `[!WARNING]`, etc.) is an alert.
+    fn parse_blockquote(&mut self) -> Block {
+        let marker = self.curr_token.clone().unwrap();
+        let (content, last_line) = self.read_block_quote_content(marker.start_line);
+        self.skip_to_end_of_line(last_line);
+
+        let (first_line, rest) = content.split_once('\n').unwrap_or((&content, ""));
+        if let Some(kind) = AlertKind::from_marker(first_line.trim()) {
+            let mut alert = Alert::new(kind);
+            for block in self.parse_nested(rest, marker.start_line + 1) {
+                alert.add_child(block);
+            }
+            return alert.into();
         }
 
         let mut block_quote = BlockQuote::new();
+        for block in self.parse_nested(&content, marker.start_line) {
+            block_quote.add_child(block);
+        }
+        block_quote.into()
+    }
 
-        let content = self.parse(TokenType::NewLine, true);
-        if let Some(content) = content {
-            println!("done parsing block quote");
-            block_quote.set_inner(content);
+    /// `read_block_quote_content` returns the content of the block quote starting at line `line`
+    /// without the `>` (and the space after it) at the start of its lines, with the number of
+    /// its last line.
+    fn read_block_quote_content(&self, line: usize) -> (String, usize) {
+        let mut content = String::new();
+        let mut last_line = line;
+        // the last line of the content, without the `>`s of the quotes nested in this one
+        let mut previous = String::new();
+
+        let mut line_number = line;
+        while let Some(line) = self.source_line(line_number) {
+            if let Some(rest) = line.strip_prefix('>') {
+                let rest = rest.strip_prefix(' ').unwrap_or(rest);
+                content.push_str(rest);
+                previous = rest.trim_start_matches(['>', ' ']).to_string();
+            } else if utils::is_paragraph_line(&previous)
+                && !line.trim().is_empty()
+                && !utils::interrupts_paragraph(line)
+            {
+                // a lazy line: the paragraph at the end of the quote goes on even though the
+                // line doesn't start with `>`
+                content.push_str(line.trim_start());
+                previous = line.trim_start().to_string();
+            } else {
+                break;
+            }
+            content.push('\n');
+            last_line = line_number;
+            line_number += 1;
         }
 
-        self.advance_token();
-        Box::new(block_quote)
+        (content, last_line)
     }
 
-    fn parse_inline_code(&mut self) -> Box<dyn Node> {
+    fn parse_inline_code(&mut self) -> Inline {
         println!("parsing inline code");
         let mut code_content = String::new();
 
@@ -359,9 +1052,13 @@ impl Parser {
 
         while let Some(token) = self.curr_token.clone() {
             if token.token_type == TokenType::NewLine || token.token_type == TokenType::EOF {
-                return Box::new(Text::new(code_content));
+                return Text::new(code_content).into();
             } else if token.token_type == TokenType::Backtick {
-                return Box::new(InlineCode::new(code_content));
+                return InlineCode::new(code_content).into();
+            } else if token.token_type == TokenType::Escape && token.literal == "\\`" {
+                // backslash escapes don't work in code, so the backtick still ends it
+                code_content.push('\\');
+                return InlineCode::new(code_content).into();
             }
 
             code_content.push_str(&token.literal);
@@ -369,128 +1066,638 @@ impl Parser {
         }
 
         // this should never run
-        Box::new(Text::new("".to_string()))
+        Text::new("".to_string()).into()
     }
 
-    fn parse_code_block(&mut self) -> Box<dyn Node> {
+    fn parse_code_block(&mut self) -> Block {
         println!("parsing code block");
 
+        let fence = self.curr_token.clone().unwrap();
         self.advance_token();
-        let language: String;
-        let mut content = String::new();
 
-        let mut curr_token = self.curr_token.clone().unwrap();
+        // everything between the opening fence and the end of the line is the info string
+        let mut info_string = String::new();
+        while let Some(token) = self.curr_token.clone() {
+            if token.token_type == TokenType::NewLine || token.token_type == TokenType::EOF {
+                break;
+            }
+            info_string.push_str(&token.literal);
+            self.advance_token();
+        }
+
+        if fence.token_type == TokenType::TripleBacktick && info_string.contains('`') {
+            // the info string of a backtick fence can't contain backticks, so this isn't a fence
+            return Inline::Text(Text::new(fence.literal + &info_string)).into();
+        }
+
+        self.advance_token(); // move past the new line character ending the info string
+        let mut content = String::new();
 
-        match curr_token.token_type {
-            TokenType::Text => {
-                language = curr_token.literal.trim().to_string();
-                self.advance_token(); // move past the text token (the language of the program)
-                self.advance_token(); // move past the new line character token
+        while let Some(token) = self.curr_token.clone() {
+            if token.token_type == TokenType::EOF {
+                break;
             }
-            TokenType::NewLine => {
+
+            if self.is_closing_fence(&token, &fence) {
+                // a closing fence may only be followed by white space on its line. if anything
+                // else follows, the fence and what we skipped over are part of the code.
                 self.advance_token();
-                language = String::new();
+                let mut skipped = String::new();
+                while let Some(next) = self.curr_token.clone() {
+                    if next.token_type != TokenType::Text || !next.literal.trim().is_empty() {
+                        break;
+                    }
+                    skipped.push_str(&next.literal);
+                    self.advance_token();
+                }
+
+                let next = self.curr_token.clone().unwrap();
+                if next.token_type == TokenType::NewLine || next.token_type == TokenType::EOF {
+                    break;
+                }
+
+                content.push_str(&token.literal);
+                content.push_str(&skipped);
+                continue;
             }
-            _ => return Box::new(Text::new("```".to_owned())),
-        };
 
-        curr_token = self.curr_token.clone().unwrap();
+            content.push_str(&token.literal);
+            self.advance_token();
+        }
+
+        CodeBlock::from_info_string(content, &info_string).into()
+    }
 
-        while curr_token.token_type != TokenType::EOF
-            && curr_token.token_type != TokenType::TripleBacktick
+    /// `is_closing_fence` reports whether `token` can close the code block opened by `fence`.
+    /// it has to be made of the same character and be at least as long as the opening fence.
+    fn is_closing_fence(&self, token: &Token, fence: &Token) -> bool {
+        token.token_type == fence.token_type
+            && token.start_col == 1
+            && token.literal.len() >= fence.literal.len()
+    }
+
+    fn parse_link_start(&mut self) -> Inline {
+        println!("parsing a new link with token {:#?}", self.curr_token);
+        let token = self.curr_token.clone().unwrap(); // this is expected to be the '[' token
+
+        if self
+            .source_from(&token)
+            .is_some_and(|text| text.starts_with(PROGRESS_MARKER))
         {
-            content.push_str(&curr_token.literal);
-            self.advance_token();
-            curr_token = self.curr_token.clone().unwrap();
+            self.skip_to_col(
+                token.start_line,
+                token.start_col + PROGRESS_MARKER.len() - 1,
+            );
+            // the progress is counted once the whole document has been parsed
+            return TaskProgress::default().into();
         }
 
-        self.advance_token(); // move past the code block so you can parse other tokens
-        println!("{:?}", self.curr_token.to_owned().unwrap());
+        let Some((target, text_end, end)) = self.scan_link(&token) else {
+            return Text::new(token.literal).into();
+        };
+
+        let inner = self.parse_link_text(&token, text_end);
+        self.skip_to_col(token.start_line, end);
 
-        Box::new(CodeBlock::new(content, language))
+        let mut link = Link::new(target.destination);
+        link.set_inner(inner);
+        if let Some(title) = target.title {
+            link.set_title(title);
+        }
+        link.into()
     }
 
-    fn parse_link_start(&mut self) -> Box<dyn Inline> {
-        print!("parsing a new link...");
-        print!("with token {:#?}", self.curr_token);
-        let res = self.parse_link();
-        println!("{:?}", res);
-        if res.is_ok() {
-            let content = res.unwrap();
-            Box::new(Link::new(content.0, content.1))
-        } else {
-            Box::new(Text::new("!".to_string() + &res.err().unwrap()))
+    /// `scan_link` reads the source of the link (or image) whose `[` is `token`, without moving
+    /// curr_token. it returns where the link points to, the column of the `]` after the link
+    /// text and the column of the last character of the link, or `None` if it isn't a link.
+    fn scan_link(&self, token: &Token) -> Option<(LinkReference, usize, usize)> {
+        let text = self.source_from(token)?;
+
+        // the brackets inside the link text have to be balanced
+        let mut depth = 0;
+        let mut text_end = None;
+        let mut escaped = false;
+        for (index, ch) in text.char_indices() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            match ch {
+                '\\' => escaped = true,
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        text_end = Some(index);
+                        break;
+                    }
+                }
+                _ => {}
+            }
         }
+
+        let text_end = text_end?;
+        let link_text = &text[1..text_end];
+        let rest = &text[text_end + 1..];
+
+        let inline_target = || reference::parse_inline_target(rest);
+        // a full (`[text][label]`) or collapsed (`[text][]`) reference
+        let full_reference = || {
+            let label_end = rest.strip_prefix('[')?.find(']')? + 1;
+            let label = match &rest[1..label_end] {
+                "" => link_text,
+                label => label,
+            };
+            Some((self.link_reference(label)?, label_end + 1))
+        };
+        // a shortcut reference (`[text]`)
+        let shortcut_reference = || Some((self.link_reference(link_text)?, 0));
+
+        let (target, len) = inline_target()
+            .or_else(full_reference)
+            .or_else(shortcut_reference)?;
+
+        let text_end_col = token.start_col + text[..text_end].chars().count();
+        let end_col = text_end_col + rest[..len].chars().count();
+        Some((target, text_end_col, end_col))
     }
 
-    fn parse_unordered_list_item(&mut self, list: &mut Box<UnorderedList>) {
-        loop {
-            let content = self.parse(TokenType::NewLine, true); // parse everything up until the
-            // next new line character
-            let mut list_item = Box::new(ListItem::new()); // create a list item
+    fn link_reference(&self, label: &str) -> Option<LinkReference> {
+        self.state
+            .link_references
+            .get(&reference::normalize_label(label))
+            .cloned()
+    }
 
-            if let Some(inner) = content {
-                list_item.set_inner(inner); // set the inner of the list item to this parsed node
-                list.add_list_item(list_item);
-            } else {
-                // content is only None when the end_token equals curr_token. so for this to
-                // panic that means curr_token should equal the new line character but this
-                // would be true because there is at least a white space (" ") character after
-                // the - token for this function to even be called
-                panic!(
-                    "content should never return none because we'd definitely have a space text token after the - token"
-                )
+    /// `parse_link_text` parses the inline nodes after the link's `[` (`token`) up to its `]`
+    /// at column `text_end`, where curr_token is left.
+    fn parse_link_text(&mut self, token: &Token, text_end: usize) -> InlineContainer {
+        let mut inner = InlineContainer::new();
+        self.advance_token();
+        self.in_link_text = true;
+
+        while let Some(curr_token) = self.curr_token.clone() {
+            if curr_token.token_type == TokenType::EOF
+                || curr_token.start_line != token.start_line
+                || curr_token.start_col >= text_end
+            {
+                break;
             }
-            println!("curr token {:?}", self.curr_token.clone().unwrap());
 
-            // at this point, curr_token should be the new line character;
+            if let Some(node) = self.parse_node(&TokenType::NewLine) {
+                inner.add_child(node);
+            }
             self.advance_token();
-            self.advance_token(); // after this we should expect the next token to be an
-            // unordered list item token. if it's not we can break the
-            // loop and return the list node.
-            //
-            println!("curr token {:?}", self.curr_token.clone().unwrap());
-            if self.curr_token.clone().unwrap().token_type == TokenType::UnorderedListItem {
-                self.advance_token();
+        }
+
+        self.in_link_text = false;
+        inner
+    }
+
+    /// `parse_list_items` parses the items of the list whose first marker is curr_token.
+    ///
+    /// an item is made up of the line with its marker and all the lines after it that are
+    /// indented at least as far as the content after the marker (blank lines in between are
+    /// allowed). that indentation is removed and the content is parsed on its own, so an item
+    /// can hold any block: paragraphs, nested lists, code blocks, etc.
+    fn parse_list_items(&mut self, marker_type: TokenType) -> Vec<ListItem> {
+        // the content of every item, with the line it starts on and the span of the item
+        let mut contents: Vec<NestedContent> = vec![];
+        let mut loose = false;
+
+        loop {
+            let marker = self.curr_token.clone().unwrap();
+            let (content, last_line, has_blank_lines) = self.read_nested_content(
+                marker.start_line,
+                marker.literal.len(),
+                marker.literal.len(),
+            );
+            loose |= has_blank_lines;
+
+            self.skip_to_end_of_line(last_line);
+            contents.push((content, marker.start_line, self.span_from(&marker)));
+
+            // the list goes on if the next line that isn't blank is another item
+            match self.next_non_blank_line(last_line + 1) {
+                Some(line) if self.is_list_marker_line(line, &marker_type) => {
+                    loose |= line > last_line + 1;
+                    self.skip_to_line(line);
+                }
+                _ => break,
+            }
+        }
+
+        let mut items: Vec<ListItem> = vec![];
+        for (content, line, span) in contents {
+            let mut list_item = ListItem::new();
+            list_item.set_span(span);
+            // the content of a task list item (`- [x] ...`) starts after its checkbox
+            let content = match utils::task_marker(&content) {
+                Some(checked) => {
+                    list_item.set_checked(checked);
+                    content[3..].trim_start_matches([' ', '\t']).to_string()
+                }
+                None => content,
+            };
+
+            for block in self.parse_nested(&content, line) {
+                // the paragraphs of a tight list (one without blank lines between its items or
+                // their blocks) aren't wrapped in <p> tags
+                list_item.add_child(if loose {
+                    block
+                } else {
+                    unwrap_plain_paragraph(block)
+                });
+            }
+            items.push(list_item);
+        }
+        items
+    }
+
+    /// `parse_definition_list` parses the definition list whose first term is `term`. curr_token
+    /// is the new line after the term and `line` is the line of its first definition.
+    ///
+    /// each definition starts with a `: ` marker and goes on like a list item: the lines after it
+    /// that are indented at least as far as the content after the marker are part of it. a term
+    /// can have several definitions, and the list goes on with the next term (a line followed by
+    /// a definition) after them. blank lines can come between a term and its definitions. like
+    /// lists, the paragraphs of a list without blank lines aren't wrapped in <p> tags.
+    fn parse_definition_list(&mut self, term: InlineContainer, line: usize) -> Block {
+        // the terms with the content of their definitions, the lines they start on and their
+        // spans
+        let mut items: Vec<(InlineContainer, Vec<NestedContent>)> = vec![(term, vec![])];
+        let mut loose = line > self.curr_token.clone().unwrap().start_line;
+        self.skip_to_line(line); // move to the definition marker
+
+        loop {
+            let marker = self.curr_token.clone().unwrap();
+            let (content, last_line, has_blank_lines) = self.read_nested_content(
+                marker.start_line,
+                marker.literal.len(),
+                self.definition_offset(marker.start_line),
+            );
+            loose |= has_blank_lines;
+            self.skip_to_end_of_line(last_line);
+            let span = self.span_from(&marker);
+            items
+                .last_mut()
+                .unwrap()
+                .1
+                .push((content, marker.start_line, span));
+
+            let Some(line) = self.next_non_blank_line(last_line + 1) else {
+                break;
+            };
+            if self.is_definition_line(line) {
+                loose |= line > last_line + 1;
+                self.skip_to_line(line);
+            } else if self.source_line(line).is_some_and(utils::is_paragraph_line)
+                && let Some(definition) = self
+                    .next_non_blank_line(line + 1)
+                    .filter(|definition| self.is_definition_line(*definition))
+            {
+                // the next term
+                self.skip_to_line(line);
+                self.trim_line_start();
+
+                let mut term = InlineContainer::new();
+                let in_paragraph = std::mem::replace(&mut self.in_paragraph, true);
+                self.parse_rest_of_line(&mut term);
+                self.in_paragraph = in_paragraph;
+                term.trim_line_end();
+
+                items.push((term, vec![]));
+                loose |= definition > line + 1;
+                self.skip_to_line(definition); // move to the definition marker
             } else {
                 break;
             }
         }
+
+        let mut definition_list = DefinitionList::new();
+        for (term, contents) in items {
+            let mut span = term.span();
+            let mut item = DefinitionItem::new(term);
+            for (content, line, definition_span) in contents {
+                let mut definition = Definition::new();
+                definition.set_span(definition_span);
+                span = span.to(definition_span);
+                for block in self.parse_nested(&content, line) {
+                    definition.add_child(if loose {
+                        block
+                    } else {
+                        unwrap_plain_paragraph(block)
+                    });
+                }
+                item.add_definition(definition);
+            }
+            item.set_span(span);
+            definition_list.add_item(item);
+        }
+        definition_list.into()
     }
 
-    fn parse_ordered_list_item(&mut self, list: &mut Box<OrderedList>) {
-        println!("parsing ordered list....");
-        loop {
-            let content = self.parse(TokenType::NewLine, true); // parse everything up until the
-            // next new line character
-            let mut list_item = Box::new(ListItem::new()); // create a list item
+    fn is_definition_line(&self, line: usize) -> bool {
+        self.source_line(line)
+            .is_some_and(|line| line.starts_with(": "))
+    }
+
+    /// `definition_after_term` returns the line of the first definition of the term (or
+    /// paragraph) ending at curr_token, if it is followed by one.
+    fn definition_after_term(&self) -> Option<usize> {
+        let new_line = self.curr_token.clone().unwrap();
+        if new_line.token_type != TokenType::NewLine {
+            return None;
+        }
+        self.next_non_blank_line(new_line.start_line)
+            .filter(|line| self.is_definition_line(*line))
+    }
+
+    /// `definition_offset` returns the indentation of the content of the definition on `line`:
+    /// the width of its `:` and the spaces after it. with 5 spaces or more, the content is
+    /// indented code that starts after the first one.
+    fn definition_offset(&self, line: usize) -> usize {
+        let text = self.source_line(line).unwrap_or_default();
+        match text[1..].chars().take_while(|ch| *ch == ' ').count() {
+            spaces @ 1..=4 => 1 + spaces,
+            _ => 2,
+        }
+    }
 
-            if let Some(inner) = content {
-                list_item.set_inner(inner); // set the inner of the list item to this parsed node
-                list.add_list_item(list_item);
+    /// `read_nested_content` returns the content of a block that holds other blocks (eg. a list
+    /// item) with its indentation removed, the number of its last line and whether it has blank
+    /// lines between its blocks.
+    ///
+    /// the content starts at byte `start` of line `line` (after the list marker, etc.) and goes on
+    /// for as long as the lines after it are blank or indented by at least `offset` spaces, or
+    /// are lazy continuation lines of a paragraph.
+    fn read_nested_content(
+        &self,
+        line: usize,
+        start: usize,
+        offset: usize,
+    ) -> (String, usize, bool) {
+        let first_line = self.source_line(line).unwrap_or_default();
+
+        let mut content = first_line
+            .get(start..)
+            .unwrap_or_default()
+            .trim_start()
+            .to_string()
+            + "\n";
+        let mut last_line = line;
+        let mut blank_lines = 0;
+        let mut has_blank_lines = false;
+        // the last line added to the content (without its indentation)
+        let mut previous = content.trim_end().to_string();
+
+        let mut line_number = line + 1;
+        while let Some(line) = self.source_line(line_number) {
+            if line.trim().is_empty() {
+                blank_lines += 1;
+            } else if utils::indentation(line) >= offset {
+                has_blank_lines |= blank_lines > 0;
+                content.push_str(&"\n".repeat(blank_lines));
+                content.push_str(&line[offset..]);
+                content.push('\n');
+                blank_lines = 0;
+                last_line = line_number;
+                previous = line[offset..].to_string();
+            } else if blank_lines == 0
+                && utils::is_paragraph_line(&previous)
+                && !utils::interrupts_paragraph(line)
+                && !self.is_definition_line(line_number + 1)
+            {
+                // a lazy line: the paragraph the content ends with goes on even though the line
+                // isn't indented (unless it's the term of a definition)
+                content.push_str(line.trim_start());
+                content.push('\n');
+                last_line = line_number;
+                previous = line.trim_start().to_string();
             } else {
-                // content is only None when the end_token equals curr_token. so for this to
-                // panic that means curr_token should equal the new line character but this
-                // would be true because there is at least a white space (" ") character after
-                // the - token for this function to even be called
-                panic!(
-                    "content should never return none because we'd definitely have a space text token after the - token"
-                )
+                break;
             }
-            println!("curr token {:?}", self.curr_token.clone().unwrap());
+            line_number += 1;
+        }
 
-            // at this point, curr_token should be the new line character;
+        (content, last_line, has_blank_lines)
+    }
+
+    fn is_list_marker_line(&self, line: usize, marker_type: &TokenType) -> bool {
+        let line = self.source_line(line).unwrap_or_default();
+        utils::list_marker(line)
+            .is_some_and(|(_, ordered)| ordered == (*marker_type == TokenType::OrderedListItem))
+    }
+
+    fn parse_indented_code_block(&mut self) -> Block {
+        let mut content = String::new();
+
+        loop {
+            // curr_token is the indentation at the start of a line of code. the first 4 spaces
+            // belong to the code block, anything after that is part of the code.
+            let indent = self.curr_token.clone().unwrap();
+            content.push_str(&indent.literal[4..]);
             self.advance_token();
-            self.advance_token(); // after this we should expect the next token to be an
-            // unordered list item token. if it's not we can break the
-            // loop and return the list node.
-            //
-            println!("curr token {:?}", self.curr_token.clone().unwrap());
-            if self.curr_token.clone().unwrap().token_type == TokenType::OrderedListItem {
+
+            while let Some(token) = self.curr_token.clone() {
+                if token.token_type == TokenType::NewLine || token.token_type == TokenType::EOF {
+                    break;
+                }
+                content.push_str(&token.literal);
                 self.advance_token();
-            } else {
+            }
+            content.push('\n');
+
+            let new_line = self.curr_token.clone().unwrap();
+            if new_line.token_type == TokenType::EOF {
                 break;
             }
+
+            // blank lines are part of the code block only if more code comes after them
+            let next_line = match self.next_non_blank_line(new_line.start_line) {
+                Some(line) if utils::indentation(self.source_line(line).unwrap()) >= 4 => line,
+                _ => break,
+            };
+
+            for line in new_line.start_line..next_line {
+                let blank_line = self.source_line(line).unwrap();
+                content.push_str(blank_line.get(4..).unwrap_or_default());
+                content.push('\n');
+            }
+            self.skip_to_line(next_line);
+        }
+
+        CodeBlock::new(content, String::new()).into()
+    }
+
+    /// `parse_nested` parses `source` (eg. the content of a list item), whose first line is on
+    /// line `line` of the source being parsed, on its own and returns its blocks.
+    fn parse_nested(&mut self, source: &str, line: usize) -> Vec<Block> {
+        let mut parser = Parser::new(Lexer::from(source));
+        parser.set_options(self.options);
+        // every line of the nested content is the end of one of the lines here (after a list
+        // marker, the `>` of a quote, some indentation, etc.), so the spans of its nodes can be
+        // positions in the document too
+        parser.line_offsets = parser
+            .lexer
+            .src
+            .split('\n')
+            .enumerate()
+            .map(|(index, text)| self.line_end_offset(line + index, text))
+            .collect();
+        // the nested content is part of this document (eg. its headings can't reuse the ids of
+        // the headings here and its footnotes are numbered along with the ones here)
+        parser.state = std::mem::take(&mut self.state);
+
+        let blocks = parser.parse_blocks();
+        self.state = parser.state;
+        blocks
+    }
+
+    /// `line_offset` returns the offset in the document of the start of `line` of the source
+    /// being parsed.
+    fn line_offset(&self, line: usize) -> usize {
+        self.line_offsets
+            .get(line.saturating_sub(1))
+            .or(self.line_offsets.last())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// `line_end_offset` returns the offset in the document of `text`, the end of `line` of the
+    /// source being parsed. a line past the end of the source is at the end of the document.
+    fn line_end_offset(&self, line: usize, text: &str) -> usize {
+        match self.source_line(line) {
+            Some(source_line) if source_line.ends_with(text) => {
+                self.line_offset(line) + source_line.len() - text.len()
+            }
+            Some(_) => self.line_offset(line),
+            None => {
+                let last_line = self.line_offsets.len();
+                self.line_offset(last_line) + self.source_line(last_line).unwrap_or_default().len()
+            }
+        }
+    }
+
+    /// `offset` returns the offset in the document of the character at column `col` of `line`
+    /// of the source being parsed. column 0 is the new line character ending the line before,
+    /// which is where new line tokens are recorded.
+    fn offset(&self, line: usize, col: usize) -> usize {
+        if col == 0 && line > 1 {
+            let text = self.source_line(line - 1).unwrap_or_default();
+            return self.line_offset(line - 1) + text.len();
+        }
+
+        let text = self.source_line(line).unwrap_or_default();
+        let index = text
+            .char_indices()
+            .nth(col.saturating_sub(1))
+            .map_or(text.len(), |(index, _)| index);
+        self.line_offset(line) + index
+    }
+
+    /// `token_span` returns the span of `token` in the document.
+    fn token_span(&self, token: &Token) -> Span {
+        let start = self.offset(token.start_line, token.start_col);
+        self.state.source.span(start, start + token.literal.len())
+    }
+
+    /// `span_from` returns the span of the node that starts with `start` and ends with
+    /// curr_token, or with the token before it if curr_token is the new line (or EOF) after the
+    /// node.
+    fn span_from(&self, start: &Token) -> Span {
+        let curr_token = self.curr_token.clone().unwrap();
+        let after_node = (curr_token.token_type == TokenType::NewLine
+            || curr_token.token_type == TokenType::EOF)
+            && (curr_token.start_line, curr_token.start_col) != (start.start_line, start.start_col);
+
+        let last = match &self.prev_token {
+            Some(prev_token) if after_node => prev_token,
+            _ => &curr_token,
+        };
+        self.token_span(start).to(self.token_span(last))
+    }
+
+    /// `trim_line_start` removes the white space at the start of curr_token, the first token of
+    /// a line that goes on with the paragraph (or term) before it.
+    fn trim_line_start(&mut self) {
+        if let Some(token) = self.curr_token.as_mut()
+            && token.token_type == TokenType::Text
+        {
+            let literal = token.literal.trim_start().to_string();
+            token.start_col += token.literal.chars().count() - literal.chars().count();
+            token.literal = literal;
+        }
+    }
+
+    /// `source_line` returns the (1-indexed) line `line` of the source being parsed.
+    fn source_line(&self, line: usize) -> Option<&str> {
+        if line == 0 {
+            return None;
+        }
+        self.lexer.src.split('\n').nth(line - 1)
+    }
+
+    /// `source_from` returns the rest of the source line of `token`, starting at the token.
+    fn source_from(&self, token: &Token) -> Option<&str> {
+        let line = self.source_line(token.start_line)?;
+        let (start, _) = line.char_indices().nth(token.start_col - 1)?;
+        Some(&line[start..])
+    }
+
+    fn is_blank_line(&self, line: usize) -> bool {
+        self.source_line(line)
+            .is_none_or(|line| line.trim().is_empty())
+    }
+
+    /// `next_non_blank_line` returns the first line from `line` onwards that isn't blank.
+    fn next_non_blank_line(&self, mut line: usize) -> Option<usize> {
+        while let Some(text) = self.source_line(line) {
+            if !text.trim().is_empty() {
+                return Some(line);
+            }
+            line += 1;
+        }
+        None
+    }
+
+    /// `skip_to_end_of_line` moves curr_token to the new line character at the end of `line`
+    /// (or to EOF).
+    fn skip_to_end_of_line(&mut self, line: usize) {
+        while let Some(token) = self.curr_token.clone() {
+            // a new line token is recorded on the line that it starts
+            if token.token_type == TokenType::EOF
+                || (token.token_type == TokenType::NewLine && token.start_line > line)
+            {
+                break;
+            }
+            self.advance_token();
+        }
+    }
+
+    /// `skip_to_col` moves curr_token forward to the token at column `col` of `line`, or to the
+    /// last token before it.
+    fn skip_to_col(&mut self, line: usize, col: usize) {
+        while let Some(token) = self.peek_token.clone() {
+            if token.token_type == TokenType::EOF
+                || token.start_line != line
+                || token.start_col > col
+            {
+                break;
+            }
+            self.advance_token();
+        }
+    }
+
+    /// `skip_to_line` moves curr_token to the first token of `line` (or to EOF).
+    fn skip_to_line(&mut self, line: usize) {
+        while let Some(token) = self.curr_token.clone() {
+            if token.token_type == TokenType::EOF
+                || (token.token_type != TokenType::NewLine && token.start_line >= line)
+            {
+                break;
+            }
+            self.advance_token();
         }
     }
 
@@ -501,3 +1708,30 @@ impl Parser {
     //    )
     //}
 }
+
+/// `TaskCounter` counts the task list items of a document.
+#[derive(Default)]
+struct TaskCounter {
+    progress: TaskProgress,
+}
+
+impl Visitor for TaskCounter {
+    fn visit_list_item(&mut self, item: &ListItem) {
+        if let Some(checked) = item.checked {
+            self.progress.add_task(checked);
+        }
+        visit::walk_list_item(self, item);
+    }
+}
+
+/// `unwrap_plain_paragraph` returns the content of `block` without its <p> tags if it is a
+/// paragraph without attributes, which isn't wrapped in <p> tags in a tight list. a paragraph
+/// with attributes keeps its tags to hold them.
+fn unwrap_plain_paragraph(block: Block) -> Block {
+    match block {
+        Block::Paragraph(paragraph) if paragraph.attributes.is_empty() => {
+            Block::Plain(paragraph.into_inner())
+        }
+        block => block,
+    }
+}
@@ -0,0 +1,157 @@
+use crate::marc::entity;
+
+/// `LinkReference` is where a link or image points to: the destination and title given inline
+/// (`[text](destination "title")`) or by a `[label]: destination "title"` definition for
+/// reference-style links (`[text][label]`, `[text][]` or `[text]`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkReference {
+    pub destination: String,
+    pub title: Option<String>,
+}
+
+/// `normalize_label` returns the form of a reference label used to match references with
+/// definitions: labels are case-insensitive and any run of white space is the same as a single
+/// space.
+pub fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// `parse_definition` parses a link reference definition (`[label]: destination "title"`) that
+/// takes up the whole of `line`. the label is returned normalized.
+pub fn parse_definition(line: &str) -> Option<(String, LinkReference)> {
+    let rest = line.trim_start().strip_prefix('[')?;
+    let label_end = rest.find(']')?;
+    let label = &rest[..label_end];
+    // `[^label]:` defines a footnote
+    if label.trim().is_empty() || label.starts_with('^') || label.contains('[') {
+        return None;
+    }
+
+    let rest = rest[label_end + 1..].strip_prefix(':')?.trim_start();
+    let (destination, len) = parse_destination(rest)?;
+    if len == 0 {
+        return None;
+    }
+
+    let rest = rest[len..].trim();
+    let title = if rest.is_empty() {
+        None
+    } else {
+        let (title, len) = parse_title(rest)?;
+        if len != rest.len() {
+            return None;
+        }
+        Some(title)
+    };
+
+    Some((normalize_label(label), LinkReference { destination, title }))
+}
+
+/// `parse_inline_target` parses the `(destination "title")` of an inline link at the start of
+/// `text`. it returns the destination and title with the (byte) length of the target.
+pub fn parse_inline_target(text: &str) -> Option<(LinkReference, usize)> {
+    let mut position = 1 + whitespace_len(text.strip_prefix('(')?);
+
+    let (destination, len) = parse_destination(&text[position..])?;
+    position += len;
+
+    // the title has to be separated from the destination by white space
+    let mut title = None;
+    let separator = whitespace_len(&text[position..]);
+    if (separator > 0 || len == 0)
+        && let Some((parsed_title, len)) = parse_title(&text[position + separator..])
+    {
+        title = Some(parsed_title);
+        position += separator + len;
+    }
+
+    position += whitespace_len(&text[position..]);
+    if !text[position..].starts_with(')') {
+        return None;
+    }
+
+    Some((LinkReference { destination, title }, position + 1))
+}
+
+/// `parse_destination` parses a link destination at the start of `text` and returns it (with its
+/// backslash escapes and entity references replaced) with the (byte) length that was read.
+///
+/// a destination wrapped in `<>` can contain spaces. any other destination ends at white space
+/// or at a `)` that doesn't close a `(` in it, so urls like `https://en.wikipedia.org/wiki/Rust_(programming_language)`
+/// can be used without escaping their parentheses.
+pub fn parse_destination(text: &str) -> Option<(String, usize)> {
+    if let Some(rest) = text.strip_prefix('<') {
+        let end = find_unescaped(rest, |ch| ['>', '<', '\n'].contains(&ch))?;
+        if !rest[end..].starts_with('>') {
+            return None;
+        }
+        return Some((entity::unescape(&rest[..end]), end + 2));
+    }
+
+    let mut depth = 0;
+    let mut end = text.len();
+    let mut escaped = false;
+    for (index, ch) in text.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match ch {
+            '\\' => escaped = true,
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                end = index;
+                break;
+            }
+            ')' => depth -= 1,
+            ch if ch.is_whitespace() => {
+                end = index;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 {
+        return None;
+    }
+    Some((entity::unescape(&text[..end]), end))
+}
+
+/// `parse_title` parses a link title wrapped in `"`, `'` or `()` at the start of `text` and
+/// returns it with the (byte) length that was read.
+pub fn parse_title(text: &str) -> Option<(String, usize)> {
+    let closing = match text.chars().next()? {
+        '"' => '"',
+        '\'' => '\'',
+        '(' => ')',
+        _ => return None,
+    };
+
+    let end = find_unescaped(&text[1..], |ch| ch == closing)? + 1;
+    Some((entity::unescape(&text[1..end]), end + 1))
+}
+
+/// `find_unescaped` returns the (byte) index of the first char in `text` that matches `pattern`
+/// and isn't escaped with a backslash.
+fn find_unescaped(text: &str, pattern: impl Fn(char) -> bool) -> Option<usize> {
+    let mut escaped = false;
+    for (index, ch) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if pattern(ch) {
+            return Some(index);
+        }
+    }
+    None
+}
+
+fn whitespace_len(text: &str) -> usize {
+    text.len() - text.trim_start().len()
+}
@@ -0,0 +1,672 @@
+//! `html` writes the nodes of a document out as html. its functions are the defaults of the
+//! methods of `Renderer`: they write the nodes inside a node out with `renderer`, so a renderer
+//! that overrides some methods and keeps the others can also call them (eg. to add to the html
+//! of a node).
+
+use super::Renderer;
+use crate::marc::ast::{
+    Program,
+    attributes::Attributes,
+    block_quote::{Alert, BlockQuote},
+    code::{CodeBlock, InlineCode},
+    container::Container,
+    definition_list::{Definition, DefinitionItem, DefinitionList},
+    footnote::{Footnote, FootnoteReference, FootnoteSection},
+    heading::Heading,
+    image::{Figure, Image},
+    link::Link,
+    list::{ListItem, OrderedList, TaskProgress, UnorderedList},
+    math::{InlineMath, MathBlock},
+    text::{BoldText, EscapedText, HardBreak, ItalicizedText, ParagraphText, SoftBreak, Text},
+    thematic_break::ThematicBreak,
+    toc::TableOfContents,
+};
+use crate::marc::image_size;
+use crate::marc::math;
+use crate::marc::options::{MathOutput, RenderOptions};
+use crate::span::Span;
+use crate::utils;
+
+/// the attributes an image gets unless its attribute block sets them: images are loaded when
+/// they are about to be seen and decoded without holding up the rest of the page.
+const DEFAULT_IMAGE_ATTRIBUTES: &[(&str, &str)] = &[("loading", "lazy"), ("decoding", "async")];
+
+/// `source_position` returns the `data-sourcepos` attribute (eg. ` data-sourcepos="1:1-2:10"`)
+/// of the block element written out for a node at `span`, if `RenderOptions::source_positions`
+/// is set. it is empty for the nodes that aren't in the source.
+pub fn source_position(span: Span, options: &RenderOptions) -> String {
+    if !options.source_positions || span.is_empty() {
+        return String::new();
+    }
+    format!(" data-sourcepos=\"{}\"", span)
+}
+
+pub fn program<R: Renderer + ?Sized>(renderer: &mut R, program: &Program) -> String {
+    let inside = renderer.render_blocks(&program.blocks);
+    format!(
+        "
+            <!DOCTYPE html>
+            <html>
+            <head>
+                <meta charset=\"UTF-8\">
+                <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">
+                <link href = \"./styles.css\" rel = \"stylesheet\"></link>
+            </head>
+            <body>{}</body>
+            </html>",
+        inside
+    )
+}
+
+pub fn paragraph<R: Renderer + ?Sized>(renderer: &mut R, paragraph: &ParagraphText) -> String {
+    let content = renderer.render_inlines(&paragraph.children);
+    format!(
+        "<p{}{}>{}</p>",
+        paragraph.attributes.html(&[]),
+        source_position(paragraph.span, renderer.options()),
+        content
+    )
+}
+
+pub fn heading<R: Renderer + ?Sized>(renderer: &mut R, heading: &Heading) -> String {
+    let content = renderer.render_inlines(&heading.children);
+    let options = renderer.options();
+    let Some(id) = &heading.id else {
+        return format!(
+            "<h{}{}{}>{}</h{}>",
+            heading.level,
+            heading.attributes.html_without_id(&[]),
+            source_position(heading.span, options),
+            content,
+            heading.level
+        );
+    };
+
+    let id = utils::escape_html(id);
+    let anchor = match &options.heading_anchor {
+        Some(symbol) => format!(
+            "<a class=\"anchor\" href=\"#{}\" aria-hidden=\"true\">{}</a>",
+            id, symbol
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        "<h{} id=\"{}\"{}{}>{}{}</h{}>",
+        heading.level,
+        id,
+        heading.attributes.html_without_id(&[]),
+        source_position(heading.span, options),
+        content,
+        anchor,
+        heading.level
+    )
+}
+
+pub fn thematic_break<R: Renderer + ?Sized>(
+    renderer: &mut R,
+    thematic_break: &ThematicBreak,
+) -> String {
+    format!(
+        "<hr{}{} />",
+        thematic_break.attributes.html(&[]),
+        source_position(thematic_break.span, renderer.options())
+    )
+}
+
+pub fn code_block<R: Renderer + ?Sized>(renderer: &mut R, code_block: &CodeBlock) -> String {
+    let options = renderer.options();
+    // the attributes of a code block are set on its `<pre>`
+    let code = if code_block.language.is_empty() {
+        format!(
+            "<pre{}{}><code>{}</code></pre>",
+            code_block.attributes.html(&[]),
+            source_position(code_block.span, options),
+            code_content(code_block, options)
+        )
+    } else {
+        format!(
+            "<pre{}{}><code class=\"language-{}\">{}</code></pre>",
+            code_block.attributes.html(&[]),
+            source_position(code_block.span, options),
+            utils::escape_html(&code_block.language),
+            code_content(code_block, options)
+        )
+    };
+
+    match &code_block.title {
+        Some(title) => format!(
+            "<figure class=\"code-block\"><figcaption>{}</figcaption>{}</figure>",
+            utils::escape_html(title),
+            code
+        ),
+        None => code,
+    }
+}
+
+/// `code_content` returns the html of the code of a code block. the code is highlighted if
+/// there's a grammar for its language.
+fn code_content(code_block: &CodeBlock, options: &RenderOptions) -> String {
+    let lines = match options
+        .highlighter
+        .highlight_lines(&code_block.language, &code_block.code_content)
+    {
+        Some(lines) => lines,
+        None => code_block
+            .code_content
+            .split('\n')
+            .map(utils::escape_html)
+            .collect(),
+    };
+
+    if code_block.highlighted_lines.is_empty() {
+        return lines.join("\n");
+    }
+
+    // every line is wrapped in its own span when there are lines to highlight so they can
+    // be styled individually. the new line characters stay outside the spans.
+    let total_lines = lines.len();
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            if index + 1 == total_lines && line.is_empty() {
+                // what comes after the last new line isn't a line of its own
+                return line;
+            }
+
+            let class = if code_block.is_line_highlighted(index + 1) {
+                "line highlighted"
+            } else {
+                "line"
+            };
+            format!("<span class=\"{}\">{}</span>", class, line)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn math_block<R: Renderer + ?Sized>(renderer: &mut R, math_block: &MathBlock) -> String {
+    let options = renderer.options();
+    match options.math {
+        MathOutput::MathML => with_attributes(
+            math::to_mathml(&math_block.tex, true),
+            &math_block.attributes,
+        ),
+        MathOutput::Delimiters => format!(
+            "<div{}{}>\\[{}\\]</div>",
+            math_block.attributes.html(&["math", "display"]),
+            source_position(math_block.span, options),
+            utils::escape_html(math_block.tex.trim())
+        ),
+    }
+}
+
+/// `with_attributes` adds `attributes` to the `<math>` element of `mathml`.
+fn with_attributes(mathml: String, attributes: &Attributes) -> String {
+    match mathml.strip_prefix("<math") {
+        Some(rest) => format!("<math{}{}", attributes.html(&[]), rest),
+        None => mathml,
+    }
+}
+
+pub fn block_quote<R: Renderer + ?Sized>(renderer: &mut R, block_quote: &BlockQuote) -> String {
+    let content = renderer.render_blocks(&block_quote.children);
+    format!(
+        "<blockquote{}{}>{}</blockquote>",
+        block_quote.attributes.html(&[]),
+        source_position(block_quote.span, renderer.options()),
+        content
+    )
+}
+
+pub fn alert<R: Renderer + ?Sized>(renderer: &mut R, alert: &Alert) -> String {
+    let content = renderer.render_blocks(&alert.children);
+    let title = alert.kind.title();
+    let kind_class = format!("markdown-alert-{}", title.to_lowercase());
+    format!(
+        "<div{}{}><p class=\"markdown-alert-title\">{}</p>{}</div>",
+        alert.attributes.html(&["markdown-alert", &kind_class]),
+        source_position(alert.span, renderer.options()),
+        title,
+        content
+    )
+}
+
+pub fn unordered_list<R: Renderer + ?Sized>(renderer: &mut R, list: &UnorderedList) -> String {
+    let items: String = list
+        .items
+        .iter()
+        .map(|item| renderer.render_list_item(item))
+        .collect();
+    format!(
+        "<ul{}{}>{}</ul>",
+        list.attributes.html(&[]),
+        source_position(list.span, renderer.options()),
+        items
+    )
+}
+
+pub fn ordered_list<R: Renderer + ?Sized>(renderer: &mut R, list: &OrderedList) -> String {
+    let items: String = list
+        .items
+        .iter()
+        .map(|item| renderer.render_list_item(item))
+        .collect();
+    // a list numbered from 1 doesn't need a start
+    let start = match list.start {
+        1 => String::new(),
+        start => format!(" start=\"{}\"", start),
+    };
+    format!(
+        "<ol{}{}{}>{}</ol>",
+        start,
+        list.attributes.html(&[]),
+        source_position(list.span, renderer.options()),
+        items
+    )
+}
+
+pub fn list_item<R: Renderer + ?Sized>(renderer: &mut R, item: &ListItem) -> String {
+    let content = renderer.render_blocks(&item.children);
+    let options = renderer.options();
+    match item.checked {
+        // the checkbox only shows the state of the task, it can't be changed
+        Some(checked) => format!(
+            "<li{}{}><input type=\"checkbox\" disabled{} /> {}</li>",
+            item.attributes.html(&["task-list-item"]),
+            source_position(item.span, options),
+            if checked { " checked" } else { "" },
+            content
+        ),
+        None => format!(
+            "<li{}{}>{}</li>",
+            item.attributes.html(&[]),
+            source_position(item.span, options),
+            content
+        ),
+    }
+}
+
+pub fn definition_list<R: Renderer + ?Sized>(renderer: &mut R, list: &DefinitionList) -> String {
+    let items: String = list
+        .items
+        .iter()
+        .map(|item| renderer.render_definition_item(item))
+        .collect();
+    format!(
+        "<dl{}{}>{}</dl>",
+        list.attributes.html(&[]),
+        source_position(list.span, renderer.options()),
+        items
+    )
+}
+
+pub fn definition_item<R: Renderer + ?Sized>(renderer: &mut R, item: &DefinitionItem) -> String {
+    let term = renderer.render_inlines(&item.term);
+    let definitions: String = item
+        .definitions
+        .iter()
+        .map(|definition| renderer.render_definition(definition))
+        .collect();
+    format!("<dt>{}</dt>{}", term, definitions)
+}
+
+pub fn definition<R: Renderer + ?Sized>(renderer: &mut R, definition: &Definition) -> String {
+    let content = renderer.render_blocks(&definition.children);
+    format!(
+        "<dd{}{}>{}</dd>",
+        definition.attributes.html(&[]),
+        source_position(definition.span, renderer.options()),
+        content
+    )
+}
+
+/// `container` writes a container out with the renderer registered for its name in
+/// `RenderOptions::containers`, or as a `<div>` with its name as its class.
+pub fn container<R: Renderer + ?Sized>(renderer: &mut R, container: &Container) -> String {
+    let content = renderer.render_blocks(&container.children);
+    let options = renderer.options();
+    if let Some(container_renderer) = options.containers.renderer(&container.name) {
+        return container_renderer.render(container, &content, options);
+    }
+
+    let classes: &[&str] = if container.name.is_empty() {
+        &[]
+    } else {
+        &[&container.name]
+    };
+    format!(
+        "<div{}{}>{}</div>",
+        container.attributes.html(classes),
+        source_position(container.span, options),
+        content
+    )
+}
+
+pub fn figure<R: Renderer + ?Sized>(renderer: &mut R, figure: &Figure) -> String {
+    let options = renderer.options();
+    let caption = figure.image.title.as_deref().unwrap_or_default();
+    format!(
+        "<figure{}{}>{}<figcaption>{}</figcaption></figure>",
+        figure.attributes.html(&[]),
+        source_position(figure.span, options),
+        img_tag(&figure.image, None, options),
+        utils::escape_html(caption)
+    )
+}
+
+pub fn table_of_contents<R: Renderer + ?Sized>(renderer: &mut R, toc: &TableOfContents) -> String {
+    let options = renderer.options();
+    let entries = toc.entries.iter().filter(|entry| {
+        entry.level >= options.toc_min_level && entry.level <= options.toc_max_level
+    });
+
+    // `levels` holds the level of every list that is still open, the innermost one last
+    let mut levels: Vec<i8> = vec![];
+    let mut html = String::new();
+
+    for entry in entries {
+        while levels.len() > 1 && entry.level < *levels.last().unwrap() {
+            html.push_str("</li></ol>");
+            levels.pop();
+        }
+
+        match levels.last() {
+            Some(level) if entry.level <= *level => html.push_str("</li>"),
+            // the first entry, or one that is nested in the entry before it
+            _ => {
+                html.push_str("<ol>");
+                levels.push(entry.level);
+            }
+        }
+
+        html.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a>",
+            utils::escape_html(&entry.id),
+            utils::escape_html(entry.text.trim())
+        ));
+    }
+
+    if levels.is_empty() {
+        return String::new();
+    }
+
+    html.push_str(&"</li></ol>".repeat(levels.len()));
+    format!(
+        "<nav class=\"toc\"{}>{}</nav>",
+        source_position(toc.span, options),
+        html
+    )
+}
+
+pub fn footnote_section<R: Renderer + ?Sized>(
+    renderer: &mut R,
+    section: &FootnoteSection,
+) -> String {
+    let footnotes: String = section
+        .footnotes
+        .iter()
+        .map(|footnote| renderer.render_footnote(footnote))
+        .collect();
+    format!(
+        "<section class=\"footnotes\"><ol>{}</ol></section>",
+        footnotes
+    )
+}
+
+pub fn footnote<R: Renderer + ?Sized>(renderer: &mut R, footnote: &Footnote) -> String {
+    let back_links: Vec<String> = (1..=footnote.references)
+        .map(|reference| {
+            let label = if reference == 1 {
+                "↩".to_string()
+            } else {
+                format!("↩<sup>{}</sup>", reference)
+            };
+            format!(
+                "<a href=\"#{}\" class=\"footnote-backref\">{}</a>",
+                reference_id(footnote.number, reference),
+                label
+            )
+        })
+        .collect();
+    let back_links = back_links.join(" ");
+
+    // the back-links go at the end of the last paragraph, so they aren't on a line of their
+    // own
+    let content = renderer.render_blocks(&footnote.children);
+    let content = match content.strip_suffix("</p>") {
+        Some(content) => format!("{} {}</p>", content, back_links),
+        None => format!("{} {}", content, back_links),
+    };
+
+    format!("<li id=\"fn-{}\">{}</li>", footnote.number, content)
+}
+
+/// `reference_id` is the id of the `reference`th reference to footnote `number`
+fn reference_id(number: usize, reference: usize) -> String {
+    if reference == 1 {
+        format!("fnref-{}", number)
+    } else {
+        format!("fnref-{}-{}", number, reference)
+    }
+}
+
+pub fn text<R: Renderer + ?Sized>(
+    renderer: &mut R,
+    text: &Text,
+    before: Option<char>,
+    after: Option<char>,
+) -> String {
+    renderer
+        .options()
+        .text_transforms
+        .iter()
+        .fold(text.value.to_owned(), |value, transform| {
+            transform.transform_between(&value, before, after)
+        })
+}
+
+pub fn escaped_text<R: Renderer + ?Sized>(_renderer: &mut R, text: &EscapedText) -> String {
+    utils::escape_html(&text.value)
+}
+
+pub fn bold<R: Renderer + ?Sized>(renderer: &mut R, bold: &BoldText) -> String {
+    format!(
+        "<strong{}>{}</strong>",
+        bold.attributes.html(&[]),
+        renderer.render_inlines(&bold.children)
+    )
+}
+
+pub fn italics<R: Renderer + ?Sized>(renderer: &mut R, italics: &ItalicizedText) -> String {
+    format!(
+        "<em{}>{}</em>",
+        italics.attributes.html(&[]),
+        renderer.render_inlines(&italics.children)
+    )
+}
+
+pub fn inline_code<R: Renderer + ?Sized>(_renderer: &mut R, code: &InlineCode) -> String {
+    format!(
+        "<code{}>{}</code>",
+        code.attributes.html(&[]),
+        utils::escape_html(&code.code_content)
+    )
+}
+
+pub fn link<R: Renderer + ?Sized>(renderer: &mut R, link: &Link) -> String {
+    let content = renderer.render_inlines(&link.children);
+    match &link.title {
+        Some(title) => format!(
+            "<a href = \"{}\" title = \"{}\"{}>{}</a>",
+            utils::escape_html(&link.href),
+            utils::escape_html(title),
+            link.attributes.html(&[]),
+            content
+        ),
+        None => format!(
+            "<a href = \"{}\"{}>{}</a>",
+            utils::escape_html(&link.href),
+            link.attributes.html(&[]),
+            content
+        ),
+    }
+}
+
+pub fn image<R: Renderer + ?Sized>(renderer: &mut R, image: &Image) -> String {
+    img_tag(image, image.title.as_deref(), renderer.options())
+}
+
+/// `img_tag` returns the `<img>` element of `image`, with the title given as the title
+/// attribute (a figure shows it as its caption instead).
+fn img_tag(image: &Image, title: Option<&str>, options: &RenderOptions) -> String {
+    let mut html = format!(
+        "<img src = \"{}\" alt = \"{}\"",
+        utils::escape_html(&image.src),
+        utils::escape_html(&image.alt)
+    );
+    let mut add = |name: &str, value: &str| {
+        html.push_str(&format!(" {} = \"{}\"", name, utils::escape_html(value)));
+    };
+
+    if let Some(title) = title {
+        add("title", title);
+    }
+    if let Some(id) = image.attributes.id() {
+        add("id", id);
+    }
+    if !image.attributes.classes().is_empty() {
+        add("class", &image.attributes.classes().join(" "));
+    }
+
+    let (width, height) = image_size(image, options);
+    if let Some(width) = width {
+        add("width", &width);
+    }
+    if let Some(height) = height {
+        add("height", &height);
+    }
+    for (name, value) in image.attributes.pairs() {
+        if name != "width" && name != "height" {
+            add(name, value);
+        }
+    }
+    for (name, value) in DEFAULT_IMAGE_ATTRIBUTES {
+        if image.attributes.get(name).is_none() {
+            add(name, value);
+        }
+    }
+
+    html + " />"
+}
+
+/// `image_size` returns the width and height written on `image`. the ones that aren't set by
+/// its attributes are read from the image file itself (when it is a local file under
+/// `RenderOptions::image_root`), so the browser can lay the page out before the image is
+/// loaded. a width or height that is set on its own keeps the image proportions.
+fn image_size(image: &Image, options: &RenderOptions) -> (Option<String>, Option<String>) {
+    let width = image.attributes.get("width").map(str::to_string);
+    let height = image.attributes.get("height").map(str::to_string);
+    if width.is_some() && height.is_some() {
+        return (width, height);
+    }
+
+    let Some((intrinsic_width, intrinsic_height)) = intrinsic_size(image, options) else {
+        return (width, height);
+    };
+    if intrinsic_width == 0 || intrinsic_height == 0 {
+        return (width, height);
+    }
+
+    let scale = |value: &Option<String>, from: u32, to: u32| {
+        value
+            .as_deref()
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(|value| (value * to as u64 / from as u64).to_string())
+    };
+    match (&width, &height) {
+        (None, None) => (
+            Some(intrinsic_width.to_string()),
+            Some(intrinsic_height.to_string()),
+        ),
+        (Some(_), None) => {
+            let height = scale(&width, intrinsic_width, intrinsic_height);
+            (width, height)
+        }
+        (None, Some(_)) => {
+            let width = scale(&height, intrinsic_height, intrinsic_width);
+            (width, height)
+        }
+        (Some(_), Some(_)) => (width, height),
+    }
+}
+
+fn intrinsic_size(image: &Image, options: &RenderOptions) -> Option<(u32, u32)> {
+    let root = options.image_root.as_ref()?;
+    // only local files are read, never urls (`https://...`, `//cdn...`, `data:...`)
+    if image.src.contains(':') || image.src.starts_with("//") {
+        return None;
+    }
+    let path = image.src.split(['?', '#']).next()?;
+    // the files outside of the image root (`../secret.png`, or a link out of it) aren't read
+    let path = root
+        .join(path.trim_start_matches('/'))
+        .canonicalize()
+        .ok()?;
+    if !path.starts_with(root.canonicalize().ok()?) {
+        return None;
+    }
+    image_size::read_image_size(&path)
+}
+
+pub fn inline_math<R: Renderer + ?Sized>(renderer: &mut R, math: &InlineMath) -> String {
+    match renderer.options().math {
+        MathOutput::MathML => {
+            with_attributes(math::to_mathml(&math.tex, math.display), &math.attributes)
+        }
+        MathOutput::Delimiters if math.display => format!(
+            "<span{}>\\[{}\\]</span>",
+            math.attributes.html(&["math", "display"]),
+            utils::escape_html(&math.tex)
+        ),
+        MathOutput::Delimiters => format!(
+            "<span{}>\\({}\\)</span>",
+            math.attributes.html(&["math", "inline"]),
+            utils::escape_html(&math.tex)
+        ),
+    }
+}
+
+pub fn footnote_reference<R: Renderer + ?Sized>(
+    _renderer: &mut R,
+    reference: &FootnoteReference,
+) -> String {
+    format!(
+        "<sup class=\"footnote-ref\"><a href=\"#fn-{}\" id=\"{}\">{}</a></sup>",
+        reference.number,
+        reference_id(reference.number, reference.reference),
+        reference.number
+    )
+}
+
+pub fn task_progress<R: Renderer + ?Sized>(_renderer: &mut R, progress: &TaskProgress) -> String {
+    format!(
+        "<span class=\"task-progress\">{}/{}</span>",
+        progress.done, progress.total
+    )
+}
+
+pub fn soft_break<R: Renderer + ?Sized>(renderer: &mut R, _soft_break: &SoftBreak) -> String {
+    renderer.options().soft_break.clone()
+}
+
+pub fn hard_break<R: Renderer + ?Sized>(_renderer: &mut R, _hard_break: &HardBreak) -> String {
+    "<br />".to_string()
+}
+
+/// an attribute block that doesn't belong to any element is written out as the text it was
+/// parsed from
+pub fn attributes<R: Renderer + ?Sized>(_renderer: &mut R, attributes: &Attributes) -> String {
+    attributes.literal.clone()
+}
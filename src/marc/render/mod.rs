@@ -0,0 +1,310 @@
+//! `render` writes a marc document out. a [`Renderer`] has a method for every kind of node, which
+//! the nodes are walked with (see `Node::render`); the output format is up to the renderer.
+//!
+//! every method of `Renderer` writes its node out as html by default (with the function of the
+//! same name in [`html`]), so [`HtmlRenderer`] is a renderer that doesn't override any of them.
+//! a renderer can override only the nodes it writes out differently, eg. the headings:
+//!
+//! ```
+//! use md_to_html::marc::ast::{Node, heading::Heading};
+//! use md_to_html::marc::render::Renderer;
+//! use md_to_html::marc::{lexer::Lexer, options::RenderOptions, parser::Parser};
+//!
+//! struct PlainHeadings {
+//!     options: RenderOptions,
+//! }
+//!
+//! impl Renderer for PlainHeadings {
+//!     fn options(&self) -> &RenderOptions {
+//!         &self.options
+//!     }
+//!
+//!     fn render_heading(&mut self, heading: &Heading) -> String {
+//!         let content = self.render_inlines(&heading.children);
+//!         format!("<div class=\"h{}\">{}</div>", heading.level, content.trim())
+//!     }
+//! }
+//!
+//! let program = Parser::new(Lexer::from("> # Quoted *title*")).parse_program();
+//! let mut renderer = PlainHeadings { options: RenderOptions::default() };
+//! assert_eq!(
+//!     renderer.render_blocks(program.get_blocks()),
+//!     "<blockquote><div class=\"h1\">Quoted <em>title</em></div></blockquote>"
+//! );
+//! ```
+//!
+//! the nodes inside a node are written out with the same renderer, so the headings in the
+//! quote above are written out by `PlainHeadings` too. a backend for another format overrides
+//! every method, like [`text::TextRenderer`] does.
+
+use crate::marc::ast::{
+    Block, Inline, Node, Program,
+    attributes::Attributes,
+    block_quote::{Alert, BlockQuote},
+    code::{CodeBlock, InlineCode},
+    container::Container,
+    definition_list::{Definition, DefinitionItem, DefinitionList},
+    footnote::{Footnote, FootnoteReference, FootnoteSection},
+    heading::Heading,
+    image::{Figure, Image},
+    link::Link,
+    list::{ListItem, OrderedList, TaskProgress, UnorderedList},
+    math::{InlineMath, MathBlock},
+    text::{BoldText, EscapedText, HardBreak, ItalicizedText, ParagraphText, SoftBreak, Text},
+    thematic_break::ThematicBreak,
+    toc::TableOfContents,
+};
+use crate::marc::options::RenderOptions;
+use std::borrow::Borrow;
+
+pub mod html;
+pub mod text;
+
+/// `Renderer` writes the nodes of a document out. `render_block` and `render_inline` call the
+/// method of the node a `Block` or `Inline` holds.
+pub trait Renderer {
+    /// `options` returns the options the document is written out with
+    fn options(&self) -> &RenderOptions;
+
+    /// `render_program` writes out a whole document. the html of a document is a complete page,
+    /// use `render_blocks` for only the html of its content.
+    fn render_program(&mut self, program: &Program) -> String {
+        html::program(self, program)
+    }
+
+    fn render_blocks(&mut self, blocks: &[Block]) -> String {
+        blocks
+            .iter()
+            .map(|block| self.render_block(block))
+            .collect()
+    }
+
+    /// `render_inlines` writes out an inline run (eg. the content of a paragraph). its texts are
+    /// written out with the chars around them in it (see [`text_neighbours`]).
+    fn render_inlines(&mut self, children: &[Inline]) -> String {
+        children
+            .iter()
+            .zip(text_neighbours(children))
+            .map(|(child, (before, after))| match child {
+                Inline::Text(text) => self.render_text(text, before, after),
+                child => self.render_inline(child),
+            })
+            .collect()
+    }
+
+    fn render_block(&mut self, block: &Block) -> String {
+        match block {
+            Block::Paragraph(node) => self.render_paragraph(node),
+            Block::Heading(node) => self.render_heading(node),
+            Block::ThematicBreak(node) => self.render_thematic_break(node),
+            Block::CodeBlock(node) => self.render_code_block(node),
+            Block::MathBlock(node) => self.render_math_block(node),
+            Block::BlockQuote(node) => self.render_block_quote(node),
+            Block::Alert(node) => self.render_alert(node),
+            Block::UnorderedList(node) => self.render_unordered_list(node),
+            Block::OrderedList(node) => self.render_ordered_list(node),
+            Block::DefinitionList(node) => self.render_definition_list(node),
+            Block::Container(node) => self.render_container(node),
+            Block::Figure(node) => self.render_figure(node),
+            Block::TableOfContents(node) => self.render_table_of_contents(node),
+            Block::FootnoteSection(node) => self.render_footnote_section(node),
+            Block::Plain(children) => self.render_plain(children),
+            Block::Inline(node) => self.render_inline(node),
+        }
+    }
+
+    fn render_inline(&mut self, inline: &Inline) -> String {
+        match inline {
+            Inline::Text(node) => self.render_text(node, None, None),
+            Inline::EscapedText(node) => self.render_escaped_text(node),
+            Inline::Bold(node) => self.render_bold(node),
+            Inline::Italics(node) => self.render_italics(node),
+            Inline::Code(node) => self.render_inline_code(node),
+            Inline::Link(node) => self.render_link(node),
+            Inline::Image(node) => self.render_image(node),
+            Inline::Math(node) => self.render_inline_math(node),
+            Inline::FootnoteReference(node) => self.render_footnote_reference(node),
+            Inline::TaskProgress(node) => self.render_task_progress(node),
+            Inline::SoftBreak(node) => self.render_soft_break(node),
+            Inline::HardBreak(node) => self.render_hard_break(node),
+            Inline::Attributes(node) => self.render_attributes(node),
+            Inline::Block(node) => self.render_block(node),
+        }
+    }
+
+    fn render_paragraph(&mut self, paragraph: &ParagraphText) -> String {
+        html::paragraph(self, paragraph)
+    }
+
+    fn render_heading(&mut self, heading: &Heading) -> String {
+        html::heading(self, heading)
+    }
+
+    fn render_thematic_break(&mut self, thematic_break: &ThematicBreak) -> String {
+        html::thematic_break(self, thematic_break)
+    }
+
+    fn render_code_block(&mut self, code_block: &CodeBlock) -> String {
+        html::code_block(self, code_block)
+    }
+
+    fn render_math_block(&mut self, math_block: &MathBlock) -> String {
+        html::math_block(self, math_block)
+    }
+
+    fn render_block_quote(&mut self, block_quote: &BlockQuote) -> String {
+        html::block_quote(self, block_quote)
+    }
+
+    fn render_alert(&mut self, alert: &Alert) -> String {
+        html::alert(self, alert)
+    }
+
+    fn render_unordered_list(&mut self, list: &UnorderedList) -> String {
+        html::unordered_list(self, list)
+    }
+
+    fn render_ordered_list(&mut self, list: &OrderedList) -> String {
+        html::ordered_list(self, list)
+    }
+
+    fn render_list_item(&mut self, item: &ListItem) -> String {
+        html::list_item(self, item)
+    }
+
+    fn render_definition_list(&mut self, list: &DefinitionList) -> String {
+        html::definition_list(self, list)
+    }
+
+    fn render_definition_item(&mut self, item: &DefinitionItem) -> String {
+        html::definition_item(self, item)
+    }
+
+    fn render_definition(&mut self, definition: &Definition) -> String {
+        html::definition(self, definition)
+    }
+
+    fn render_container(&mut self, container: &Container) -> String {
+        html::container(self, container)
+    }
+
+    fn render_figure(&mut self, figure: &Figure) -> String {
+        html::figure(self, figure)
+    }
+
+    fn render_table_of_contents(&mut self, toc: &TableOfContents) -> String {
+        html::table_of_contents(self, toc)
+    }
+
+    fn render_footnote_section(&mut self, section: &FootnoteSection) -> String {
+        html::footnote_section(self, section)
+    }
+
+    fn render_footnote(&mut self, footnote: &Footnote) -> String {
+        html::footnote(self, footnote)
+    }
+
+    /// `render_plain` writes out the content of a `Block::Plain`, a paragraph without <p> tags.
+    fn render_plain(&mut self, children: &[Inline]) -> String {
+        self.render_inlines(children)
+    }
+
+    /// `render_text` writes out `text`, which comes after the char `before` and before the char
+    /// `after` in its inline run (`None` at the start or the end of it).
+    fn render_text(&mut self, text: &Text, before: Option<char>, after: Option<char>) -> String {
+        html::text(self, text, before, after)
+    }
+
+    fn render_escaped_text(&mut self, text: &EscapedText) -> String {
+        html::escaped_text(self, text)
+    }
+
+    fn render_bold(&mut self, bold: &BoldText) -> String {
+        html::bold(self, bold)
+    }
+
+    fn render_italics(&mut self, italics: &ItalicizedText) -> String {
+        html::italics(self, italics)
+    }
+
+    fn render_inline_code(&mut self, code: &InlineCode) -> String {
+        html::inline_code(self, code)
+    }
+
+    fn render_link(&mut self, link: &Link) -> String {
+        html::link(self, link)
+    }
+
+    fn render_image(&mut self, image: &Image) -> String {
+        html::image(self, image)
+    }
+
+    fn render_inline_math(&mut self, math: &InlineMath) -> String {
+        html::inline_math(self, math)
+    }
+
+    fn render_footnote_reference(&mut self, reference: &FootnoteReference) -> String {
+        html::footnote_reference(self, reference)
+    }
+
+    fn render_task_progress(&mut self, progress: &TaskProgress) -> String {
+        html::task_progress(self, progress)
+    }
+
+    fn render_soft_break(&mut self, soft_break: &SoftBreak) -> String {
+        html::soft_break(self, soft_break)
+    }
+
+    fn render_hard_break(&mut self, hard_break: &HardBreak) -> String {
+        html::hard_break(self, hard_break)
+    }
+
+    /// `render_attributes` writes out an attribute block that doesn't belong to any element
+    /// (`Inline::Attributes`).
+    fn render_attributes(&mut self, attributes: &Attributes) -> String {
+        html::attributes(self, attributes)
+    }
+}
+
+/// `text_neighbours` returns the chars right before and after each of `children` in the inline
+/// run they make up (`None` at the start or the end of it). the chars can be in the nodes next to
+/// a child: the first quote of `"*a*"` comes right before the emphasis.
+pub fn text_neighbours<I: Borrow<Inline>>(children: &[I]) -> Vec<(Option<char>, Option<char>)> {
+    let texts: Vec<String> = children
+        .iter()
+        .map(|child| child.borrow().plain_text())
+        .collect();
+
+    let mut before = None;
+    let mut neighbours: Vec<(Option<char>, Option<char>)> = texts
+        .iter()
+        .map(|text| {
+            let neighbours = (before, None);
+            before = text.chars().next_back().or(before);
+            neighbours
+        })
+        .collect();
+    let mut after = None;
+    for (neighbours, text) in neighbours.iter_mut().zip(&texts).rev() {
+        neighbours.1 = after;
+        after = text.chars().next().or(after);
+    }
+    neighbours
+}
+
+/// `HtmlRenderer` writes a document out as html. it is what `Node::evaluate` renders with.
+pub struct HtmlRenderer<'a> {
+    options: &'a RenderOptions,
+}
+
+impl<'a> HtmlRenderer<'a> {
+    pub fn new(options: &'a RenderOptions) -> Self {
+        HtmlRenderer { options }
+    }
+}
+
+impl Renderer for HtmlRenderer<'_> {
+    fn options(&self) -> &RenderOptions {
+        self.options
+    }
+}
@@ -0,0 +1,485 @@
+//! `text` writes a document out as plain text, eg. for search indexes, `<meta name="description">`
+//! or the text part of an email.
+//!
+//! the markup is dropped and the text is kept: links are their text, images their alt text, list
+//! items keep their marker (and their number in an ordered list), code blocks are written out as
+//! they are and the rows of a table are written one per line, with ` | ` between their cells.
+//! the table of contents and thematic breaks are left out.
+//!
+//! ```
+//! use md_to_html::marc::render::{Renderer, text::{self, TextRenderer}};
+//! use md_to_html::marc::{lexer::Lexer, options::RenderOptions, parser::Parser};
+//!
+//! let program = Parser::new(Lexer::from("# Notes\n\nsee **the** [guide](/guide)\n\n1. one\n2. two"))
+//!     .parse_program();
+//! let options = RenderOptions::default();
+//! let text = TextRenderer::new(&options).render_program(&program);
+//! assert_eq!(text, "Notes\n\nsee the guide\n\n1. one\n2. two");
+//! assert_eq!(text::summary(&text, 20), "Notes see the guide…");
+//! ```
+
+use super::Renderer;
+use crate::marc::ast::{
+    Block, Inline, Program,
+    attributes::Attributes,
+    block_quote::{Alert, BlockQuote},
+    code::{CodeBlock, InlineCode},
+    container::Container,
+    definition_list::{Definition, DefinitionItem, DefinitionList},
+    footnote::{Footnote, FootnoteReference, FootnoteSection},
+    heading::Heading,
+    image::{Figure, Image},
+    link::Link,
+    list::{ListItem, OrderedList, TaskProgress, UnorderedList},
+    math::{InlineMath, MathBlock},
+    text::{BoldText, EscapedText, HardBreak, ItalicizedText, ParagraphText, SoftBreak, Text},
+    thematic_break::ThematicBreak,
+    toc::TableOfContents,
+};
+use crate::marc::options::RenderOptions;
+
+/// `TextRenderer` writes a document out as plain text. the blocks are separated by blank lines
+/// (the items of a list by new lines), and the text keeps the lines of the source unless it is
+/// wrapped with `set_width`.
+pub struct TextRenderer<'a> {
+    options: &'a RenderOptions,
+    width: Option<usize>,
+    // the indentation of the content being written out (in list items and definitions), which
+    // the width of its lines doesn't include
+    indent: usize,
+    // the marker of the next list item: `- `, or its number in an ordered list
+    item_marker: String,
+    // what a soft line break is written out as: the new line of the source, or a space while the
+    // text is wrapped
+    soft_break: &'static str,
+}
+
+impl<'a> TextRenderer<'a> {
+    pub fn new(options: &'a RenderOptions) -> Self {
+        TextRenderer {
+            options,
+            width: None,
+            indent: 0,
+            item_marker: String::new(),
+            soft_break: "\n",
+        }
+    }
+
+    /// `set_width` wraps the text of the paragraphs (and headings, list items, etc.) to lines of
+    /// at most `width` characters. code blocks and tables aren't wrapped.
+    pub fn set_width(&mut self, width: usize) {
+        self.width = Some(width);
+    }
+
+    /// `fill` writes out inline content, the text of a paragraph: a table if every line of it is
+    /// a row of one, or else the text wrapped to the width of the renderer.
+    ///
+    /// the lines of the text (between its soft breaks) are written out once, and joined with new
+    /// lines for a table or text that isn't wrapped, or with spaces for text that is.
+    fn fill(&mut self, children: &[&Inline]) -> String {
+        let soft_break = self.soft_break;
+        if self.width.is_some() {
+            self.soft_break = " ";
+        }
+        // the soft breaks are left out (as `None`), the lines are split at them
+        let rendered: Vec<Option<String>> = children
+            .iter()
+            .zip(super::text_neighbours(children))
+            .map(|(child, (before, after))| match child {
+                Inline::SoftBreak(_) => None,
+                Inline::Text(text) => Some(self.render_text(text, before, after)),
+                child => Some(self.render_inline(child)),
+            })
+            .collect();
+        let lines: Vec<String> = rendered
+            .split(Option::is_none)
+            .map(|line| line.iter().flatten().map(String::as_str).collect())
+            .collect();
+        self.soft_break = soft_break;
+
+        let text = lines.join("\n");
+        if let Some(table) = table(&text) {
+            return table;
+        }
+        match self.width {
+            Some(width) => wrap(
+                lines.join(" ").trim(),
+                width.saturating_sub(self.indent).max(1),
+            ),
+            None => trim_lines(&text),
+        }
+    }
+
+    /// `list_items` writes out the items of a list, numbered from `start` in an ordered list
+    /// (and with a `-` in an unordered one, without a `start`).
+    fn list_items(&mut self, items: &[ListItem], start: Option<usize>) -> String {
+        let items: Vec<String> = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                self.item_marker = match start {
+                    Some(start) => format!("{}. ", start + index),
+                    None => "- ".to_string(),
+                };
+                self.render_list_item(item)
+            })
+            .collect();
+        items.join("\n")
+    }
+
+    /// `nested` writes out `blocks` as the content of a block that is indented by `indent`
+    /// characters, with `first` before its first line.
+    fn nested(&mut self, blocks: &[Block], first: &str, indent: usize) -> String {
+        self.indent += indent;
+        let content = self.render_blocks(blocks);
+        self.indent -= indent;
+        prefix_lines(&content, first, indent)
+    }
+}
+
+impl Renderer for TextRenderer<'_> {
+    fn options(&self) -> &RenderOptions {
+        self.options
+    }
+
+    fn render_program(&mut self, program: &Program) -> String {
+        self.render_blocks(&program.blocks)
+    }
+
+    /// the inline nodes that aren't in a paragraph (`Block::Inline`) are written out together, as
+    /// the paragraph they make up
+    fn render_blocks(&mut self, blocks: &[Block]) -> String {
+        let mut text = String::new();
+        let mut tight = false;
+        let mut index = 0;
+        while index < blocks.len() {
+            let block = match &blocks[index] {
+                Block::Inline(_) => {
+                    let run: Vec<&Inline> = blocks[index..]
+                        .iter()
+                        .map_while(|block| match block {
+                            Block::Inline(inline) => Some(inline),
+                            _ => None,
+                        })
+                        .collect();
+                    index += run.len();
+                    self.fill(&run)
+                }
+                block => {
+                    index += 1;
+                    self.render_block(block)
+                }
+            };
+            if block.is_empty() {
+                continue;
+            }
+
+            // the paragraphs of a tight list item (`Block::Plain`) aren't separated by blank
+            // lines from the blocks around them
+            let plain = matches!(blocks[index - 1], Block::Plain(_));
+            if !text.is_empty() {
+                text.push_str(if tight || plain { "\n" } else { "\n\n" });
+            }
+            text.push_str(&block);
+            tight = plain;
+        }
+        text
+    }
+
+    fn render_paragraph(&mut self, paragraph: &ParagraphText) -> String {
+        let children: Vec<&Inline> = paragraph.children.iter().collect();
+        self.fill(&children)
+    }
+
+    fn render_heading(&mut self, heading: &Heading) -> String {
+        let children: Vec<&Inline> = heading.children.iter().collect();
+        self.fill(&children)
+    }
+
+    fn render_thematic_break(&mut self, _thematic_break: &ThematicBreak) -> String {
+        String::new()
+    }
+
+    fn render_code_block(&mut self, code_block: &CodeBlock) -> String {
+        code_block.code_content.trim_end_matches('\n').to_string()
+    }
+
+    fn render_math_block(&mut self, math_block: &MathBlock) -> String {
+        math_block.tex.trim().to_string()
+    }
+
+    fn render_block_quote(&mut self, block_quote: &BlockQuote) -> String {
+        self.render_blocks(&block_quote.children)
+    }
+
+    fn render_alert(&mut self, alert: &Alert) -> String {
+        let content = self.render_blocks(&alert.children);
+        format!("{}\n{}", alert.kind.title(), content)
+    }
+
+    fn render_unordered_list(&mut self, list: &UnorderedList) -> String {
+        self.list_items(&list.items, None)
+    }
+
+    fn render_ordered_list(&mut self, list: &OrderedList) -> String {
+        self.list_items(&list.items, Some(list.start))
+    }
+
+    fn render_list_item(&mut self, item: &ListItem) -> String {
+        let mut marker = std::mem::take(&mut self.item_marker);
+        match item.checked {
+            Some(true) => marker.push_str("[x] "),
+            Some(false) => marker.push_str("[ ] "),
+            None => {}
+        }
+        self.nested(&item.children, &marker, marker.chars().count())
+    }
+
+    fn render_definition_list(&mut self, list: &DefinitionList) -> String {
+        let items: Vec<String> = list
+            .items
+            .iter()
+            .map(|item| self.render_definition_item(item))
+            .collect();
+        items.join("\n")
+    }
+
+    fn render_definition_item(&mut self, item: &DefinitionItem) -> String {
+        let term: Vec<&Inline> = item.term.iter().collect();
+        let mut text = self.fill(&term);
+        for definition in &item.definitions {
+            text.push('\n');
+            text.push_str(&self.render_definition(definition));
+        }
+        text
+    }
+
+    fn render_definition(&mut self, definition: &Definition) -> String {
+        self.nested(&definition.children, "  ", 2)
+    }
+
+    fn render_container(&mut self, container: &Container) -> String {
+        let content = self.render_blocks(&container.children);
+        match container.argument.trim() {
+            "" => content,
+            argument => format!("{}\n{}", argument, content),
+        }
+    }
+
+    fn render_figure(&mut self, figure: &Figure) -> String {
+        match &figure.image.title {
+            Some(title) => title.clone(),
+            None => figure.image.alt.clone(),
+        }
+    }
+
+    fn render_table_of_contents(&mut self, _toc: &TableOfContents) -> String {
+        String::new()
+    }
+
+    fn render_footnote_section(&mut self, section: &FootnoteSection) -> String {
+        let footnotes: Vec<String> = section
+            .footnotes
+            .iter()
+            .map(|footnote| self.render_footnote(footnote))
+            .collect();
+        footnotes.join("\n")
+    }
+
+    fn render_footnote(&mut self, footnote: &Footnote) -> String {
+        let marker = format!("[{}] ", footnote.number);
+        self.nested(&footnote.children, &marker, marker.chars().count())
+    }
+
+    fn render_plain(&mut self, children: &[Inline]) -> String {
+        let children: Vec<&Inline> = children.iter().collect();
+        self.fill(&children)
+    }
+
+    fn render_text(&mut self, text: &Text, before: Option<char>, after: Option<char>) -> String {
+        self.options
+            .text_transforms
+            .iter()
+            .fold(text.value.to_owned(), |value, transform| {
+                transform.transform_between(&value, before, after)
+            })
+    }
+
+    fn render_escaped_text(&mut self, text: &EscapedText) -> String {
+        text.value.clone()
+    }
+
+    fn render_bold(&mut self, bold: &BoldText) -> String {
+        self.render_inlines(&bold.children)
+    }
+
+    fn render_italics(&mut self, italics: &ItalicizedText) -> String {
+        self.render_inlines(&italics.children)
+    }
+
+    fn render_inline_code(&mut self, code: &InlineCode) -> String {
+        code.code_content.clone()
+    }
+
+    fn render_link(&mut self, link: &Link) -> String {
+        self.render_inlines(&link.children)
+    }
+
+    fn render_image(&mut self, image: &Image) -> String {
+        image.alt.clone()
+    }
+
+    fn render_inline_math(&mut self, math: &InlineMath) -> String {
+        math.tex.clone()
+    }
+
+    fn render_footnote_reference(&mut self, reference: &FootnoteReference) -> String {
+        format!("[{}]", reference.number)
+    }
+
+    fn render_task_progress(&mut self, progress: &TaskProgress) -> String {
+        format!("{}/{}", progress.done, progress.total)
+    }
+
+    fn render_soft_break(&mut self, _soft_break: &SoftBreak) -> String {
+        self.soft_break.to_string()
+    }
+
+    fn render_hard_break(&mut self, _hard_break: &HardBreak) -> String {
+        "\n".to_string()
+    }
+
+    fn render_attributes(&mut self, attributes: &Attributes) -> String {
+        attributes.literal.clone()
+    }
+}
+
+/// `trim_lines` removes the white space around every line of `text`, and the blank lines around
+/// it.
+fn trim_lines(text: &str) -> String {
+    let lines: Vec<&str> = text.trim().split('\n').map(str::trim).collect();
+    lines.join("\n")
+}
+
+/// `prefix_lines` writes `first` before the first line of `text`, and indents the lines after it
+/// by `indent` spaces. blank lines stay blank.
+fn prefix_lines(text: &str, first: &str, indent: usize) -> String {
+    let padding = " ".repeat(indent);
+    let lines: Vec<String> = text
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| match index {
+            0 => format!("{}{}", first, line),
+            _ if line.is_empty() => String::new(),
+            _ => format!("{}{}", padding, line),
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// `table` returns the rows of the table `text` is (with the cells of each row separated by
+/// ` | `, and without the delimiter row), or `None` if it isn't a table: a header row and a
+/// delimiter row (`|---|:---:|`), and any number of rows after them, all starting with a `|`.
+fn table(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text.trim().split('\n').map(str::trim).collect();
+    if lines.len() < 2 || !lines.iter().all(|line| line.starts_with('|')) {
+        return None;
+    }
+
+    let cells = |line: &str| -> Vec<String> {
+        let line = line.strip_prefix('|').unwrap_or(line);
+        let line = line.strip_suffix('|').unwrap_or(line);
+        line.split('|')
+            .map(|cell| cell.trim().to_string())
+            .collect()
+    };
+    let is_delimiter = |cell: &String| {
+        let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+        !dashes.is_empty() && dashes.chars().all(|ch| ch == '-')
+    };
+    if !cells(lines[1]).iter().all(is_delimiter) {
+        return None;
+    }
+
+    let rows: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != 1)
+        .map(|(_, line)| cells(line).join(" | "))
+        .collect();
+    Some(rows.join("\n"))
+}
+
+/// `wrap` breaks the lines of `text` so they are at most `width` characters long, between
+/// words. a word longer than `width` is on a line of its own.
+pub fn wrap(text: &str, width: usize) -> String {
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let mut wrapped = String::new();
+            let mut line_width = 0;
+            for word in line.split_whitespace() {
+                let word_width = word.chars().count();
+                if line_width > 0 && line_width + 1 + word_width > width {
+                    wrapped.push('\n');
+                    line_width = 0;
+                } else if line_width > 0 {
+                    wrapped.push(' ');
+                    line_width += 1;
+                }
+                wrapped.push_str(word);
+                line_width += word_width;
+            }
+            wrapped
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// `summary` returns the beginning of `text` on a single line, at most `max_chars` characters
+/// long, eg. for a search result or a `<meta name="description">`. it ends between two words,
+/// with `…` when some of the text is left out. the first word is only cut if it doesn't fit on
+/// its own.
+pub fn summary(text: &str, max_chars: usize) -> String {
+    let mut summary = String::new();
+    let mut length = 0;
+    let mut words = text.split_whitespace().peekable();
+    while let Some(word) = words.peek() {
+        let word_length = word.chars().count();
+        let separator = if length == 0 { 0 } else { 1 };
+        if length + separator + word_length > max_chars {
+            break;
+        }
+        if separator == 1 {
+            summary.push(' ');
+        }
+        summary.push_str(word);
+        length += separator + word_length;
+        words.next();
+    }
+    if words.peek().is_none() || max_chars == 0 {
+        return summary;
+    }
+
+    // make room for the ellipsis, which doesn't go after punctuation
+    let trim_punctuation = |summary: &mut String| {
+        let trimmed = summary
+            .trim_end_matches([',', ';', ':', '.', '!', '?'])
+            .len();
+        summary.truncate(trimmed);
+    };
+    trim_punctuation(&mut summary);
+    while summary.chars().count() + 1 > max_chars {
+        match summary.rfind(' ') {
+            Some(index) => summary.truncate(index),
+            None => summary.clear(),
+        }
+        trim_punctuation(&mut summary);
+    }
+    if summary.is_empty() {
+        // the first word doesn't fit with the ellipsis after it
+        let word = text.split_whitespace().next().unwrap_or_default();
+        summary = word.chars().take(max_chars - 1).collect();
+    }
+    format!("{}…", summary)
+}
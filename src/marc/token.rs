@@ -12,17 +12,32 @@ pub enum TokenType {
     GreaterThan,
     Backtick,
     TripleBacktick,
+    TripleTilde,
     LeftBracket,
     RightBracket,
     LeftParen,
     RightParen,
     Exclamation,
+    Attributes,
+    FootnoteReference,
+    FootnoteDefinition,
+    Autolink,
+    Url,
+    ThematicBreak,
+    SetextUnderline,
+    Escape,
+    Entity,
+    Math,
+    MathFence,
+    ContainerFence,
 
     UnorderedListItem,
     OrderedListItem,
+    DefinitionMarker,
 
     Text,
     NewLine,
+    Indent,
 
     EOF,
 }
@@ -41,15 +56,30 @@ impl TokenType {
             TokenType::DoubleAsterisk => "**".to_string(),
             TokenType::GreaterThan => ">".to_string(),
             TokenType::NewLine => "\n".to_string(),
+            TokenType::Indent => "    ".to_string(),
             TokenType::LeftBracket => "[".to_string(),
             TokenType::RightBracket => "]".to_string(),
             TokenType::LeftParen => "(".to_string(),
             TokenType::RightParen => ")".to_string(),
             TokenType::Exclamation => "!".to_string(),
+            TokenType::Attributes => "{}".to_string(),
+            TokenType::FootnoteReference => "[^]".to_string(),
+            TokenType::FootnoteDefinition => "[^]:".to_string(),
+            TokenType::Autolink => "<>".to_string(),
+            TokenType::Url => "URL_TYPE".to_string(),
+            TokenType::ThematicBreak => "---".to_string(),
+            TokenType::SetextUnderline => "===".to_string(),
+            TokenType::Escape => "\\".to_string(),
+            TokenType::Entity => "&;".to_string(),
+            TokenType::Math => "$".to_string(),
+            TokenType::MathFence => "$$".to_string(),
+            TokenType::ContainerFence => ":::".to_string(),
             TokenType::UnorderedListItem => "-".to_string(),
             TokenType::OrderedListItem => "1.".to_string(),
+            TokenType::DefinitionMarker => ":".to_string(),
             TokenType::Backtick => "`".to_string(),
             TokenType::TripleBacktick => "```".to_string(),
+            TokenType::TripleTilde => "~~~".to_string(),
             TokenType::EOF => "EOF".to_string(),
         }
     }
@@ -0,0 +1,407 @@
+/// `TextTransform` rewrites the text of a document as it is written out, eg. to replace emoji
+/// shortcodes or straight quotes. transforms only see the plain text of a document: code, math,
+/// urls and escaped characters are left alone.
+///
+/// transforms are opt-in: they are added to `RenderOptions::text_transforms` and run in the
+/// order they were added.
+pub trait TextTransform {
+    fn transform(&self, text: &str) -> String;
+
+    /// `transform_between` rewrites `text`, which comes after the char `before` and before the
+    /// char `after` in its paragraph (`None` at the start or the end of it). the chars around a
+    /// text can be in other inlines, like the quotes around the emphasis of `"*a*"`. they are
+    /// ignored by default.
+    fn transform_between(&self, text: &str, _before: Option<char>, _after: Option<char>) -> String {
+        self.transform(text)
+    }
+}
+
+/// `Emoji` replaces `:shortcode:`s (like `:rocket:`) with the emoji they stand for. unknown
+/// shortcodes are left as they are.
+pub struct Emoji;
+
+impl TextTransform for Emoji {
+    fn transform(&self, text: &str) -> String {
+        outside_tags(text, None, None, |text, _, _| {
+            let mut result = String::with_capacity(text.len());
+            let mut rest = text;
+            while let Some(start) = rest.find(':') {
+                result.push_str(&rest[..start]);
+                rest = &rest[start..];
+
+                let name_len = rest[1..]
+                    .find(|ch: char| {
+                        !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '+' || ch == '-')
+                    })
+                    .unwrap_or(rest.len() - 1);
+                let name = &rest[1..1 + name_len];
+                if rest[1 + name_len..].starts_with(':')
+                    && let Some(emoji) = emoji(name)
+                {
+                    result.push_str(emoji);
+                    rest = &rest[name_len + 2..];
+                } else {
+                    result.push(':');
+                    rest = &rest[1..];
+                }
+            }
+            result.push_str(rest);
+            result
+        })
+    }
+}
+
+/// `SmartPunctuation` is a "smartypants" pass: straight quotes become curly ones (`"a"` is
+/// `“a”` and `don't` is `don’t`), `--` becomes an en dash, `---` an em dash and `...` an
+/// ellipsis.
+pub struct SmartPunctuation;
+
+impl TextTransform for SmartPunctuation {
+    fn transform(&self, text: &str) -> String {
+        self.transform_between(text, None, None)
+    }
+
+    /// a quote at the start or the end of the text is opening or closing depending on the chars
+    /// around the text, so `"*a*"` is `“<em>a</em>”`
+    fn transform_between(&self, text: &str, before: Option<char>, after: Option<char>) -> String {
+        outside_tags(text, before, after, |text, before, after| {
+            let chars: Vec<char> = text.chars().collect();
+            let mut result = String::with_capacity(text.len());
+            let mut i = 0;
+            while i < chars.len() {
+                let prev = i.checked_sub(1).map(|i| chars[i]).or(before);
+                let next = chars.get(i + 1).copied().or(after);
+                match chars[i] {
+                    '-' if next == Some('-') => {
+                        if chars.get(i + 2) == Some(&'-') {
+                            result.push('—');
+                            i += 3;
+                        } else {
+                            result.push('–');
+                            i += 2;
+                        }
+                        continue;
+                    }
+                    '.' if next == Some('.') && chars.get(i + 2) == Some(&'.') => {
+                        result.push('…');
+                        i += 3;
+                        continue;
+                    }
+                    '"' if opens_quote(prev, next) => result.push('“'),
+                    '"' => result.push('”'),
+                    '\'' if opens_quote(prev, next) => result.push('‘'),
+                    // a closing single quote is also an apostrophe (`don’t`)
+                    '\'' => result.push('’'),
+                    ch => result.push(ch),
+                }
+                i += 1;
+            }
+            result
+        })
+    }
+}
+
+/// `opens_quote` reports whether a quote between `prev` and `next` opens a quotation: it comes
+/// after white space or an opening bracket (or the start of the text) and before a word.
+fn opens_quote(prev: Option<char>, next: Option<char>) -> bool {
+    let after_space = prev.is_none_or(|ch| ch.is_whitespace() || "([{-–—".contains(ch));
+    let before_word = next.is_some_and(|ch| {
+        !ch.is_whitespace() && !matches!(ch, ',' | '.' | ';' | ':' | '!' | '?' | ')' | ']' | '}')
+    });
+    after_space && before_word
+}
+
+/// `outside_tags` applies `transform` to the parts of `text` that aren't html tags (`<...>`),
+/// so that the attributes of inline html aren't rewritten. `transform` is given the chars
+/// around each part: `before` and `after` at the ends of `text`, none next to a tag.
+fn outside_tags(
+    text: &str,
+    before: Option<char>,
+    after: Option<char>,
+    transform: impl Fn(&str, Option<char>, Option<char>) -> String,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut before = before;
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        result.push_str(&transform(&rest[..start], before, None));
+        result.push_str(&rest[start..start + end + 1]);
+        rest = &rest[start + end + 1..];
+        before = None;
+    }
+    result.push_str(&transform(rest, before, after));
+    result
+}
+
+/// `emoji` returns the emoji of a shortcode (without its colons), eg. `emoji("tada")` is `🎉`.
+pub fn emoji(shortcode: &str) -> Option<&'static str> {
+    EMOJI
+        .iter()
+        .find(|(name, _)| *name == shortcode)
+        .map(|(_, emoji)| *emoji)
+}
+
+/// the shortcodes that are recognized, with the emoji they stand for. it is the commonly used
+/// part of the github (gemoji) list.
+const EMOJI: &[(&str, &str)] = &[
+    // smileys
+    ("smile", "😄"),
+    ("smiley", "😃"),
+    ("grinning", "😀"),
+    ("grin", "😁"),
+    ("laughing", "😆"),
+    ("sweat_smile", "😅"),
+    ("joy", "😂"),
+    ("rofl", "🤣"),
+    ("slightly_smiling_face", "🙂"),
+    ("upside_down_face", "🙃"),
+    ("wink", "😉"),
+    ("blush", "😊"),
+    ("innocent", "😇"),
+    ("heart_eyes", "😍"),
+    ("star_struck", "🤩"),
+    ("kissing_heart", "😘"),
+    ("yum", "😋"),
+    ("stuck_out_tongue", "😛"),
+    ("stuck_out_tongue_winking_eye", "😜"),
+    ("thinking", "🤔"),
+    ("neutral_face", "😐"),
+    ("expressionless", "😑"),
+    ("no_mouth", "😶"),
+    ("smirk", "😏"),
+    ("unamused", "😒"),
+    ("roll_eyes", "🙄"),
+    ("grimacing", "😬"),
+    ("relieved", "😌"),
+    ("pensive", "😔"),
+    ("sleepy", "😪"),
+    ("sleeping", "😴"),
+    ("mask", "😷"),
+    ("nerd_face", "🤓"),
+    ("sunglasses", "😎"),
+    ("confused", "😕"),
+    ("worried", "😟"),
+    ("open_mouth", "😮"),
+    ("astonished", "😲"),
+    ("flushed", "😳"),
+    ("pleading_face", "🥺"),
+    ("cry", "😢"),
+    ("sob", "😭"),
+    ("scream", "😱"),
+    ("disappointed", "😞"),
+    ("sweat", "😓"),
+    ("weary", "😩"),
+    ("triumph", "😤"),
+    ("rage", "😡"),
+    ("angry", "😠"),
+    ("exploding_head", "🤯"),
+    ("skull", "💀"),
+    ("poop", "💩"),
+    ("clown_face", "🤡"),
+    ("ghost", "👻"),
+    ("alien", "👽"),
+    ("robot", "🤖"),
+    ("see_no_evil", "🙈"),
+    // people and gestures
+    ("wave", "👋"),
+    ("ok_hand", "👌"),
+    ("v", "✌️"),
+    ("crossed_fingers", "🤞"),
+    ("point_up", "☝️"),
+    ("point_right", "👉"),
+    ("point_left", "👈"),
+    ("point_down", "👇"),
+    ("+1", "👍"),
+    ("thumbsup", "👍"),
+    ("-1", "👎"),
+    ("thumbsdown", "👎"),
+    ("fist", "✊"),
+    ("clap", "👏"),
+    ("raised_hands", "🙌"),
+    ("pray", "🙏"),
+    ("handshake", "🤝"),
+    ("muscle", "💪"),
+    ("eyes", "👀"),
+    ("brain", "🧠"),
+    ("man_shrugging", "🤷‍♂️"),
+    ("woman_shrugging", "🤷‍♀️"),
+    ("shrug", "🤷"),
+    ("facepalm", "🤦"),
+    // hearts and symbols
+    ("heart", "❤️"),
+    ("orange_heart", "🧡"),
+    ("yellow_heart", "💛"),
+    ("green_heart", "💚"),
+    ("blue_heart", "💙"),
+    ("purple_heart", "💜"),
+    ("black_heart", "🖤"),
+    ("broken_heart", "💔"),
+    ("sparkling_heart", "💖"),
+    ("100", "💯"),
+    ("boom", "💥"),
+    ("collision", "💥"),
+    ("sparkles", "✨"),
+    ("star", "⭐"),
+    ("star2", "🌟"),
+    ("dizzy", "💫"),
+    ("zap", "⚡"),
+    ("fire", "🔥"),
+    ("warning", "⚠️"),
+    ("no_entry", "⛔"),
+    ("x", "❌"),
+    ("heavy_check_mark", "✔️"),
+    ("white_check_mark", "✅"),
+    ("ballot_box_with_check", "☑️"),
+    ("question", "❓"),
+    ("exclamation", "❗"),
+    ("bangbang", "‼️"),
+    ("heavy_plus_sign", "➕"),
+    ("heavy_minus_sign", "➖"),
+    ("arrow_right", "➡️"),
+    ("arrow_left", "⬅️"),
+    ("arrow_up", "⬆️"),
+    ("arrow_down", "⬇️"),
+    ("recycle", "♻️"),
+    ("information_source", "ℹ️"),
+    ("copyright", "©️"),
+    ("registered", "®️"),
+    ("tm", "™️"),
+    ("red_circle", "🔴"),
+    ("green_circle", "🟢"),
+    ("large_blue_circle", "🔵"),
+    ("white_circle", "⚪"),
+    ("black_circle", "⚫"),
+    // nature and food
+    ("sunny", "☀️"),
+    ("cloud", "☁️"),
+    ("umbrella", "☔"),
+    ("snowflake", "❄️"),
+    ("rainbow", "🌈"),
+    ("earth_americas", "🌎"),
+    ("crescent_moon", "🌙"),
+    ("seedling", "🌱"),
+    ("evergreen_tree", "🌲"),
+    ("cactus", "🌵"),
+    ("four_leaf_clover", "🍀"),
+    ("rose", "🌹"),
+    ("sunflower", "🌻"),
+    ("cat", "🐱"),
+    ("dog", "🐶"),
+    ("fox_face", "🦊"),
+    ("bear", "🐻"),
+    ("panda_face", "🐼"),
+    ("penguin", "🐧"),
+    ("bird", "🐦"),
+    ("turtle", "🐢"),
+    ("snake", "🐍"),
+    ("crab", "🦀"),
+    ("octopus", "🐙"),
+    ("whale", "🐳"),
+    ("bug", "🐛"),
+    ("bee", "🐝"),
+    ("unicorn", "🦄"),
+    ("apple", "🍎"),
+    ("banana", "🍌"),
+    ("lemon", "🍋"),
+    ("cherries", "🍒"),
+    ("avocado", "🥑"),
+    ("pizza", "🍕"),
+    ("hamburger", "🍔"),
+    ("taco", "🌮"),
+    ("cake", "🍰"),
+    ("birthday", "🎂"),
+    ("cookie", "🍪"),
+    ("coffee", "☕"),
+    ("tea", "🍵"),
+    ("beer", "🍺"),
+    ("beers", "🍻"),
+    ("wine_glass", "🍷"),
+    // objects, activities and travel
+    ("rocket", "🚀"),
+    ("airplane", "✈️"),
+    ("car", "🚗"),
+    ("bike", "🚲"),
+    ("ship", "🚢"),
+    ("construction", "🚧"),
+    ("house", "🏠"),
+    ("tada", "🎉"),
+    ("confetti_ball", "🎊"),
+    ("balloon", "🎈"),
+    ("gift", "🎁"),
+    ("trophy", "🏆"),
+    ("medal_sports", "🏅"),
+    ("dart", "🎯"),
+    ("video_game", "🎮"),
+    ("art", "🎨"),
+    ("musical_note", "🎵"),
+    ("bell", "🔔"),
+    ("mega", "📣"),
+    ("loudspeaker", "📢"),
+    ("bulb", "💡"),
+    ("flashlight", "🔦"),
+    ("wrench", "🔧"),
+    ("hammer", "🔨"),
+    ("hammer_and_wrench", "🛠️"),
+    ("gear", "⚙️"),
+    ("link", "🔗"),
+    ("lock", "🔒"),
+    ("unlock", "🔓"),
+    ("key", "🔑"),
+    ("mag", "🔍"),
+    ("computer", "💻"),
+    ("keyboard", "⌨️"),
+    ("iphone", "📱"),
+    ("floppy_disk", "💾"),
+    ("cd", "💿"),
+    ("camera", "📷"),
+    ("tv", "📺"),
+    ("battery", "🔋"),
+    ("electric_plug", "🔌"),
+    ("email", "📧"),
+    ("envelope", "✉️"),
+    ("inbox_tray", "📥"),
+    ("outbox_tray", "📤"),
+    ("package", "📦"),
+    ("memo", "📝"),
+    ("pencil2", "✏️"),
+    ("book", "📖"),
+    ("books", "📚"),
+    ("bookmark", "🔖"),
+    ("label", "🏷️"),
+    ("clipboard", "📋"),
+    ("pushpin", "📌"),
+    ("paperclip", "📎"),
+    ("scissors", "✂️"),
+    ("calendar", "📆"),
+    ("chart_with_upwards_trend", "📈"),
+    ("chart_with_downwards_trend", "📉"),
+    ("bar_chart", "📊"),
+    ("file_folder", "📁"),
+    ("wastebasket", "🗑️"),
+    ("hourglass", "⌛"),
+    ("stopwatch", "⏱️"),
+    ("alarm_clock", "⏰"),
+    ("moneybag", "💰"),
+    ("dollar", "💵"),
+    ("gem", "💎"),
+    ("lipstick", "💄"),
+    ("ring", "💍"),
+    ("crown", "👑"),
+    ("tophat", "🎩"),
+    ("checkered_flag", "🏁"),
+    ("triangular_flag_on_post", "🚩"),
+    ("white_flag", "🏳️"),
+    ("pirate_flag", "🏴‍☠️"),
+    ("test_tube", "🧪"),
+    ("microscope", "🔬"),
+    ("telescope", "🔭"),
+    ("satellite", "📡"),
+    ("pill", "💊"),
+    ("lady_beetle", "🐞"),
+    ("zzz", "💤"),
+    ("speech_balloon", "💬"),
+    ("thought_balloon", "💭"),
+];
@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// `Warning` is a problem found in a document that doesn't stop it from being parsed, eg. a
+/// reference to a footnote that is never defined.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub line: usize,
+    pub col: usize,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(line: usize, col: usize, message: String) -> Self {
+        Warning { line, col, message }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.col, self.message)
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+/// `Span` is the part of the source a node was parsed from: the byte range it covers (`start`
+/// up to, but not including, `end`) and the line and column of its first and last characters.
+/// lines and columns are 1-indexed and columns count characters, like the positions of tokens.
+///
+/// the offsets are in the source the lexer works on, ie. with its line endings normalized to
+/// `\n` (see `marc::lexer::Lexer::from`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl Span {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// `to` returns the span from the start of this span to the end of `other`.
+    pub fn to(&self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end,
+            start_line: self.start_line,
+            start_col: self.start_col,
+            end_line: other.end_line,
+            end_col: other.end_col,
+        }
+    }
+}
+
+impl fmt::Display for Span {
+    /// a span is written as `start_line:start_col-end_line:end_col`, the format of the
+    /// `data-sourcepos` attribute.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}-{}:{}",
+            self.start_line, self.start_col, self.end_line, self.end_col
+        )
+    }
+}
+
+/// `LineIndex` knows where the lines of a source start, to turn the byte offsets of a node into
+/// its span.
+#[derive(Debug, Default, Clone)]
+pub struct LineIndex {
+    source: String,
+    // the byte offset of the first character of every line
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        LineIndex {
+            source: source.to_string(),
+            line_starts,
+        }
+    }
+
+    /// `offset` returns the byte offset of the character at column `col` of `line`. column 0
+    /// is the new line character ending the line before, which is where the lexers record new
+    /// line tokens.
+    pub fn offset(&self, line: usize, col: usize) -> usize {
+        if line == 0 {
+            return 0;
+        }
+        if col == 0 {
+            return self.line_start(line).saturating_sub(1);
+        }
+
+        let start = self.line_start(line);
+        let text = &self.source[start..self.line_end(line)];
+        let index = text
+            .char_indices()
+            .nth(col - 1)
+            .map_or(text.len(), |(index, _)| index);
+        start + index
+    }
+
+    /// `span` returns the span of the bytes from `start` to `end`.
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        let start = start.min(self.source.len());
+        let end = end.clamp(start, self.source.len());
+        let (start_line, start_col) = self.position(start);
+
+        // the end of the span is its last character
+        let last = match self.source[..end].chars().next_back() {
+            Some(ch) if end > start => end - ch.len_utf8(),
+            _ => start,
+        };
+        let (end_line, end_col) = self.position(last);
+
+        Span {
+            start,
+            end,
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+        }
+    }
+
+    /// `position` returns the line and column of the character at byte `offset`.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let start = self.line_starts[line - 1];
+        let col = self.source[start..offset].chars().count() + 1;
+        (line, col)
+    }
+
+    fn line_start(&self, line: usize) -> usize {
+        self.line_starts
+            .get(line - 1)
+            .copied()
+            .unwrap_or(self.source.len())
+    }
+
+    fn line_end(&self, line: usize) -> usize {
+        self.line_starts
+            .get(line)
+            .map_or(self.source.len(), |start| start - 1)
+            .max(self.line_start(line))
+    }
+}
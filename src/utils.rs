@@ -1,3 +1,4 @@
+use crate::marc::ast::attributes::Attributes;
 use crate::marc::token::{self, TokenType};
 
 pub const KEYWORDS: [&str; 9] = [
@@ -22,6 +23,7 @@ pub fn is_numeric(ch: Option<char>) -> bool {
 pub fn is_block_level_token(t: token::TokenType) -> bool {
     match t {
         TokenType::TripleBacktick
+        | TokenType::TripleTilde
         | TokenType::H1
         | TokenType::H2
         | TokenType::H3
@@ -41,6 +43,157 @@ pub fn is_inline_token(ch: Option<char>) -> bool {
     }
 }
 
+/// `slugify` turns `text` into something that can be used as the id of an html element (and in
+/// a url fragment): it is lowercased, white space becomes `-` and any punctuation other than `-`
+/// and `_` is removed. eg. `slugify("Hello, World!")` is `hello-world`.
+pub fn slugify(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|ch| match ch {
+            ch if ch.is_alphanumeric() || ch == '-' || ch == '_' => Some(ch),
+            ch if ch.is_whitespace() => Some('-'),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `footnote_label` returns the label of the footnote reference (`[^label]`) at the start of
+/// `text`, if there's one. labels can't be empty or contain white space.
+pub fn footnote_label(text: &str) -> Option<&str> {
+    let rest = text.strip_prefix("[^")?;
+    let label = &rest[..rest.find(']')?];
+    if label.is_empty() || label.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(label)
+}
+
+/// `task_marker` returns whether the task list item marker (`[ ]` or `[x]`) at the start of
+/// `text` is checked, or `None` if `text` doesn't start with one. the marker has to be followed
+/// by white space (or nothing).
+pub fn task_marker(text: &str) -> Option<bool> {
+    let checked = match text.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+
+    match text[3..].chars().next() {
+        Some(ch) if !ch.is_whitespace() => None,
+        _ => Some(checked),
+    }
+}
+
+/// `is_thematic_break` reports whether `line` is a thematic break: three or more `-`, `*` or `_`
+/// (all the same), optionally separated by spaces and indented by up to 3 spaces.
+pub fn is_thematic_break(line: &str) -> bool {
+    if indentation(line) > 3 {
+        return false;
+    }
+
+    let mut chars = line.chars().filter(|ch| !ch.is_whitespace());
+    let Some(marker) = chars.next().filter(|ch| "-*_".contains(*ch)) else {
+        return false;
+    };
+
+    let mut count = 1;
+    for ch in chars {
+        if ch != marker {
+            return false;
+        }
+        count += 1;
+    }
+    count >= 3
+}
+
+/// `is_setext_underline` reports whether `line` is a run of `marker`s (`=` or `-`), indented by
+/// up to 3 spaces and followed by nothing but white space.
+pub fn is_setext_underline(line: &str, marker: char) -> bool {
+    let trimmed = line.trim();
+    indentation(line) <= 3 && !trimmed.is_empty() && trimmed.chars().all(|ch| ch == marker)
+}
+
+/// `interrupts_paragraph` reports whether `line` starts a block that ends the paragraph before
+/// it (a heading, a block quote, a code fence, a list item or definition, a thematic break, etc.) instead of
+/// going on with it.
+pub fn interrupts_paragraph(line: &str) -> bool {
+    let trimmed = line.trim();
+    if indentation(line) > 3 {
+        return false;
+    }
+
+    trimmed.starts_with('>')
+        || trimmed.starts_with("```")
+        || trimmed.starts_with("~~~")
+        || line.starts_with(":::")
+        || list_marker(line).is_some()
+        || line.starts_with(": ")
+        || (trimmed.starts_with('#') && trimmed.trim_start_matches('#').starts_with(' '))
+        || footnote_label(trimmed).is_some_and(|label| trimmed[label.len() + 3..].starts_with(':'))
+        || trimmed == "[[toc]]"
+        || line.trim_end() == "$$"
+        || is_attribute_line(line)
+        || is_thematic_break(line)
+}
+
+/// `list_marker` returns the marker of the list item `line` starts with, with the indentation
+/// before it and the space after it (eg. `- ` or `  10. `), and whether it is the marker of an
+/// ordered list. a marker can be indented by up to 3 spaces and the number of an ordered list
+/// item has at most 9 digits.
+pub fn list_marker(line: &str) -> Option<(&str, bool)> {
+    let indentation = indentation(line);
+    if indentation > 3 {
+        return None;
+    }
+
+    let rest = &line[indentation..];
+    if rest.starts_with("- ") {
+        return Some((&line[..indentation + 2], false));
+    }
+    let digits = rest.chars().take_while(|ch| ch.is_ascii_digit()).count();
+    if (1..=9).contains(&digits) && rest[digits..].starts_with(". ") {
+        return Some((&line[..indentation + digits + 2], true));
+    }
+    None
+}
+
+/// `is_attribute_line` reports whether `line` is an attribute block (`{#id .class}`) on its own,
+/// which sets the attributes of the block before it.
+pub fn is_attribute_line(line: &str) -> bool {
+    line.starts_with('{') && Attributes::parse(line.trim_end()).is_some()
+}
+
+/// `is_paragraph_line` reports whether `line` can be a line of a paragraph: it isn't blank,
+/// indented code or a line that starts (or underlines) another kind of block.
+pub fn is_paragraph_line(line: &str) -> bool {
+    !line.trim().is_empty()
+        && indentation(line) < 4
+        && !interrupts_paragraph(line)
+        && !is_setext_underline(line, '=')
+}
+
+/// `indentation` returns the number of spaces at the start of `line`.
+pub fn indentation(line: &str) -> usize {
+    line.chars().take_while(|ch| *ch == ' ').count()
+}
+
 pub fn is_keyword(t: &str) -> bool {
     KEYWORDS.contains(&t)
 }
+
+/// `escape_html` replaces the characters that have a special meaning in html (`&`, `<`, `>` and
+/// `"`) with their entity references so the text can be safely written into the output.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
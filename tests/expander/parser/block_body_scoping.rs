@@ -67,7 +67,7 @@ fn test_fn_allows_bare_statements() {
     let fn_stmt = FunctionDefinitionStatement::new(
         "add".to_string(),
         vec!["a".to_string(), "b".to_string()],
-        vec![Box::new(
+        vec![Box::new(MarcNode::Statement(Box::new(
             md_to_html::expander::ast::statement::VariableAssignmentStatement::new(
                 "sum".to_string(),
                 Box::new(Expression::OperatorInfix(
@@ -94,7 +94,7 @@ fn test_fn_allows_bare_statements() {
                     ),
                 )),
             ),
-        )],
+        )))],
         Some(md_to_html::expander::ast::statement::ReturnStatement::new(
             Box::new(Expression::VariableAccess(VariableAccessExpression::new(
                 "sum".to_string(),
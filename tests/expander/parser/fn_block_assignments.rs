@@ -1,6 +1,6 @@
 use md_to_html::expander::{
     ast::{
-        Document, Node,
+        Document, MarcNode, Node,
         expression::{Expression, InfixExpression, IntegerExpression, VariableAccessExpression},
         operators::{Math, Op},
         statement::{FunctionDefinitionStatement, ReturnStatement, VariableAssignmentStatement},
@@ -33,7 +33,7 @@ fn test_fn_block_with_assignment_and_return() {
     )));
 
     let assign = VariableAssignmentStatement::new("sum".to_string(), rhs);
-    let body: Vec<Box<dyn Node>> = vec![Box::new(assign)];
+    let body = vec![Box::new(MarcNode::Statement(Box::new(assign)))];
 
     let return_stmt = ReturnStatement::new(Box::new(Expression::VariableAccess(
         VariableAccessExpression::new("sum".to_string()),
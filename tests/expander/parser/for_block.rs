@@ -8,6 +8,7 @@ use md_to_html::expander::{
     lexer::Lexer,
     parser::Parser,
 };
+use md_to_html::span::Span;
 
 #[test]
 fn test_for_block() {
@@ -25,9 +26,12 @@ fn test_for_block() {
     for_block.add_operation(Box::new(MarcNode::Text(TextNode::new(
         "Hello ".to_string(),
     ))));
-    for_block.add_operation(Box::new(MarcNode::Expression(Box::new(
-        Expression::VariableAccess(VariableAccessExpression::new("item".to_string())),
-    ))));
+    for_block.add_operation(Box::new(MarcNode::Expression(
+        Box::new(Expression::VariableAccess(VariableAccessExpression::new(
+            "item".to_string(),
+        ))),
+        Span::default(),
+    )));
 
     expected_program.add_node(Box::new(for_block));
 
@@ -14,4 +14,5 @@ pub mod object_access;
 pub mod operator_precedence;
 pub mod prefix_operators;
 pub mod sample_full;
+pub mod span;
 pub mod variable;
@@ -0,0 +1,105 @@
+use md_to_html::expander::{
+    ast::{MarcNode, Node, expression::Expression, text_node::TextNode},
+    lexer::Lexer,
+    parser::Parser,
+};
+use md_to_html::span::{LineIndex, Span};
+
+/// `node_sources` returns the source of every node of the document, as given by its span.
+fn node_sources(input: &str) -> Vec<String> {
+    let mut parser = Parser::new(Lexer::from(input));
+    let document = parser.parse_document();
+
+    document
+        .nodes()
+        .iter()
+        .map(|node| input[node.span().start..node.span().end].to_string())
+        .collect()
+}
+
+#[test]
+fn test_node_spans() {
+    let input = "Hi\n{% if name == \"x\" %}Hello{% endif %}\n@import \"a.md\" as a";
+
+    assert_eq!(
+        node_sources(input),
+        vec![
+            "Hi",
+            "\n",
+            "{% if name == \"x\" %}Hello{% endif %}",
+            "\n",
+            "@import \"a.md\" as a",
+        ]
+    );
+}
+
+#[test]
+fn test_block_span_lines_and_columns() {
+    let input = "Intro\n{% for item in items %}\n{{ item }}\n{% endfor %}";
+    let mut parser = Parser::new(Lexer::from(input));
+    let document = parser.parse_document();
+
+    let for_block = document.nodes()[2].span();
+    assert_eq!(for_block.to_string(), "2:1-4:12");
+    assert_eq!(&input[for_block.start..for_block.end], &input[6..]);
+}
+
+#[test]
+fn test_expression_spans() {
+    let input = "hello {{ name }} there";
+    assert_eq!(node_sources(input), vec!["hello ", "{{ name }}", " there"]);
+}
+
+#[test]
+fn test_span_of_nodes_starting_and_ending_with_expressions() {
+    let input = "{{ a }} and {{ b }}";
+    let source = LineIndex::new(input);
+    let expression = |start, end| {
+        let mut node =
+            MarcNode::Expression(Box::new(Expression::True(Span::default())), Span::default());
+        node.set_span(source.span(start, end));
+        Box::new(node) as Box<dyn Node>
+    };
+
+    // the empty expression of a blank line has no span and is left out
+    let nodes: Vec<Box<dyn Node>> = vec![
+        expression(0, 7),
+        Box::new(TextNode::new(" and ".to_string())),
+        expression(12, 19),
+        Box::new(MarcNode::Expression(
+            Box::new(Expression::Empty),
+            Span::default(),
+        )),
+    ];
+    assert_eq!(nodes.span(), source.span(0, 19));
+}
+
+#[test]
+fn test_spans_of_the_parts_of_an_expression() {
+    let input = "{{ total + (1 + 2) * -x }} and {{ greet(user.name, items[0], \"hi\") }}";
+    let source = |expression: &Expression| {
+        let span = expression.span();
+        input[span.start..span.end].to_string()
+    };
+    let mut parser = Parser::new(Lexer::from(input));
+
+    parser.advance_token(); // move past the `{{`
+    let expression = parser.parse_expression(0);
+    let Expression::OperatorInfix(sum) = expression.as_ref() else {
+        panic!("expected a sum, got {}", expression.token_literal());
+    };
+    let Expression::OperatorInfix(product) = sum.right.as_ref() else {
+        panic!("expected a product, got {}", sum.right.token_literal());
+    };
+    assert_eq!(source(&expression), "total + (1 + 2) * -x");
+    assert_eq!(source(&sum.left), "total");
+    assert_eq!(source(&sum.right), "(1 + 2) * -x");
+    assert_eq!(source(&product.left), "(1 + 2)");
+    assert_eq!(source(&product.right), "-x");
+
+    while parser.get_curr_token().literal != "greet" {
+        parser.advance_token();
+    }
+    let call = parser.parse_expression(0);
+    assert_eq!(source(&call), "greet(user.name, items[0], \"hi\")");
+}
@@ -0,0 +1,38 @@
+use md_to_html::expander::{
+    ast::{Node, statement::FunctionDefinitionStatement},
+    environment::Environment,
+    lexer::Lexer,
+    parser::Parser,
+};
+
+#[test]
+fn test_toc_expands_to_the_toc_marker() {
+    let input = "{{ toc() }}\n\n# Title";
+    let lexer = Lexer::from(input);
+    let mut parser = Parser::new(lexer);
+    let mut env = Environment::new();
+
+    let document = parser.parse_document();
+    let res = document.translate(&mut env);
+
+    assert_eq!(res, "[[toc]]\n\n# Title");
+}
+
+#[test]
+fn test_progress_expands_to_the_progress_marker() {
+    let input = "Done: {{ progress() }}";
+    let lexer = Lexer::from(input);
+    let mut parser = Parser::new(lexer);
+    let mut env = Environment::new();
+
+    let document = parser.parse_document();
+    let res = document.translate(&mut env);
+
+    assert_eq!(res, "Done: [[progress]]");
+}
+
+#[test]
+#[should_panic(expected = "`toc` is a built-in function")]
+fn test_builtin_names_cannot_be_redefined() {
+    FunctionDefinitionStatement::new("toc".to_string(), vec![], vec![], None);
+}
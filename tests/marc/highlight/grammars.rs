@@ -0,0 +1,156 @@
+use md_to_html::marc::highlight::{Highlight, Highlighter, Segment};
+
+fn tokenize<'a>(language: &str, code: &'a str) -> Vec<Segment<'a>> {
+    let highlighter = Highlighter::default();
+    let grammar = highlighter.grammar(language).unwrap();
+    let segments = grammar.tokenize(code);
+
+    // the segments must always add up to the original code
+    let joined: String = segments.iter().map(|segment| segment.text).collect();
+    assert_eq!(joined, code);
+
+    segments
+}
+
+fn highlighted<'a>(segments: &[Segment<'a>]) -> Vec<(Highlight, &'a str)> {
+    segments
+        .iter()
+        .filter(|segment| segment.highlight != Highlight::Plain)
+        .map(|segment| (segment.highlight, segment.text))
+        .collect()
+}
+
+#[test]
+fn test_highlights_rust() {
+    let code = "#[derive(Debug)]
+fn first<'a>(x: &'a str) -> Option<char> {
+    // the first char
+    let c = '\\n';
+    println!(\"{}\", 0..10);
+    x.chars().next()
+}";
+    let segments = tokenize("rust", code);
+
+    assert_eq!(
+        highlighted(&segments),
+        vec![
+            (Highlight::Attribute, "#[derive(Debug)]"),
+            (Highlight::Keyword, "fn"),
+            (Highlight::Function, "first"),
+            (Highlight::Operator, "<"),
+            (Highlight::Operator, ">"),
+            (Highlight::Operator, ":"),
+            (Highlight::Operator, "&"),
+            (Highlight::Type, "str"),
+            (Highlight::Operator, "->"),
+            (Highlight::Type, "Option"),
+            (Highlight::Operator, "<"),
+            (Highlight::Type, "char"),
+            (Highlight::Operator, ">"),
+            (Highlight::Comment, "// the first char"),
+            (Highlight::Keyword, "let"),
+            (Highlight::Operator, "="),
+            (Highlight::String, "'\\n'"),
+            (Highlight::Macro, "println!"),
+            (Highlight::String, "\"{}\""),
+            (Highlight::Number, "0"),
+            (Highlight::Number, "10"),
+            (Highlight::Function, "chars"),
+            (Highlight::Function, "next"),
+        ]
+    );
+}
+
+#[test]
+fn test_highlights_go() {
+    let code = "func main() {\n\tfmt.Println(`raw`, nil, 42)\n}";
+    let segments = tokenize("golang", code);
+
+    assert_eq!(
+        highlighted(&segments),
+        vec![
+            (Highlight::Keyword, "func"),
+            (Highlight::Function, "main"),
+            (Highlight::Function, "Println"),
+            (Highlight::String, "`raw`"),
+            (Highlight::Constant, "nil"),
+            (Highlight::Number, "42"),
+        ]
+    );
+}
+
+#[test]
+fn test_highlights_javascript() {
+    let code = "const $el = document.querySelector('#app'); /* the root */";
+    let segments = tokenize("js", code);
+
+    assert_eq!(
+        highlighted(&segments),
+        vec![
+            (Highlight::Keyword, "const"),
+            (Highlight::Operator, "="),
+            (Highlight::Function, "querySelector"),
+            (Highlight::String, "'#app'"),
+            (Highlight::Comment, "/* the root */"),
+        ]
+    );
+}
+
+#[test]
+fn test_highlights_shell() {
+    let code = "# build it\ncargo build --release && echo \"done $USER\" | tee out.log";
+    let segments = tokenize("bash", code);
+
+    assert_eq!(
+        highlighted(&segments),
+        vec![
+            (Highlight::Comment, "# build it"),
+            (Highlight::Function, "cargo"),
+            (Highlight::Operator, "&&"),
+            (Highlight::Function, "echo"),
+            (Highlight::String, "\"done $USER\""),
+            (Highlight::Operator, "|"),
+            (Highlight::Function, "tee"),
+        ]
+    );
+}
+
+#[test]
+fn test_highlights_json() {
+    let code = "{\"name\": \"marc\", \"version\": 1.5, \"private\": true, \"main\": null}";
+    let segments = tokenize("json", code);
+
+    assert_eq!(
+        highlighted(&segments),
+        vec![
+            (Highlight::Property, "\"name\""),
+            (Highlight::String, "\"marc\""),
+            (Highlight::Property, "\"version\""),
+            (Highlight::Number, "1.5"),
+            (Highlight::Property, "\"private\""),
+            (Highlight::Constant, "true"),
+            (Highlight::Property, "\"main\""),
+            (Highlight::Constant, "null"),
+        ]
+    );
+}
+
+#[test]
+fn test_highlights_markdown() {
+    let code = "## Usage\n- run `cargo run`, *then* open [it](out.html)\n```\n# not a heading\n```";
+    let segments = tokenize("md", code);
+
+    assert_eq!(
+        highlighted(&segments),
+        vec![
+            (Highlight::Heading, "## Usage"),
+            (Highlight::Keyword, "-"),
+            (Highlight::Code, "`cargo run`"),
+            (Highlight::Emphasis, "*then*"),
+            (Highlight::Link, "[it](out.html)"),
+            (Highlight::Code, "```"),
+            (Highlight::Code, "# not a heading"),
+            (Highlight::Code, "```"),
+        ]
+    );
+}
@@ -0,0 +1,89 @@
+use md_to_html::marc::{
+    ast::Node,
+    highlight::{Grammar, Highlight, Highlighter, Segment},
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+};
+
+/// `Sql` is a tiny grammar that only knows a handful of keywords
+struct Sql;
+
+impl Grammar for Sql {
+    fn names(&self) -> &[&str] {
+        &["sql"]
+    }
+
+    fn tokenize<'a>(&self, code: &'a str) -> Vec<Segment<'a>> {
+        code.split_inclusive(' ')
+            .map(|word| {
+                let highlight = match word.trim() {
+                    "SELECT" | "FROM" => Highlight::Keyword,
+                    _ => Highlight::Plain,
+                };
+                Segment::new(highlight, word)
+            })
+            .collect()
+    }
+}
+
+fn render(input: &str, options: &RenderOptions) -> String {
+    let lexer = Lexer::from(input);
+    let mut parser = Parser::new(lexer);
+    let program = parser.parse_program();
+    program.get_blocks()[0].evaluate(options)
+}
+
+#[test]
+fn test_highlights_code_blocks() {
+    let input = "```rust\nlet x = \"<\";\n```";
+
+    assert_eq!(
+        render(input, &RenderOptions::default()),
+        "<pre><code class=\"language-rust\">\
+<span class=\"hl-keyword\">let</span> x <span class=\"hl-operator\">=</span> \
+<span class=\"hl-string\">&quot;&lt;&quot;</span>;\n</code></pre>"
+    );
+}
+
+#[test]
+fn test_unknown_languages_are_not_highlighted() {
+    let input = "```cobol\nDISPLAY 'HELLO'.\n```";
+
+    assert_eq!(
+        render(input, &RenderOptions::default()),
+        "<pre><code class=\"language-cobol\">DISPLAY 'HELLO'.\n</code></pre>"
+    );
+}
+
+#[test]
+fn test_registers_custom_grammars() {
+    let mut options = RenderOptions::default();
+    options.highlighter.register(Sql);
+
+    let input = "```SQL\nSELECT name FROM users\n```";
+
+    assert_eq!(
+        render(input, &options),
+        "<pre><code class=\"language-SQL\">\
+<span class=\"hl-keyword\">SELECT </span>name \
+<span class=\"hl-keyword\">FROM </span>users\n</code></pre>"
+    );
+}
+
+#[test]
+fn test_highlighted_comments_are_split_per_line() {
+    let mut highlighter = Highlighter::new();
+    assert_eq!(highlighter.highlight("rust", "fn"), None);
+
+    highlighter = Highlighter::default();
+    let lines = highlighter.highlight_lines("rust", "/* a\nb */ x").unwrap();
+
+    assert_eq!(
+        lines,
+        vec![
+            "<span class=\"hl-comment\">/* a</span>",
+            "<span class=\"hl-comment\">b */</span> x",
+        ]
+    );
+}
@@ -0,0 +1,2 @@
+pub mod grammars;
+pub mod highlighter;
@@ -280,3 +280,74 @@ let x = 15;
         assert_eq!(token.literal, expected_token.literal);
     }
 }
+
+#[test]
+fn test_tokenize_tilde_and_long_fences() {
+    let input = "\
+~~~~
+```
+~~~~
+a ~~~ b";
+    let mut lexer = Lexer::from(input);
+    let expected_tokens = vec![
+        (TokenType::TripleTilde, "~~~~"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::TripleBacktick, "```"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::TripleTilde, "~~~~"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "a ~~~ b"),
+        (TokenType::EOF, ""),
+    ];
+
+    for (token_type, literal) in expected_tokens {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_tokenize_braces_in_code() {
+    let input = "fn main() {}";
+    let mut lexer = Lexer::from(input);
+    let expected_tokens = vec![
+        (TokenType::Text, "fn main"),
+        (TokenType::LeftParen, "("),
+        (TokenType::RightParen, ")"),
+        (TokenType::Text, " "),
+        (TokenType::Text, "{"),
+        (TokenType::Text, "}"),
+        (TokenType::EOF, ""),
+    ];
+
+    for (token_type, literal) in expected_tokens {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_tokenize_indentation() {
+    let input = "    code\ntext\n  not code\n\tafter a tab";
+    let mut lexer = Lexer::from(input);
+    let expected_tokens = vec![
+        (TokenType::Indent, "    "),
+        (TokenType::Text, "code"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "text"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "  not code"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Indent, "    "),
+        (TokenType::Text, "after a tab"),
+        (TokenType::EOF, ""),
+    ];
+
+    for (token_type, literal) in expected_tokens {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
@@ -113,3 +113,38 @@ fn test_heading_1() {
         assert_eq!(token.literal, expected_token.literal);
     }
 }
+
+#[test]
+fn test_thematic_breaks_and_setext_underlines() {
+    let input = "Title\n===\ntext\n---\n\n---\n * * *\n___\n- item\n---\n--- x";
+    let mut lexer = Lexer::from(input);
+
+    let expected = vec![
+        (TokenType::Text, "Title"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::SetextUnderline, "==="),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "text"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::SetextUnderline, "---"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::ThematicBreak, "---"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::ThematicBreak, " * * *"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::ThematicBreak, "___"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::UnorderedListItem, "- "),
+        (TokenType::Text, "item"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::ThematicBreak, "---"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "--- x"),
+    ];
+    for (token_type, literal) in expected {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
@@ -41,3 +41,202 @@ fn test_bare_text() {
         assert_eq!(token.literal, expected_token.literal);
     }
 }
+
+#[test]
+fn test_tokenize_dashes_without_losing_chars() {
+    let input = "- item\n-x";
+    let mut lexer = Lexer::from(input);
+    let expected_tokens = vec![
+        (TokenType::UnorderedListItem, "- "),
+        (TokenType::Text, "item"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "-x"),
+        (TokenType::EOF, ""),
+    ];
+
+    for (token_type, literal) in expected_tokens {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_list_markers_only_start_lines() {
+    let input = "-- x - y\n- item\n  12. item";
+    let mut lexer = Lexer::from(input);
+    let expected_tokens = vec![
+        (TokenType::Text, "-- x - y"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::UnorderedListItem, "- "),
+        (TokenType::Text, "item"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::OrderedListItem, "  12. "),
+        (TokenType::Text, "item"),
+        (TokenType::EOF, ""),
+    ];
+
+    for (token_type, literal) in expected_tokens {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_tokenize_attribute_blocks() {
+    let input = "# Title {#custom-id}  \n{not} attributes";
+    let mut lexer = Lexer::from(input);
+    let expected_tokens = vec![
+        (TokenType::H1, "#"),
+        (TokenType::Text, " Title "),
+        (TokenType::Attributes, "{#custom-id}"),
+        (TokenType::Text, "  "),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "{"),
+        (TokenType::Text, "not"),
+        (TokenType::Text, "}"),
+        (TokenType::Text, " attributes"),
+        (TokenType::EOF, ""),
+    ];
+
+    for (token_type, literal) in expected_tokens {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_tokenize_footnotes() {
+    let input = "[^1]: note\ntext[^1] [^not a label] [link]";
+    let mut lexer = Lexer::from(input);
+    let expected_tokens = vec![
+        (TokenType::FootnoteDefinition, "[^1]:"),
+        (TokenType::Text, " note"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "text"),
+        (TokenType::FootnoteReference, "[^1]"),
+        (TokenType::Text, " "),
+        (TokenType::LeftBracket, "["),
+        (TokenType::Text, "^not a label"),
+        (TokenType::RightBracket, "]"),
+        (TokenType::Text, " "),
+        (TokenType::LeftBracket, "["),
+        (TokenType::Text, "link"),
+        (TokenType::RightBracket, "]"),
+        (TokenType::EOF, ""),
+    ];
+
+    for (token_type, literal) in expected_tokens {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_autolink_tokenizing() {
+    let input = "<https://a.com> (https://b.com) x@y.com<z>";
+    let mut lexer = Lexer::from(input);
+
+    let expected = vec![
+        (TokenType::Autolink, "<https://a.com>"),
+        (TokenType::Text, " "),
+        (TokenType::LeftParen, "("),
+        (TokenType::Url, "https://b.com"),
+        (TokenType::RightParen, ")"),
+        (TokenType::Text, " "),
+        (TokenType::Url, "x@y.com"),
+        (TokenType::Text, "<z>"),
+        (TokenType::EOF, ""),
+    ];
+    for (token_type, literal) in expected {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_escape_and_entity_tokenizing() {
+    let input = "\\*a\\b &copy; & &#x;";
+    let mut lexer = Lexer::from(input);
+
+    let expected = vec![
+        (TokenType::Escape, "\\*"),
+        (TokenType::Text, "a\\b "),
+        (TokenType::Entity, "&copy;"),
+        (TokenType::Text, " & &#x;"),
+        (TokenType::EOF, ""),
+    ];
+    for (token_type, literal) in expected {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_definition_marker_tokenizing() {
+    let input = "Term\n: a: b\n:c";
+    let mut lexer = Lexer::from(input);
+
+    let expected = vec![
+        (TokenType::Text, "Term"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::DefinitionMarker, ": "),
+        (TokenType::Text, "a: b"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, ":c"),
+        (TokenType::EOF, ""),
+    ];
+    for (token_type, literal) in expected {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_math_tokenizing() {
+    let input = "$$\na $x*y$ $5\n$$";
+    let mut lexer = Lexer::from(input);
+
+    let expected = vec![
+        (TokenType::MathFence, "$$"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "a "),
+        (TokenType::Math, "$x*y$"),
+        (TokenType::Text, " $5"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::MathFence, "$$"),
+        (TokenType::EOF, ""),
+    ];
+    for (token_type, literal) in expected {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
+
+#[test]
+fn test_container_fence_tokenizing() {
+    let input = ":::note\na ::: b\n:::";
+    let mut lexer = Lexer::from(input);
+
+    let expected = vec![
+        (TokenType::ContainerFence, ":::"),
+        (TokenType::Text, "note"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::Text, "a ::: b"),
+        (TokenType::NewLine, "\n"),
+        (TokenType::ContainerFence, ":::"),
+        (TokenType::EOF, ""),
+    ];
+    for (token_type, literal) in expected {
+        let token = lexer.next_token();
+        assert_eq!(token.token_type, token_type);
+        assert_eq!(token.literal, literal);
+    }
+}
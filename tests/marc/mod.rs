@@ -1,2 +1,4 @@
+mod highlight;
 mod lexer;
 mod parser;
+mod render;
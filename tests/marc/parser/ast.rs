@@ -0,0 +1,101 @@
+use md_to_html::marc::{
+    ast::{Block, Inline, Node, text::Text},
+    options::RenderOptions,
+};
+
+use super::parse;
+
+/// `link_targets` collects the destination of every link in `inlines`, including the links
+/// nested in emphasis.
+fn link_targets(inlines: &[Inline], targets: &mut Vec<String>) {
+    for inline in inlines {
+        match inline {
+            Inline::Link(link) => targets.push(link.href.clone()),
+            Inline::Bold(bold) => link_targets(&bold.children, targets),
+            Inline::Italics(italics) => link_targets(&italics.children, targets),
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_blocks_can_be_pattern_matched() {
+    let blocks =
+        parse("## Intro\n\nSee [the docs](/docs) and **[more](/more)**.\n\n---").into_blocks();
+
+    let [
+        Block::Heading(heading),
+        Block::Paragraph(paragraph),
+        Block::ThematicBreak(_),
+    ] = blocks.as_slice()
+    else {
+        panic!("unexpected blocks: {}", blocks.token_literal());
+    };
+    assert_eq!(heading.level, 2);
+    assert_eq!(heading.id.as_deref(), Some("intro"));
+
+    let mut targets = vec![];
+    link_targets(&paragraph.children, &mut targets);
+    assert_eq!(targets, ["/docs", "/more"]);
+}
+
+#[test]
+fn test_blocks_can_be_transformed() {
+    let mut blocks = parse("# Title\n\ntext").into_blocks();
+    for block in blocks.iter_mut() {
+        if let Block::Heading(heading) = block {
+            heading.level += 1;
+            heading.children.push(Text::new("!".to_string()).into());
+        }
+    }
+
+    assert_eq!(
+        blocks.evaluate(&RenderOptions::default()),
+        "<h2 id=\"title\"> Title!</h2><p>text</p>"
+    );
+}
+
+#[test]
+fn test_list_items_hold_blocks() {
+    let blocks = parse("- a\n- b\n\n  > quoted").into_blocks();
+
+    let [Block::UnorderedList(list)] = blocks.as_slice() else {
+        panic!("unexpected blocks: {}", blocks.token_literal());
+    };
+    assert_eq!(list.items.len(), 2);
+    assert!(matches!(
+        list.items[1].children.as_slice(),
+        [Block::Paragraph(_), Block::BlockQuote(_)]
+    ));
+}
+
+#[test]
+fn test_inline_nodes_start_paragraphs() {
+    // a line starting with an inline element is a paragraph, like a line starting with text
+    let blocks = parse("[a](/b) c\n`d` e").into_blocks();
+    let [Block::Paragraph(paragraph)] = blocks.as_slice() else {
+        panic!("unexpected blocks: {}", blocks.token_literal());
+    };
+    assert!(matches!(
+        paragraph.children.as_slice(),
+        [
+            Inline::Link(_),
+            Inline::Text(_),
+            Inline::SoftBreak(_),
+            Inline::Code(_),
+            Inline::Text(_)
+        ]
+    ));
+
+    // an unclosed `**` goes on over the blocks after it
+    let blocks = parse("**a\n# b").into_blocks();
+    let [Block::Paragraph(paragraph)] = blocks.as_slice() else {
+        panic!("unexpected blocks: {}", blocks.token_literal());
+    };
+    let [Inline::Bold(bold)] = paragraph.children.as_slice() else {
+        panic!("unexpected blocks: {}", blocks.token_literal());
+    };
+    assert!(
+        matches!(bold.children.last(), Some(Inline::Block(block)) if matches!(**block, Block::Heading(_)))
+    );
+}
@@ -0,0 +1,91 @@
+use md_to_html::marc::ast::attributes::Attributes;
+
+use super::render;
+
+#[test]
+fn test_parses_attribute_blocks() {
+    let attributes = Attributes::parse("{#intro .lead .wide data-x=1 title=\"a title\"}").unwrap();
+    assert_eq!(attributes.id(), Some("intro"));
+    assert_eq!(attributes.classes(), ["lead", "wide"]);
+    assert_eq!(attributes.get("data-x"), Some("1"));
+    assert_eq!(attributes.get("title"), Some("a title"));
+    assert_eq!(
+        attributes.html(&["base"]),
+        " id=\"intro\" class=\"base lead wide\" data-x=\"1\" title=\"a title\""
+    );
+
+    // `id=` and `class=` are the same as `#` and `.`
+    let attributes = Attributes::parse("{id=x class=\"a b\"}").unwrap();
+    assert_eq!(attributes.id(), Some("x"));
+    assert_eq!(attributes.classes(), ["a", "b"]);
+
+    for invalid in [
+        "{}",
+        "{#}",
+        "{.}",
+        "{key}",
+        "{=value}",
+        "{title=\"open}",
+        "{a b}",
+    ] {
+        assert!(Attributes::parse(invalid).is_none(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_attributes_after_inline_elements() {
+    assert_eq!(
+        render(
+            "A *lead*{.hl} with `code`{.lang}, **bold**{#b} and [a link](/x){.btn target=_blank}."
+        ),
+        "<p>A <em class=\"hl\">lead</em> with <code class=\"lang\">code</code>, \
+         <strong id=\"b\">bold</strong> and \
+         <a href = \"/x\" class=\"btn\" target=\"_blank\">a link</a>.</p>"
+    );
+
+    // an attribute block after plain text (or after a space) is just text
+    assert_eq!(
+        render("plain{.x} and *em* {.y}"),
+        "<p>plain{.x} and <em>em</em> {.y}</p>"
+    );
+}
+
+#[test]
+fn test_attributes_on_the_line_after_blocks() {
+    let input = "A paragraph\ngoing on.\n{.lead #first}\n\n\
+- one\n- two\n{.checklist}\n\n\
+```rust\nlet x = 1;\n```\n{.wide}\n\n\
+> quote\n{.pull}\n\n\
+---\n{.fancy}";
+    assert_eq!(
+        render(input),
+        "<p id=\"first\" class=\"lead\">A paragraph\ngoing on.</p>\
+         <ul class=\"checklist\"><li>one</li><li>two</li></ul>\
+         <pre class=\"wide\"><code class=\"language-rust\"><span class=\"hl-keyword\">let</span> \
+         x <span class=\"hl-operator\">=</span> <span class=\"hl-number\">1</span>;\n</code></pre>\
+         <blockquote class=\"pull\"><p>quote</p></blockquote><hr class=\"fancy\" />"
+    );
+}
+
+#[test]
+fn test_heading_attributes() {
+    assert_eq!(
+        render("## Title {#intro .big data-x=1}\n# Other {.small}"),
+        "<h2 id=\"intro\" class=\"big\" data-x=\"1\"> Title</h2>\
+         <h1 id=\"other\" class=\"small\"> Other</h1>"
+    );
+}
+
+#[test]
+fn test_attributes_keep_the_classes_of_the_element() {
+    assert_eq!(
+        render("- item\n\n  item paragraph\n  {.note}\n\n> [!TIP]\n> Hi\n{.wide}"),
+        "<ul><li><p>item</p><p class=\"note\">item paragraph</p></li></ul>\
+         <div class=\"markdown-alert markdown-alert-tip wide\">\
+         <p class=\"markdown-alert-title\">Tip</p><p>Hi</p></div>"
+    );
+    assert_eq!(
+        render("with $x^2${.big}"),
+        "<p>with <span class=\"math inline big\">\\(x^2\\)</span></p>"
+    );
+}
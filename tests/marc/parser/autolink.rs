@@ -0,0 +1,117 @@
+use md_to_html::marc::{
+    ast::{
+        Node, Program,
+        inline_container::InlineContainer,
+        link::Link,
+        text::{EscapedText, ParagraphText, Text},
+    },
+    autolink,
+    lexer::Lexer,
+    options::{ParseOptions, RenderOptions},
+    parser::Parser,
+};
+
+use super::{parse_with, render_blocks};
+
+fn autolink(text: &str, href: &str) -> Link {
+    let mut container = InlineContainer::new();
+    container.add_child(EscapedText::new(text.to_string()));
+    let mut link = Link::new(href.to_string());
+    link.set_inner(container);
+    link
+}
+
+fn render(input: &str, autolinks: bool) -> String {
+    let program = parse_with(input, ParseOptions { autolinks });
+    render_blocks(&program, &RenderOptions::default())
+}
+
+#[test]
+fn parse_autolinks() {
+    let input = "see <https://example.com/a?b=1> or <me@example.com>.";
+    let mut parser = Parser::new(Lexer::from(input));
+
+    let mut inline_container = InlineContainer::new();
+    inline_container.add_child(Text::new("see ".to_string()));
+    inline_container.add_child(autolink(
+        "https://example.com/a?b=1",
+        "https://example.com/a?b=1",
+    ));
+    inline_container.add_child(Text::new(" or ".to_string()));
+    inline_container.add_child(autolink("me@example.com", "mailto:me@example.com"));
+    inline_container.add_child(Text::new(".".to_string()));
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(inline_container);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(paragraph);
+
+    let program = parser.parse_program();
+    assert_eq!(program.token_literal(), expected_program.token_literal())
+}
+
+#[test]
+fn parse_invalid_autolinks() {
+    assert_eq!(
+        render("<not a link> and <example.com>", false),
+        "<p><not a link> and <example.com></p>"
+    );
+}
+
+#[test]
+fn parse_extended_autolinks() {
+    let input = "\
+https://example.com first, then www.example.com/path and me.you@example.co.uk
+
+no http://x or a.b@c";
+
+    assert_eq!(
+        render(input, true),
+        "<p><a href = \"https://example.com\">https://example.com</a> first, then \
+<a href = \"http://www.example.com/path\">www.example.com/path</a> and \
+<a href = \"mailto:me.you@example.co.uk\">me.you@example.co.uk</a></p>\
+<p>no http://x or a.b@c</p>"
+    );
+}
+
+#[test]
+fn extended_autolinks_are_opt_in() {
+    assert_eq!(
+        render("visit https://example.com", false),
+        "<p>visit https://example.com</p>"
+    );
+}
+
+#[test]
+fn extended_autolinks_in_links_are_text() {
+    assert_eq!(
+        render("[see https://example.com](/there)", true),
+        "<p><a href = \"/there\">see https://example.com</a></p>"
+    );
+}
+
+#[test]
+fn extended_autolink_trailing_punctuation() {
+    let cases = [
+        ("https://example.com.", "https://example.com"),
+        ("www.example.com/a?b!,", "www.example.com/a?b"),
+        (
+            "https://en.wikipedia.org/wiki/Rust_(language))",
+            "https://en.wikipedia.org/wiki/Rust_(language)",
+        ),
+        ("https://example.com/a&amp;", "https://example.com/a"),
+        ("https://example.com/a&b;c", "https://example.com/a&b;c"),
+        ("me@example.com.", "me@example.com"),
+    ];
+
+    for (text, url) in cases {
+        assert_eq!(
+            autolink::extended_autolink_len(text),
+            Some(url.len()),
+            "{text}"
+        );
+    }
+
+    assert_eq!(autolink::extended_autolink_len("www.example_a.com"), None);
+    assert_eq!(autolink::extended_autolink_len("me@example.com_"), None);
+}
@@ -1,9 +1,29 @@
 use md_to_html::marc::{
-    ast::{Node, Program, block_quote::BlockQuote, inline_container::InlineContainer, text::Text},
+    ast::{
+        Inline, Node, Program,
+        block_quote::{Alert, AlertKind, BlockQuote},
+        inline_container::InlineContainer,
+        text::{ParagraphText, SoftBreak, Text},
+    },
     lexer::Lexer,
+    options::RenderOptions,
     parser::Parser,
 };
 
+fn paragraph(children: Vec<Inline>) -> ParagraphText {
+    let mut container = InlineContainer::new();
+    for child in children {
+        container.add_child(child);
+    }
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(container);
+    paragraph
+}
+
+fn text(value: &str) -> Inline {
+    Text::new(value.to_string()).into()
+}
+
 #[test]
 fn test_parses_block_quote_valid() {
     let input = "> Hello World";
@@ -12,11 +32,8 @@ fn test_parses_block_quote_valid() {
 
     let mut expected_program = Program::new();
 
-    let mut block_quote = Box::new(BlockQuote::new());
-    let mut block_quote_inner = Box::new(InlineContainer::new());
-    block_quote_inner.add_child(Box::new(Text::new(" Hello World".to_string())));
-
-    block_quote.set_inner(block_quote_inner);
+    let mut block_quote = BlockQuote::new();
+    block_quote.add_child(paragraph(vec![text("Hello World")]));
 
     expected_program.add_block(block_quote);
 
@@ -36,18 +53,87 @@ fn test_parses_block_quote_with_middle_sign() {
 
     let mut expected_program = Program::new();
 
-    let mut block_quote = Box::new(BlockQuote::new());
-    let mut block_quote_inner = Box::new(InlineContainer::new());
-    block_quote_inner.add_child(Box::new(Text::new(" Hello > World".to_string())));
+    let mut block_quote = BlockQuote::new();
+    block_quote.add_child(paragraph(vec![text("Hello > World")]));
+
+    expected_program.add_block(block_quote);
 
-    block_quote.set_inner(block_quote_inner);
+    let parsed_program = p.parse_program();
 
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+}
+
+#[test]
+fn test_parses_block_quotes_with_several_blocks() {
+    let input = "> first\nlazy\n>\n> > nested\n\n> another";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut nested = BlockQuote::new();
+    nested.add_child(paragraph(vec![text("nested")]));
+
+    let mut block_quote = BlockQuote::new();
+    block_quote.add_child(paragraph(vec![
+        text("first"),
+        SoftBreak::new().into(),
+        text("lazy"),
+    ]));
+    block_quote.add_child(nested);
+
+    let mut another = BlockQuote::new();
+    another.add_child(paragraph(vec![text("another")]));
+
+    let mut expected_program = Program::new();
     expected_program.add_block(block_quote);
+    expected_program.add_block(another);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+}
+
+#[test]
+fn test_renders_block_quotes_with_lists_and_code() {
+    let input = "> # Title\n>\n>     code\n>\n> - one\n> - two";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
 
     let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<blockquote><h1 id=\"title\"> Title</h1><pre><code>code\n</code></pre>\
+<ul><li>one</li><li>two</li></ul></blockquote>"
+    );
+}
+
+#[test]
+fn test_parses_alerts() {
+    let input = "> [!NOTE]\n> Useful information.\n\n> [!Warning]\n> Careful.";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut note = Alert::new(AlertKind::Note);
+    note.add_child(paragraph(vec![text("Useful information.")]));
+    let mut warning = Alert::new(AlertKind::Warning);
+    warning.add_child(paragraph(vec![text("Careful.")]));
 
+    let mut expected_program = Program::new();
+    expected_program.add_block(note);
+    expected_program.add_block(warning);
+
+    let parsed_program = p.parse_program();
     assert_eq!(
         parsed_program.token_literal(),
         expected_program.token_literal()
     );
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<div class=\"markdown-alert markdown-alert-note\"><p class=\"markdown-alert-title\">Note</p>\
+<p>Useful information.</p></div>"
+    );
 }
@@ -1,6 +1,10 @@
 use md_to_html::marc::{
-    ast::{Node, Program, code::CodeBlock},
+    ast::{
+        Node, Program,
+        code::{CodeBlock, InfoString},
+    },
     lexer::Lexer,
+    options::RenderOptions,
     parser::Parser,
 };
 
@@ -13,10 +17,7 @@ print(\"Hello World\")
     let mut p = Parser::new(lexer);
 
     let mut expected_program = Program::new();
-    let code_block = Box::new(CodeBlock::new(
-        "print(\"Hello World\")\n".to_string(),
-        "python".to_string(),
-    ));
+    let code_block = CodeBlock::new("print(\"Hello World\")\n".to_string(), "python".to_string());
     expected_program.add_block(code_block);
 
     let parsed_program = p.parse_program();
@@ -31,3 +32,137 @@ print(\"Hello World\")
         expected_program.token_literal()
     )
 }
+
+#[test]
+fn test_parses_code_block_info_string() {
+    let input = "```rust title=\"main.rs\" {1,3-4}
+fn main() {
+    let x = 5;
+    let y = x + 1;
+    println!(\"{y}\");
+}
+```";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut expected_program = Program::new();
+    let mut code_block = CodeBlock::new(
+        "fn main() {\n    let x = 5;\n    let y = x + 1;\n    println!(\"{y}\");\n}\n".to_string(),
+        "rust".to_string(),
+    );
+    code_block.set_title("main.rs".to_string());
+    code_block.set_highlighted_lines(vec![1..=1, 3..=4]);
+    expected_program.add_block(code_block);
+
+    let parsed_program = p.parse_program();
+
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    )
+}
+
+#[test]
+fn test_parses_tilde_and_longer_fences() {
+    let input = "~~~markdown
+```python
+print(\"nested\")
+```
+~~~
+
+````
+```
+````";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(CodeBlock::new(
+        "```python\nprint(\"nested\")\n```\n".to_string(),
+        "markdown".to_string(),
+    ));
+    expected_program.add_block(CodeBlock::new("```\n".to_string(), String::new()));
+
+    let parsed_program = p.parse_program();
+
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    )
+}
+
+#[test]
+fn test_parses_info_string() {
+    let info = InfoString::parse("rust title='src/main.rs' {3-5}");
+    assert_eq!(info.language, "rust");
+    assert_eq!(info.title, Some("src/main.rs".to_string()));
+    assert_eq!(info.highlighted_lines, vec![3..=5]);
+
+    let info = InfoString::parse("go{2}");
+    assert_eq!(info.language, "go");
+    assert_eq!(info.title, None);
+    assert_eq!(info.highlighted_lines, vec![2..=2]);
+}
+
+#[test]
+fn test_evaluates_code_block() {
+    let input = "```html title=\"index.html\" {2}
+<p>
+  Hello & welcome
+</p>
+```";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+    let program = p.parse_program();
+
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<figure class=\"code-block\"><figcaption>index.html</figcaption>\
+<pre><code class=\"language-html\">\
+<span class=\"line\">&lt;p&gt;</span>\n\
+<span class=\"line highlighted\">  Hello &amp; welcome</span>\n\
+<span class=\"line\">&lt;/p&gt;</span>\n\
+</code></pre></figure>"
+    );
+
+    let lexer = Lexer::from("```\nplain\n```");
+    let mut p = Parser::new(lexer);
+    let program = p.parse_program();
+
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<pre><code>plain\n</code></pre>"
+    );
+}
+
+#[test]
+fn test_parses_indented_code_blocks() {
+    let input = "    fn main() {\n        println!(\"hi\");\n\n    }\n\ntext";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<pre><code>fn main() {\n    println!(&quot;hi&quot;);\n\n}\n</code></pre>"
+    );
+    assert_eq!(parsed_program.get_blocks().len(), 2);
+}
+
+#[test]
+fn test_indented_lines_continue_paragraphs() {
+    let input = "\
+a paragraph
+    can't be interrupted by indented code";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.evaluate(&RenderOptions::default()),
+        Program::new().evaluate(&RenderOptions::default()).replace(
+            "<body></body>",
+            "<body><p>a paragraph\ncan't be interrupted by indented code</p></body>"
+        )
+    );
+}
@@ -0,0 +1,138 @@
+use md_to_html::marc::{
+    ast::{
+        Node,
+        container::Container,
+        inline_container::InlineContainer,
+        text::{ParagraphText, Text},
+    },
+    container::{ContainerRenderer, Containers},
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+};
+
+use super::{render, render_with};
+
+#[test]
+fn test_parses_container() {
+    let input = ":::note Heads up\nSome text\n:::";
+    let mut p = Parser::new(Lexer::from(input));
+    let program = p.parse_program();
+
+    let mut container = InlineContainer::new();
+    container.add_child(Text::new("Some text".to_string()));
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(container);
+    let mut expected = Container::new("note".to_string(), "Heads up".to_string());
+    expected.add_child(paragraph);
+
+    let blocks = program.get_blocks();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].token_literal(), expected.token_literal());
+}
+
+#[test]
+fn test_renders_container_as_div() {
+    assert_eq!(
+        render(":::note\nSome *text*.\n\n- a\n:::\n\nafter"),
+        "<div class=\"note\"><p>Some <em>text</em>.</p><ul><li>a</li></ul></div><p>after</p>"
+    );
+}
+
+#[test]
+fn test_container_interrupts_paragraph() {
+    assert_eq!(
+        render("text\n:::tip\ninside\n:::"),
+        "<p>text</p><div class=\"tip\"><p>inside</p></div>"
+    );
+}
+
+#[test]
+fn test_unclosed_container_runs_to_end_of_document() {
+    assert_eq!(
+        render(":::note\nfirst\n\nsecond"),
+        "<div class=\"note\"><p>first</p><p>second</p></div>"
+    );
+}
+
+#[test]
+fn test_nested_containers() {
+    assert_eq!(
+        render(":::outer\na\n::::inner\nb\n::::\nc\n:::"),
+        "<div class=\"outer\"><p>a</p><div class=\"inner\"><p>b</p></div><p>c</p></div>"
+    );
+}
+
+#[test]
+fn test_container_attributes() {
+    assert_eq!(
+        render(":::warning {#careful .big data-level=2}\nx\n:::"),
+        "<div id=\"careful\" class=\"warning big\" data-level=\"2\"><p>x</p></div>"
+    );
+    assert_eq!(
+        render("::: {.plain}\nx\n:::"),
+        "<div class=\"plain\"><p>x</p></div>"
+    );
+}
+
+#[test]
+fn test_details_container() {
+    assert_eq!(
+        render(":::details Click <here>\nHidden\n:::"),
+        "<details><summary>Click &lt;here&gt;</summary><p>Hidden</p></details>"
+    );
+    assert_eq!(
+        render(":::details\nHidden\n:::"),
+        "<details><summary>Details</summary><p>Hidden</p></details>"
+    );
+}
+
+struct Columns;
+
+impl ContainerRenderer for Columns {
+    fn names(&self) -> &[&str] {
+        &["columns"]
+    }
+
+    fn render(&self, container: &Container, content: &str, _options: &RenderOptions) -> String {
+        format!(
+            "<div class=\"columns\" style=\"column-count: {}\">{}</div>",
+            container.argument(),
+            content
+        )
+    }
+}
+
+#[test]
+fn test_custom_container_renderer() {
+    let mut options = RenderOptions::default();
+    options.containers.register(Columns);
+    assert_eq!(
+        render_with(":::columns 2\nx\n:::", &options),
+        "<div class=\"columns\" style=\"column-count: 2\"><p>x</p></div>"
+    );
+}
+
+#[test]
+fn test_containers_without_renderers() {
+    let options = RenderOptions {
+        containers: Containers::new(),
+        ..RenderOptions::default()
+    };
+    assert_eq!(
+        render_with(":::details Summary\nx\n:::", &options),
+        "<div class=\"details\"><p>x</p></div>"
+    );
+}
+
+#[test]
+fn test_container_fences_in_code_blocks_are_code() {
+    assert_eq!(
+        render(":::note\n```\n:::\n```\n:::\n\nafter"),
+        "<div class=\"note\"><pre><code>:::\n</code></pre></div><p>after</p>"
+    );
+    assert_eq!(
+        render(":::note\n~~~ md\n:::tip\nx\n~~~\n:::"),
+        "<div class=\"note\"><pre><code class=\"language-md\">:::tip\nx\n</code></pre></div>"
+    );
+}
@@ -0,0 +1,100 @@
+use md_to_html::marc::{
+    ast::{
+        Node,
+        definition_list::{Definition, DefinitionItem, DefinitionList},
+        inline_container::InlineContainer,
+        text::Text,
+    },
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+};
+
+use super::render;
+
+fn inline(value: &str) -> InlineContainer {
+    let mut container = InlineContainer::new();
+    container.add_child(Text::new(value.to_string()));
+    container
+}
+
+#[test]
+fn test_parses_definition_list() {
+    let input = "Apple\n: A fruit.\n: A company.";
+    let mut p = Parser::new(Lexer::from(input));
+    let program = p.parse_program();
+
+    let mut item = DefinitionItem::new(inline("Apple"));
+    for value in ["A fruit.", "A company."] {
+        let mut definition = Definition::new();
+        definition.add_child(inline(value));
+        item.add_definition(definition);
+    }
+    let mut expected = DefinitionList::new();
+    expected.add_item(item);
+
+    assert_eq!(program.get_blocks().len(), 1);
+    assert_eq!(
+        program.get_blocks()[0].token_literal(),
+        expected.token_literal()
+    );
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<dl><dt>Apple</dt><dd>A fruit.</dd><dd>A company.</dd></dl>"
+    );
+}
+
+#[test]
+fn test_definition_list_with_several_terms() {
+    let input = "Apple\n: A fruit.\nRust *lang*\n: A language\nwith a lazy line\n- after";
+    assert_eq!(
+        render(input),
+        "<dl><dt>Apple</dt><dd>A fruit.</dd><dt>Rust <em>lang</em></dt>\
+         <dd>A language\nwith a lazy line</dd></dl><ul><li>after</li></ul>"
+    );
+}
+
+#[test]
+fn test_loose_definition_list_with_blocks() {
+    let input = "Term\n: First\n\n  - a\n  - b\n\n  > quote\n\nOther\n: Second\n\nafter";
+    assert_eq!(
+        render(input),
+        "<dl><dt>Term</dt><dd><p>First</p><ul><li>a</li><li>b</li></ul>\
+         <blockquote><p>quote</p></blockquote></dd>\
+         <dt>Other</dt><dd><p>Second</p></dd></dl><p>after</p>"
+    );
+}
+
+#[test]
+fn test_definition_without_term_is_text() {
+    assert_eq!(render(": no term"), "<p>: no term</p>");
+    assert_eq!(
+        render("- item\n: def"),
+        "<ul><li>item</li></ul><p>: def</p>"
+    );
+}
+
+#[test]
+fn test_definition_content_is_indented_like_its_first_line() {
+    // the content of a definition lines up with the text after its marker
+    let input = "Term\n:   First\n\n        let x = 1;\n\n    - a\n    - b";
+    assert_eq!(
+        render(input),
+        "<dl><dt>Term</dt><dd><p>First</p><pre><code>let x = 1;\n</code></pre>\
+         <ul><li>a</li><li>b</li></ul></dd></dl>"
+    );
+
+    let input = "Term\n: First\n\n      code";
+    assert_eq!(
+        render(input),
+        "<dl><dt>Term</dt><dd><p>First</p><pre><code>code\n</code></pre></dd></dl>"
+    );
+}
+
+#[test]
+fn test_blank_lines_between_term_and_definition() {
+    assert_eq!(
+        render("Term\n\n: def\n\nOther\n\n: more"),
+        "<dl><dt>Term</dt><dd><p>def</p></dd><dt>Other</dt><dd><p>more</p></dd></dl>"
+    );
+}
@@ -0,0 +1,75 @@
+use md_to_html::marc::{
+    ast::{
+        Node, Program,
+        inline_container::InlineContainer,
+        text::{EscapedText, ParagraphText, Text},
+    },
+    entity,
+    lexer::Lexer,
+    parser::Parser,
+};
+
+use super::render;
+
+#[test]
+fn test_parses_backslash_escapes() {
+    let input = "\\# 2 \\* 3";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut container = InlineContainer::new();
+    container.add_child(EscapedText::new("#".to_string()));
+    container.add_child(Text::new(" 2 ".to_string()));
+    container.add_child(EscapedText::new("*".to_string()));
+    container.add_child(Text::new(" 3".to_string()));
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(container);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(paragraph);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+    assert_eq!(
+        render("a \\*literal\\* \\[text\\] \\<b\\>"),
+        "<p>a *literal* [text] &lt;b&gt;</p>"
+    );
+}
+
+#[test]
+fn test_escapes_are_kept_in_code() {
+    assert_eq!(
+        render("`a\\*b` and `C:\\`"),
+        "<p><code>a\\*b</code> and <code>C:\\</code></p>"
+    );
+}
+
+#[test]
+fn test_decodes_entity_references() {
+    assert_eq!(
+        render("x &copy; &#169; &#xA9; &lt;&amp;&gt; &unknown; `&copy;`"),
+        "<p>x © © © &lt;&amp;&gt; &unknown; <code>&amp;copy;</code></p>"
+    );
+}
+
+#[test]
+fn test_unescapes_link_destinations_and_titles() {
+    assert_eq!(
+        render("x [a](/b\\)c?d=1&amp;e=2 \"say \\\"hi\\\"\")"),
+        "<p>x <a href = \"/b)c?d=1&amp;e=2\" title = \"say &quot;hi&quot;\">a</a></p>"
+    );
+}
+
+#[test]
+fn test_decode_entities() {
+    assert_eq!(entity::decode("&amp;"), Some("&".to_string()));
+    assert_eq!(entity::decode("&#35;"), Some("#".to_string()));
+    assert_eq!(entity::decode("&#X22;"), Some("\"".to_string()));
+    assert_eq!(entity::decode("&#0;"), Some("\u{fffd}".to_string()));
+    assert_eq!(entity::decode("&#12345678;"), None);
+    assert_eq!(entity::decode("&nope;"), None);
+    assert_eq!(entity::unescape("\\*a\\b&ne;"), "*a\\b≠");
+}
@@ -0,0 +1,137 @@
+use md_to_html::marc::{
+    ast::{
+        Inline, Node, Program,
+        footnote::{Footnote, FootnoteReference, FootnoteSection},
+        inline_container::InlineContainer,
+        text::{ParagraphText, Text},
+    },
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+    warning::Warning,
+};
+
+fn paragraph(children: Vec<Inline>) -> ParagraphText {
+    let mut container = InlineContainer::new();
+    for child in children {
+        container.add_child(child);
+    }
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(container);
+    paragraph
+}
+
+fn text(text: &str) -> Inline {
+    Text::new(text.to_string()).into()
+}
+
+#[test]
+fn test_numbers_footnotes_by_first_reference() {
+    let input = "\
+[^b]: Bee.
+
+A[^a] B[^b] A again[^a]
+
+[^a]: Ay.";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(paragraph(vec![
+        text("A"),
+        FootnoteReference::new(1, 1).into(),
+        text(" B"),
+        FootnoteReference::new(2, 1).into(),
+        text(" A again"),
+        FootnoteReference::new(1, 2).into(),
+    ]));
+
+    let mut section = FootnoteSection::new();
+    section.add_footnote(Footnote::new(
+        1,
+        2,
+        vec![paragraph(vec![text("Ay.")]).into()],
+    ));
+    section.add_footnote(Footnote::new(
+        2,
+        1,
+        vec![paragraph(vec![text("Bee.")]).into()],
+    ));
+    expected_program.add_block(section);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+    assert!(p.warnings().is_empty());
+}
+
+#[test]
+fn test_renders_footnotes_with_back_links() {
+    let input = "Text[^1] and[^1].\n\n[^1]: The note.";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    let html: Vec<String> = parsed_program
+        .get_blocks()
+        .iter()
+        .map(|block| block.evaluate(&RenderOptions::default()))
+        .collect();
+
+    assert_eq!(
+        html,
+        vec![
+            "<p>Text<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup> and\
+<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1-2\">1</a></sup>.</p>",
+            "<section class=\"footnotes\"><ol><li id=\"fn-1\"><p>The note. \
+<a href=\"#fnref-1\" class=\"footnote-backref\">↩</a> \
+<a href=\"#fnref-1-2\" class=\"footnote-backref\">↩<sup>2</sup></a></p></li></ol></section>",
+        ]
+    );
+}
+
+#[test]
+fn test_warns_about_undefined_footnotes() {
+    let input = "Some text[^missing].";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.evaluate(&RenderOptions::default()),
+        Program::new()
+            .evaluate(&RenderOptions::default())
+            .replace("<body></body>", "<body><p>Some text[^missing].</p></body>")
+    );
+    assert_eq!(
+        p.warnings(),
+        &[Warning::new(
+            1,
+            10,
+            "footnote `[^missing]` is referenced but never defined".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_footnotes_defined_in_nested_content() {
+    let section = "<section class=\"footnotes\"><ol><li id=\"fn-1\"><p>note \
+<a href=\"#fnref-1\" class=\"footnote-backref\">↩</a></p></li></ol></section>";
+    let reference = "<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup>";
+
+    for input in [
+        "a[^1]\n\n> [^1]: note",
+        "> [^1]: note\n\na[^1]",
+        "- [^1]: note\n\na[^1]",
+    ] {
+        let mut p = Parser::new(Lexer::from(input));
+        let program = p.parse_program();
+
+        let html = super::render_blocks(&program, &RenderOptions::default());
+        assert!(html.contains(&format!("<p>a{reference}</p>")), "{html}");
+        assert!(html.ends_with(section), "{html}");
+        assert!(p.warnings().is_empty());
+    }
+}
@@ -1,11 +1,12 @@
 use md_to_html::marc::{
     ast::{
-        Node, Program,
+        Block, Node, Program,
         heading::Heading,
         inline_container::InlineContainer,
         text::{BoldText, Text},
     },
     lexer::Lexer,
+    options::RenderOptions,
     parser::Parser,
 };
 
@@ -16,17 +17,19 @@ fn parse_heading_1() {
     let mut p = Parser::new(lexer);
 
     let mut expected_program = Program::new();
-    let mut heading_block = Box::new(Heading::new(1));
-    let mut heading_2_block = Box::new(Heading::new(2));
+    let mut heading_block = Heading::new(1);
+    let mut heading_2_block = Heading::new(2);
 
-    let mut inner_1 = Box::new(InlineContainer::new());
-    let mut inner_2 = Box::new(InlineContainer::new());
+    let mut inner_1 = InlineContainer::new();
+    let mut inner_2 = InlineContainer::new();
 
-    inner_1.add_child(Box::new(Text::new(" Hello World".to_string())));
-    inner_2.add_child(Box::new(Text::new(" Hello World 2".to_string())));
+    inner_1.add_child(Text::new(" Hello World".to_string()));
+    inner_2.add_child(Text::new(" Hello World 2".to_string()));
 
     heading_block.set_inner(inner_1);
+    heading_block.set_id("hello-world".to_string());
     heading_2_block.set_inner(inner_2);
+    heading_2_block.set_id("hello-world-2".to_string());
 
     expected_program.add_block(heading_block);
     expected_program.add_block(heading_2_block);
@@ -50,18 +53,19 @@ fn parses_heading_with_inline_elements() {
 
     let mut expected_program = Program::new();
 
-    let mut heading_block = Box::new(Heading::new(1));
-    let mut inner = Box::new(InlineContainer::new());
-    inner.add_child(Box::new(Text::new(" Hello ".to_string())));
+    let mut heading_block = Heading::new(1);
+    let mut inner = InlineContainer::new();
+    inner.add_child(Text::new(" Hello ".to_string()));
 
-    let mut bold_text = Box::new(BoldText::new());
-    let mut bold_inner = Box::new(InlineContainer::new());
-    bold_inner.add_child(Box::new(Text::new("World".to_string())));
+    let mut bold_text = BoldText::new();
+    let mut bold_inner = InlineContainer::new();
+    bold_inner.add_child(Text::new("World".to_string()));
     bold_text.set_inner(bold_inner);
 
     inner.add_child(bold_text);
 
     heading_block.set_inner(inner);
+    heading_block.set_id("hello-world".to_string());
 
     expected_program.add_block(heading_block);
     let parsed_program = p.parse_program();
@@ -74,3 +78,114 @@ fn parses_heading_with_inline_elements() {
         expected_program.token_literal()
     );
 }
+
+#[test]
+fn deduplicates_heading_ids() {
+    let input = "# Intro\n## Intro\n## Intro {#intro-1}\n## Intro";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    let ids: Vec<String> = parsed_program
+        .into_blocks()
+        .into_iter()
+        .map(|block| {
+            let Block::Heading(heading) = block else {
+                panic!("expected a heading, got {}", block.token_literal());
+            };
+            heading.id().unwrap().to_string()
+        })
+        .collect();
+
+    assert_eq!(ids, vec!["intro", "intro-1", "intro-1", "intro-2"]);
+}
+
+#[test]
+fn parses_custom_heading_ids() {
+    let input = "## Getting *started* {#start}  ";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<h2 id=\"start\"> Getting <em>started</em></h2>"
+    );
+}
+
+#[test]
+fn renders_heading_anchors() {
+    let input = "### API & Usage";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let options = RenderOptions {
+        heading_anchor: Some("¶".to_string()),
+        ..Default::default()
+    };
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&options),
+        "<h3 id=\"api--usage\"> API & Usage<a class=\"anchor\" href=\"#api--usage\" aria-hidden=\"true\">¶</a></h3>"
+    );
+}
+
+#[test]
+fn parse_setext_headings() {
+    let input = "Hello World\n===\n\nSub **title** {#sub}\n---";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut heading_1 = Heading::new(1);
+    let mut inner_1 = InlineContainer::new();
+    inner_1.add_child(Text::new("Hello World".to_string()));
+    heading_1.set_inner(inner_1);
+    heading_1.set_id("hello-world".to_string());
+
+    let mut bold = BoldText::new();
+    let mut bold_inner = InlineContainer::new();
+    bold_inner.add_child(Text::new("title".to_string()));
+    bold.set_inner(bold_inner);
+
+    let mut heading_2 = Heading::new(2);
+    let mut inner_2 = InlineContainer::new();
+    inner_2.add_child(Text::new("Sub ".to_string()));
+    inner_2.add_child(bold);
+    heading_2.set_inner(inner_2);
+    heading_2.set_id("sub".to_string());
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(heading_1);
+    expected_program.add_block(heading_2);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+}
+
+#[test]
+fn setext_underlines_need_a_paragraph() {
+    let input = "# Title\n---\n\n===\n- item\n---";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    let html: Vec<String> = parsed_program
+        .get_blocks()
+        .iter()
+        .map(|block| block.evaluate(&RenderOptions::default()))
+        .collect();
+    assert_eq!(
+        html,
+        vec![
+            "<h1 id=\"title\"> Title</h1>",
+            "<hr />",
+            "<p>===</p>",
+            "<ul><li>item</li></ul>",
+            "<hr />",
+        ]
+    );
+}
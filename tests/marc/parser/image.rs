@@ -1,22 +1,36 @@
 use md_to_html::marc::{
-    ast::{Node, Program, image::Image},
+    ast::{
+        Node, Program,
+        image::{Figure, Image},
+        inline_container::InlineContainer,
+        text::ParagraphText,
+    },
+    image_size,
     lexer::Lexer,
+    options::RenderOptions,
     parser::Parser,
 };
 
+use super::{render, render_with};
+
 #[test]
 fn parse_image() {
     let input = "![this is the alt](https://google.com)";
     let lexer = Lexer::from(input);
     let mut parser = Parser::new(lexer);
 
-    let image = Box::new(Image::new(
+    let image = Image::new(
         "https://google.com".to_string(),
         "this is the alt".to_string(),
-    ));
+    );
 
+    // a line starting with an image is a paragraph, like a line starting with text
+    let mut inner = InlineContainer::new();
+    inner.add_child(image);
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(inner);
     let mut expected_program = Program::new();
-    expected_program.add_block(image);
+    expected_program.add_block(paragraph);
 
     let program = parser.parse_program();
 
@@ -28,3 +42,158 @@ fn parse_image() {
 
     assert_eq!(program.token_literal(), expected_program.token_literal())
 }
+
+#[test]
+fn test_images_are_lazy_by_default() {
+    assert_eq!(
+        render("see ![a cat](cat.png \"Cat\")"),
+        "<p>see <img src = \"cat.png\" alt = \"a cat\" title = \"Cat\" loading = \"lazy\" \
+decoding = \"async\" /></p>"
+    );
+}
+
+#[test]
+fn test_image_attributes() {
+    let input = "![a](x.png){width=300 height=200 .rounded}\n\n\
+text ![b](y.png){#pic loading=eager data-zoom=\"2 x\"} more";
+    assert_eq!(
+        render(input),
+        "<p><img src = \"x.png\" alt = \"a\" class = \"rounded\" width = \"300\" height = \"200\" \
+loading = \"lazy\" decoding = \"async\" /></p>\
+<p>text <img src = \"y.png\" alt = \"b\" id = \"pic\" loading = \"eager\" data-zoom = \"2 x\" \
+decoding = \"async\" /> more</p>"
+    );
+}
+
+#[test]
+fn test_standalone_image_with_title_is_a_figure() {
+    let input = "![a cat](cat.png \"My cat\"){.wide}";
+    let mut parser = Parser::new(Lexer::from(input));
+    let program = parser.parse_program();
+
+    let mut image = Image::new("cat.png".to_string(), "a cat".to_string());
+    image.set_title("My cat".to_string());
+    assert_eq!(
+        program.get_blocks()[0].token_literal(),
+        Figure::new(image).token_literal()
+    );
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<figure><img src = \"cat.png\" alt = \"a cat\" class = \"wide\" loading = \"lazy\" \
+decoding = \"async\" /><figcaption>My cat</figcaption></figure>"
+    );
+
+    // without a title, or with text around it, an image stays an image (in a paragraph)
+    assert_eq!(
+        render("![a](a.png)"),
+        "<p><img src = \"a.png\" alt = \"a\" loading = \"lazy\" decoding = \"async\" /></p>"
+    );
+    assert_eq!(
+        render("a ![b](b.png \"B\")"),
+        "<p>a <img src = \"b.png\" alt = \"b\" title = \"B\" loading = \"lazy\" \
+decoding = \"async\" /></p>"
+    );
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    bytes.extend(width.to_be_bytes());
+    bytes.extend(height.to_be_bytes());
+    bytes.extend([8, 6, 0, 0, 0]);
+    bytes
+}
+
+fn gif(width: u16, height: u16) -> Vec<u8> {
+    let mut bytes = b"GIF89a".to_vec();
+    bytes.extend(width.to_le_bytes());
+    bytes.extend(height.to_le_bytes());
+    bytes
+}
+
+fn jpeg(width: u16, height: u16) -> Vec<u8> {
+    // a start of image marker, an (empty) APP0 segment and then a baseline frame
+    let mut bytes = vec![
+        0xff, 0xd8, 0xff, 0xe0, 0x00, 0x02, 0xff, 0xc0, 0x00, 0x11, 0x08,
+    ];
+    bytes.extend(height.to_be_bytes());
+    bytes.extend(width.to_be_bytes());
+    bytes.extend([0x03, 0x01, 0x22, 0x00]);
+    bytes
+}
+
+#[test]
+fn test_reads_image_sizes() {
+    assert_eq!(image_size::image_size(&png(640, 480)), Some((640, 480)));
+    assert_eq!(image_size::image_size(&gif(16, 32)), Some((16, 32)));
+    assert_eq!(
+        image_size::image_size(&jpeg(1920, 1080)),
+        Some((1920, 1080))
+    );
+    assert_eq!(image_size::image_size(b"not an image"), None);
+    assert_eq!(image_size::image_size(&png(1, 1)[..20]), None);
+}
+
+#[test]
+fn test_reads_the_size_of_jpeg_images_after_their_metadata() {
+    // an APP1 segment (eg. exif data) that is longer than the first chunk that is read
+    let mut bytes = vec![0xff, 0xd8, 0xff, 0xe1, 0x27, 0x10];
+    bytes.resize(bytes.len() + 0x2710 - 2, 0);
+    bytes.extend(&jpeg(640, 480)[2..]);
+    let path = std::env::temp_dir().join(format!("marc-exif-{}.jpg", std::process::id()));
+    std::fs::write(&path, bytes).unwrap();
+
+    let size = image_size::read_image_size(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(size, Some((640, 480)));
+}
+
+#[test]
+fn test_images_outside_of_the_image_root_are_not_read() {
+    let dir = std::env::temp_dir().join(format!("marc-outside-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("root")).unwrap();
+    std::fs::write(dir.join("secret.png"), png(800, 400)).unwrap();
+
+    let options = RenderOptions {
+        image_root: Some(dir.join("root")),
+        ..RenderOptions::default()
+    };
+    let rendered = render_with("![a](../secret.png) ![b](/../secret.png)", &options);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        rendered,
+        "<p><img src = \"../secret.png\" alt = \"a\" loading = \"lazy\" decoding = \"async\" /> \
+<img src = \"/../secret.png\" alt = \"b\" loading = \"lazy\" decoding = \"async\" /></p>"
+    );
+}
+
+#[test]
+fn test_local_images_get_their_size() {
+    let root = std::env::temp_dir().join(format!("marc-images-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("img")).unwrap();
+    std::fs::write(root.join("img/wide.png"), png(800, 400)).unwrap();
+    std::fs::write(root.join("photo.jpg"), jpeg(300, 200)).unwrap();
+
+    let options = RenderOptions {
+        image_root: Some(root.clone()),
+        ..RenderOptions::default()
+    };
+    let rendered = render_with(
+        "a ![w](/img/wide.png) ![p](photo.jpg){width=150} ![m](missing.gif) \
+![r](https://example.com/img/wide.png)",
+        &options,
+    );
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(
+        rendered,
+        "<p>a <img src = \"/img/wide.png\" alt = \"w\" width = \"800\" height = \"400\" \
+loading = \"lazy\" decoding = \"async\" /> \
+<img src = \"photo.jpg\" alt = \"p\" width = \"150\" height = \"100\" loading = \"lazy\" \
+decoding = \"async\" /> \
+<img src = \"missing.gif\" alt = \"m\" loading = \"lazy\" decoding = \"async\" /> \
+<img src = \"https://example.com/img/wide.png\" alt = \"r\" loading = \"lazy\" \
+decoding = \"async\" /></p>"
+    );
+}
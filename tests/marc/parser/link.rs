@@ -1,9 +1,26 @@
 use md_to_html::marc::{
-    ast::{Node, Program, link::Link},
+    ast::{
+        Node, Program,
+        code::InlineCode,
+        image::{Figure, Image},
+        inline_container::InlineContainer,
+        link::Link,
+        text::{BoldText, ParagraphText, SoftBreak, Text},
+    },
     lexer::Lexer,
+    options::RenderOptions,
     parser::Parser,
+    reference::{self, LinkReference},
 };
 
+fn link(text: &str, href: &str) -> Link {
+    let mut inner = InlineContainer::new();
+    inner.add_child(Text::new(text.to_string()));
+    let mut link = Link::new(href.to_string());
+    link.set_inner(inner);
+    link
+}
+
 #[test]
 fn parse_link() {
     let input = "\
@@ -12,19 +29,19 @@ fn parse_link() {
     let lexer = Lexer::from(input);
     let mut parser = Parser::new(lexer);
 
-    let link1 = Box::new(Link::new(
-        "this is the alt".to_string(),
-        "https://google.com".to_string(),
-    ));
+    let link1 = link("this is the alt", "https://google.com");
+    let link2 = link("hello world", "https://jeremiah.vercel.app");
 
-    let link2 = Box::new(Link::new(
-        "hello world".to_string(),
-        "https://jeremiah.vercel.app".to_string(),
-    ));
+    // the lines are one paragraph, like lines of text
+    let mut inner = InlineContainer::new();
+    inner.add_child(link1);
+    inner.add_child(SoftBreak::new());
+    inner.add_child(link2);
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(inner);
 
     let mut expected_program = Program::new();
-    expected_program.add_block(link1);
-    expected_program.add_block(link2);
+    expected_program.add_block(paragraph);
 
     let program = parser.parse_program();
 
@@ -36,3 +53,194 @@ fn parse_link() {
 
     assert_eq!(program.token_literal(), expected_program.token_literal())
 }
+
+#[test]
+fn parse_reference_links() {
+    let input = "\
+Read [the guide][Guide], [guide][] or [GUIDE] but not [missing].
+
+[guide]: https://example.com/guide \"The Guide\"";
+    let lexer = Lexer::from(input);
+    let mut parser = Parser::new(lexer);
+
+    let guide_link = |text: &str| {
+        let mut link = link(text, "https://example.com/guide");
+        link.set_title("The Guide".to_string());
+        link
+    };
+
+    let mut inner = InlineContainer::new();
+    inner.add_child(Text::new("Read ".to_string()));
+    inner.add_child(guide_link("the guide"));
+    inner.add_child(Text::new(", ".to_string()));
+    inner.add_child(guide_link("guide"));
+    inner.add_child(Text::new(" or ".to_string()));
+    inner.add_child(guide_link("GUIDE"));
+    inner.add_child(Text::new(" but not ".to_string()));
+    inner.add_child(Text::new("[".to_string()));
+    inner.add_child(Text::new("missing".to_string()));
+    inner.add_child(Text::new("]".to_string()));
+    inner.add_child(Text::new(".".to_string()));
+
+    let program = parser.parse_program();
+    assert_eq!(program.get_blocks().len(), 1);
+    assert_eq!(
+        program.get_blocks()[0].token_literal(),
+        format!("Paragraph({})", inner.token_literal())
+    );
+}
+
+#[test]
+fn parse_reference_images() {
+    let input = "![logo][]\n\n[logo]: <./images/logo v2.png> 'Our logo'";
+    let lexer = Lexer::from(input);
+    let mut parser = Parser::new(lexer);
+
+    let mut image = Image::new("./images/logo v2.png".to_string(), "logo".to_string());
+    image.set_title("Our logo".to_string());
+
+    // an image with a title on a line of its own is a figure
+    let figure = Figure::new(image);
+
+    let program = parser.parse_program();
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        figure.evaluate(&RenderOptions::default())
+    );
+}
+
+#[test]
+fn parse_link_reference_definitions() {
+    assert_eq!(
+        reference::parse_definition("[Some  Label]: /url (title)"),
+        Some((
+            "some label".to_string(),
+            LinkReference {
+                destination: "/url".to_string(),
+                title: Some("title".to_string()),
+            }
+        ))
+    );
+    assert_eq!(
+        reference::parse_definition("  [a]: <>"),
+        Some((
+            "a".to_string(),
+            LinkReference {
+                destination: "".to_string(),
+                title: None,
+            }
+        ))
+    );
+    assert_eq!(reference::parse_definition("[^1]: a footnote"), None);
+    assert_eq!(reference::parse_definition("[a]:"), None);
+    assert_eq!(
+        reference::parse_definition("[a]: /url \"title\" more"),
+        None
+    );
+    assert_eq!(reference::parse_definition("[a] /url"), None);
+}
+
+#[test]
+fn parse_inline_content_in_links() {
+    let input = "A [**bold** `code`](https://example.com \"Title\")";
+    let lexer = Lexer::from(input);
+    let mut parser = Parser::new(lexer);
+
+    let mut bold = BoldText::new();
+    let mut bold_inner = InlineContainer::new();
+    bold_inner.add_child(Text::new("bold".to_string()));
+    bold.set_inner(bold_inner);
+
+    let mut link_inner = InlineContainer::new();
+    link_inner.add_child(bold);
+    link_inner.add_child(Text::new(" ".to_string()));
+    link_inner.add_child(InlineCode::new("code".to_string()));
+
+    let mut link = Link::new("https://example.com".to_string());
+    link.set_inner(link_inner);
+    link.set_title("Title".to_string());
+
+    let mut inner = InlineContainer::new();
+    inner.add_child(Text::new("A ".to_string()));
+    inner.add_child(link);
+
+    let program = parser.parse_program();
+    assert_eq!(
+        program.get_blocks()[0].token_literal(),
+        format!("Paragraph({})", inner.token_literal())
+    );
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<p>A <a href = \"https://example.com\" title = \"Title\"><strong>bold</strong> <code>code</code></a></p>"
+    );
+}
+
+#[test]
+fn parse_link_destinations() {
+    let input = "\
+x [wiki](https://en.wikipedia.org/wiki/Rust_(programming_language)) \
+[spaces](<my file.html> 'Mine') [![logo *v2*](logo.png)](/) [not a link] [open";
+    let lexer = Lexer::from(input);
+    let mut parser = Parser::new(lexer);
+
+    let program = parser.parse_program();
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<p>x <a href = \"https://en.wikipedia.org/wiki/Rust_(programming_language)\">wiki</a> \
+<a href = \"my file.html\" title = \"Mine\">spaces</a> \
+<a href = \"/\"><img src = \"logo.png\" alt = \"logo v2\" loading = \"lazy\" decoding = \"async\" /></a> [not a link] [open</p>"
+    );
+}
+
+#[test]
+fn parse_inline_link_targets() {
+    assert_eq!(
+        reference::parse_inline_target("(/a(b)c \"t\") rest"),
+        Some((
+            LinkReference {
+                destination: "/a(b)c".to_string(),
+                title: Some("t".to_string()),
+            },
+            12
+        ))
+    );
+    assert_eq!(reference::parse_inline_target("(/a(b c)"), None);
+    assert_eq!(
+        reference::parse_inline_target("(/url \"title\" more)"),
+        None
+    );
+}
+
+#[test]
+fn parse_link_reference_definitions_in_code_and_block_quotes() {
+    let input = "    [a]: /indented\n\n~~~~\n~~~\n[b]: /fenced\n~~~~\n\n> [c]: /quoted\n\nsee [a], [b] and [c]";
+    let lexer = Lexer::from(input);
+    let mut parser = Parser::new(lexer);
+
+    let program = parser.parse_program();
+    let blocks = program.get_blocks();
+    let options = RenderOptions::default();
+    assert_eq!(
+        blocks[0].evaluate(&options),
+        "<pre><code>[a]: /indented\n</code></pre>"
+    );
+    assert_eq!(
+        blocks[1].evaluate(&options),
+        "<pre><code>~~~\n[b]: /fenced\n</code></pre>"
+    );
+    // the definition in the quote is recorded, but not written out
+    assert_eq!(blocks[2].evaluate(&options), "<blockquote></blockquote>");
+    assert_eq!(
+        blocks[3].evaluate(&options),
+        "<p>see [a], [b] and <a href = \"/quoted\">c</a></p>"
+    );
+}
+
+#[test]
+fn parse_links_to_definitions_in_later_block_quotes_and_list_items() {
+    assert_eq!(
+        super::render("[q] and [l]\n\n> [q]: /quoted\n\n- > [l]: /listed"),
+        "<p><a href = \"/quoted\">q</a> and <a href = \"/listed\">l</a></p>\
+<blockquote></blockquote><ul><li><blockquote></blockquote></li></ul>"
+    );
+}
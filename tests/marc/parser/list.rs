@@ -0,0 +1,258 @@
+use md_to_html::marc::{
+    ast::{
+        Block, Node, Program,
+        code::CodeBlock,
+        inline_container::InlineContainer,
+        list::{ListItem, OrderedList, TaskProgress, UnorderedList},
+        text::{ParagraphText, Text},
+    },
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+};
+
+fn text_item(text: &str) -> ListItem {
+    let mut container = InlineContainer::new();
+    container.add_child(Text::new(text.to_string()));
+    let mut item = ListItem::new();
+    item.add_child(container);
+    item
+}
+
+fn paragraph(text: &str) -> ParagraphText {
+    let mut container = InlineContainer::new();
+    container.add_child(Text::new(text.to_string()));
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(container);
+    paragraph
+}
+
+#[test]
+fn test_parses_nested_lists() {
+    let input = "\
+- one
+- two
+  - nested
+  - also nested
+- three";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut nested_list = UnorderedList::new();
+    nested_list.add_list_item(text_item("nested"));
+    nested_list.add_list_item(text_item("also nested"));
+
+    let mut two = text_item("two");
+    two.add_child(nested_list);
+
+    let mut list = UnorderedList::new();
+    list.add_list_item(text_item("one"));
+    list.add_list_item(two);
+    list.add_list_item(text_item("three"));
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(list);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    )
+}
+
+#[test]
+fn test_parses_loose_lists_with_several_paragraphs() {
+    let input = "\
+- first
+
+- second
+
+  more about the second";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut first = ListItem::new();
+    first.add_child(paragraph("first"));
+    let mut second = ListItem::new();
+    second.add_child(paragraph("second"));
+    second.add_child(paragraph("more about the second"));
+
+    let mut list = UnorderedList::new();
+    list.add_list_item(first);
+    list.add_list_item(second);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(list);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<ul><li><p>first</p></li><li><p>second</p><p>more about the second</p></li></ul>"
+    );
+}
+
+#[test]
+fn test_parses_indented_code_in_list_items() {
+    let input = "\
+1. run this:
+
+       cargo build
+2. done";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut first = ListItem::new();
+    first.add_child(paragraph("run this:"));
+    first.add_child(CodeBlock::new("cargo build\n".to_string(), String::new()));
+    let mut second = ListItem::new();
+    second.add_child(paragraph("done"));
+
+    let mut list = OrderedList::new();
+    list.add_list_item(first);
+    list.add_list_item(second);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(list);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    )
+}
+
+#[test]
+fn test_parses_task_list_items() {
+    let input = "\
+- [x] write the parser
+- [ ] write the renderer";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut done = text_item("write the parser");
+    done.set_checked(true);
+    let mut todo = text_item("write the renderer");
+    todo.set_checked(false);
+
+    let mut list = UnorderedList::new();
+    list.add_list_item(done);
+    list.add_list_item(todo);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(list);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<ul><li class=\"task-list-item\"><input type=\"checkbox\" disabled checked /> write the parser</li>\
+<li class=\"task-list-item\"><input type=\"checkbox\" disabled /> write the renderer</li></ul>"
+    );
+}
+
+#[test]
+fn test_counts_task_progress() {
+    let input = "\
+Progress: [[progress]]
+
+- [x] lexer
+- [ ] parser
+  1. [X] headings
+  2. [ ] lists
+- [y] not a task
+
+```
+- [ ] not a task
+```";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(parsed_program.task_progress(), TaskProgress::new(2, 4));
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<p>Progress: <span class=\"task-progress\">2/4</span></p>"
+    );
+}
+
+#[test]
+fn test_counts_task_progress_in_nested_content() {
+    let input = "\
+[[progress]]
+
+- [x] top
+
+> - [ ] quoted
+> - [x] quoted too
+
+    - [x] indented code
+
+Term
+: - [ ] defined
+
+~~~~
+~~~
+- [x] fenced code
+~~~~";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(parsed_program.task_progress(), TaskProgress::new(2, 4));
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<p><span class=\"task-progress\">2/4</span></p>"
+    );
+}
+
+#[test]
+fn test_ordered_lists_keep_their_start() {
+    let mut p = Parser::new(Lexer::from("3. third\n4. fourth\n\ntext\n\n1. first"));
+    let program = p.parse_program();
+
+    let Block::OrderedList(list) = &program.get_blocks()[0] else {
+        panic!("expected an ordered list");
+    };
+    assert_eq!(list.start, 3);
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<ol start=\"3\"><li>third</li><li>fourth</li></ol>"
+    );
+    assert_eq!(
+        program.get_blocks()[2].evaluate(&RenderOptions::default()),
+        "<ol><li>first</li></ol>"
+    );
+}
+
+#[test]
+fn test_sub_lists_indented_past_the_content_of_their_item() {
+    let mut p = Parser::new(Lexer::from("- [x] a\n    - [ ] nested\n- b"));
+    let program = p.parse_program();
+
+    assert_eq!(program.task_progress(), TaskProgress::new(1, 2));
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<ul><li class=\"task-list-item\"><input type=\"checkbox\" disabled checked /> a\
+         <ul><li class=\"task-list-item\"><input type=\"checkbox\" disabled /> nested</li></ul>\
+         </li><li>b</li></ul>"
+    );
+}
+
+#[test]
+fn test_ordered_list_items_with_several_digits() {
+    let mut p = Parser::new(Lexer::from("9. a\n10. b\n 11. c"));
+    let program = p.parse_program();
+
+    assert_eq!(program.get_blocks().len(), 1);
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<ol start=\"9\"><li>a</li><li>b</li><li>c</li></ol>"
+    );
+}
@@ -0,0 +1,119 @@
+use md_to_html::marc::{
+    ast::{
+        Node,
+        inline_container::InlineContainer,
+        math::{InlineMath, MathBlock},
+        text::{ParagraphText, Text},
+    },
+    lexer::Lexer,
+    math,
+    options::{MathOutput, RenderOptions},
+    parser::Parser,
+};
+
+use super::{render, render_with};
+
+#[test]
+fn test_parses_inline_math() {
+    let input = "Energy $E = mc^2$ and $a*b*c$";
+    let mut p = Parser::new(Lexer::from(input));
+    let program = p.parse_program();
+
+    let mut container = InlineContainer::new();
+    container.add_child(Text::new("Energy ".to_string()));
+    container.add_child(InlineMath::new("E = mc^2".to_string(), false));
+    container.add_child(Text::new(" and ".to_string()));
+    container.add_child(InlineMath::new("a*b*c".to_string(), false));
+    let mut expected = ParagraphText::new();
+    expected.set_inner(container);
+
+    assert_eq!(
+        program.get_blocks()[0].token_literal(),
+        expected.token_literal()
+    );
+    assert_eq!(
+        render(input),
+        "<p>Energy <span class=\"math inline\">\\(E = mc^2\\)</span> and \
+         <span class=\"math inline\">\\(a*b*c\\)</span></p>"
+    );
+}
+
+#[test]
+fn test_dollars_that_are_not_math() {
+    assert_eq!(
+        render("costs $5 and $10, \\$x\\$ or $ x $"),
+        "<p>costs $5 and $10, $x$ or $ x $</p>"
+    );
+}
+
+#[test]
+fn test_display_math() {
+    assert_eq!(
+        render("see $$\\sum_{i=1}^n i$$ here"),
+        "<p>see <span class=\"math display\">\\[\\sum_{i=1}^n i\\]</span> here</p>"
+    );
+
+    let input = "before\n$$\n\\frac{a}{b} < 1\n$$\nafter";
+    let mut p = Parser::new(Lexer::from(input));
+    let program = p.parse_program();
+    assert_eq!(
+        program.get_blocks()[1].token_literal(),
+        MathBlock::new("\\frac{a}{b} < 1\n".to_string()).token_literal()
+    );
+    assert_eq!(
+        render(input),
+        "<p>before</p><div class=\"math display\">\\[\\frac{a}{b} &lt; 1\\]</div><p>after</p>"
+    );
+}
+
+#[test]
+fn test_display_math_opened_in_a_paragraph() {
+    assert_eq!(
+        render("and $$\n\\frac{1}{2}\n$$"),
+        "<p>and <span class=\"math display\">\\[\n\\frac{1}{2}\n\\]</span></p>"
+    );
+
+    // a span can't go over a blank line
+    assert_eq!(render("and $$\nx\n\ny $$"), "<p>and $$\nx</p><p>y $$</p>");
+}
+
+#[test]
+fn test_renders_mathml() {
+    let options = RenderOptions {
+        math: MathOutput::MathML,
+        ..RenderOptions::default()
+    };
+    assert_eq!(
+        render_with("$x^2 + y_i$", &options),
+        "<p><math><mrow><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo>\
+         <msub><mi>y</mi><mi>i</mi></msub></mrow></math></p>"
+    );
+    assert_eq!(
+        render_with("$$\n\\sum_{i=0}^{n} \\frac{\\alpha}{2}\n$$", &options),
+        "<math display=\"block\"><mrow><munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo>\
+         <mn>0</mn></mrow><mi>n</mi></munderover><mfrac><mi>α</mi><mn>2</mn>\
+         </mfrac></mrow></math>"
+    );
+}
+
+#[test]
+fn test_mathml_subset() {
+    assert_eq!(
+        math::to_mathml("\\sum_i x_i^2", false),
+        "<math><mrow><msub><mo>∑</mo><mi>i</mi></msub>\
+         <msubsup><mi>x</mi><mi>i</mi><mn>2</mn></msubsup></mrow></math>"
+    );
+    assert_eq!(
+        math::to_mathml("\\sqrt{3.14} \\leq \\Omega", false),
+        "<math><mrow><msqrt><mn>3.14</mn></msqrt><mo>≤</mo>\
+         <mi mathvariant=\"normal\">Ω</mi></mrow></math>"
+    );
+    assert_eq!(
+        math::to_mathml("\\text{if } a<b", false),
+        "<math><mrow><mtext>if </mtext><mi>a</mi><mo>&lt;</mo><mi>b</mi></mrow></math>"
+    );
+    assert_eq!(
+        math::to_mathml("\\unknown", false),
+        "<math><merror><mtext>\\unknown</mtext></merror></math>"
+    );
+}
@@ -1,5 +1,57 @@
+use md_to_html::marc::{
+    ast::{Node, Program},
+    lexer::Lexer,
+    options::{ParseOptions, RenderOptions},
+    parser::Parser,
+};
+
+pub mod ast;
+pub mod attributes;
+pub mod autolink;
 pub mod block_quote;
 pub mod code_block;
+pub mod container;
+pub mod definition_list;
+pub mod escape;
+pub mod footnote;
 pub mod headings;
 pub mod image;
 pub mod link;
+pub mod list;
+pub mod math;
+pub mod paragraph;
+pub mod span;
+pub mod thematic_break;
+pub mod toc;
+pub mod typography;
+pub mod visit;
+
+/// `parse` parses `input` with the default options.
+pub fn parse(input: &str) -> Program {
+    parse_with(input, ParseOptions::default())
+}
+
+pub fn parse_with(input: &str, options: ParseOptions) -> Program {
+    let mut parser = Parser::new(Lexer::from(input));
+    parser.set_options(options);
+    parser.parse_program()
+}
+
+/// `render` parses `input` and returns the html of its blocks, written out with the default
+/// options.
+pub fn render(input: &str) -> String {
+    render_with(input, &RenderOptions::default())
+}
+
+pub fn render_with(input: &str, options: &RenderOptions) -> String {
+    render_blocks(&parse(input), options)
+}
+
+/// `render_blocks` returns the html of the blocks of `program` (without the page around them).
+pub fn render_blocks(program: &Program, options: &RenderOptions) -> String {
+    program
+        .get_blocks()
+        .iter()
+        .map(|block| block.evaluate(options))
+        .collect()
+}
@@ -0,0 +1,91 @@
+use md_to_html::marc::{
+    ast::{
+        Node, Program,
+        inline_container::InlineContainer,
+        text::{HardBreak, ParagraphText, SoftBreak, Text},
+    },
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+};
+
+use super::{render, render_with};
+
+fn text(value: &str) -> Text {
+    Text::new(value.to_string())
+}
+
+#[test]
+fn test_paragraphs_continue_over_lines() {
+    let input = "a paragraph\n  wrapped over  \nseveral\\\nlines  ";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut container = InlineContainer::new();
+    container.add_child(text("a paragraph"));
+    container.add_child(SoftBreak::new());
+    container.add_child(text("wrapped over"));
+    container.add_child(HardBreak::new());
+    container.add_child(text("several"));
+    container.add_child(HardBreak::new());
+    container.add_child(text("lines"));
+    let mut paragraph = ParagraphText::new();
+    paragraph.set_inner(container);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(paragraph);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<p>a paragraph\nwrapped over<br />several<br />lines</p>"
+    );
+}
+
+#[test]
+fn test_blocks_interrupt_paragraphs() {
+    let input = "one\n# heading\ntwo\n- item\nthree\n```\ncode\n```";
+
+    assert_eq!(
+        render(input),
+        "<p>one</p><h1 id=\"heading\"> heading</h1><p>two</p><ul><li>item\nthree</li></ul>\
+<pre><code>code\n</code></pre>"
+    );
+}
+
+#[test]
+fn test_soft_breaks_can_be_spaces() {
+    let options = RenderOptions {
+        soft_break: " ".to_string(),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        render_with("one\ntwo **and**\nthree", &options),
+        "<p>one two <strong>and</strong> three</p>"
+    );
+}
+
+#[test]
+fn test_paragraphs_can_start_with_inline_elements() {
+    assert_eq!(
+        render("**bold** first\nsecond line"),
+        "<p><strong>bold</strong> first\nsecond line</p>"
+    );
+    assert_eq!(
+        render("[a link](/x) first  \nsecond line"),
+        "<p><a href = \"/x\">a link</a> first<br />second line</p>"
+    );
+    assert_eq!(
+        render("`code` first\n*second* line\n\nnext"),
+        "<p><code>code</code> first\n<em>second</em> line</p><p>next</p>"
+    );
+    assert_eq!(
+        render("> [!NOTE] title\n> body"),
+        "<blockquote><p>[!NOTE] title\nbody</p></blockquote>"
+    );
+}
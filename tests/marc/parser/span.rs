@@ -0,0 +1,125 @@
+use md_to_html::marc::{
+    ast::{Block, Inline, Node},
+    options::RenderOptions,
+};
+use md_to_html::span::Span;
+
+use super::parse;
+
+/// `source` returns the part of `input` that `span` covers.
+fn source(input: &str, span: Span) -> &str {
+    &input[span.start..span.end]
+}
+
+#[test]
+fn test_block_and_inline_spans() {
+    let input = "# Hello *world*\n\nSome **bold** and a [link](/x)\nsecond line";
+    let program = parse(input);
+
+    let [Block::Heading(heading), Block::Paragraph(paragraph)] = program.get_blocks().as_slice()
+    else {
+        panic!("unexpected blocks: {}", program.token_literal());
+    };
+    assert_eq!(source(input, heading.span), "# Hello *world*");
+    assert_eq!(heading.span.to_string(), "1:1-1:15");
+    assert_eq!(source(input, heading.children[1].span()), "*world*");
+
+    assert_eq!(
+        source(input, paragraph.span),
+        "Some **bold** and a [link](/x)\nsecond line"
+    );
+    assert_eq!(paragraph.span.to_string(), "3:1-4:11");
+
+    let sources: Vec<&str> = paragraph
+        .children
+        .iter()
+        .map(|inline| source(input, inline.span()))
+        .collect();
+    assert_eq!(
+        sources,
+        vec![
+            "Some ",
+            "**bold**",
+            " and a ",
+            "[link](/x)",
+            "\n",
+            "second line"
+        ]
+    );
+
+    let Inline::Link(link) = &paragraph.children[3] else {
+        panic!("expected a link");
+    };
+    assert_eq!(link.span.to_string(), "3:21-3:30");
+    assert_eq!(source(input, link.children[0].span()), "link");
+}
+
+#[test]
+fn test_nested_content_spans_are_in_the_document() {
+    let input = "- one\n- two\n\n  > quoted `code`\n  > more\n";
+    let program = parse(input);
+
+    let [Block::UnorderedList(list)] = program.get_blocks().as_slice() else {
+        panic!("unexpected blocks: {}", program.token_literal());
+    };
+    assert_eq!(list.span.to_string(), "1:1-5:8");
+    assert_eq!(source(input, list.items[0].span), "- one");
+
+    let item = &list.items[1];
+    assert_eq!(
+        source(input, item.span),
+        "- two\n\n  > quoted `code`\n  > more"
+    );
+
+    let Block::BlockQuote(quote) = &item.children[1] else {
+        panic!("expected a block quote: {}", item.token_literal());
+    };
+    assert_eq!(quote.span.to_string(), "4:3-5:8");
+
+    let Block::Paragraph(paragraph) = &quote.children[0] else {
+        panic!("expected a paragraph");
+    };
+    assert_eq!(source(input, paragraph.span), "quoted `code`\n  > more");
+    assert_eq!(paragraph.children[1].span().to_string(), "4:12-4:17");
+}
+
+#[test]
+fn test_columns_count_characters() {
+    let input = "é and **ü**";
+    let program = parse(input);
+
+    let Block::Paragraph(paragraph) = &program.get_blocks()[0] else {
+        panic!("expected a paragraph");
+    };
+    let bold = paragraph.children[1].span();
+    assert_eq!(source(input, bold), "**ü**");
+    assert_eq!((bold.start, bold.end), (7, 13));
+    assert_eq!(bold.to_string(), "1:7-1:11");
+}
+
+#[test]
+fn test_data_sourcepos() {
+    let input = "# Title\n\n- a\n- b\n\n> quote";
+    let program = parse(input);
+    let options = RenderOptions {
+        source_positions: true,
+        ..RenderOptions::default()
+    };
+
+    let html: String = program
+        .get_blocks()
+        .iter()
+        .map(|block| block.evaluate(&options))
+        .collect();
+    assert_eq!(
+        html,
+        "<h1 id=\"title\" data-sourcepos=\"1:1-1:7\"> Title</h1>\
+         <ul data-sourcepos=\"3:1-4:3\"><li data-sourcepos=\"3:1-3:3\">a</li>\
+         <li data-sourcepos=\"4:1-4:3\">b</li></ul>\
+         <blockquote data-sourcepos=\"6:1-6:7\"><p data-sourcepos=\"6:3-6:7\">quote</p></blockquote>"
+    );
+
+    // the positions are only written out when they are asked for
+    let html = program.get_blocks()[0].evaluate(&RenderOptions::default());
+    assert_eq!(html, "<h1 id=\"title\"> Title</h1>");
+}
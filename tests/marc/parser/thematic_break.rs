@@ -0,0 +1,28 @@
+use md_to_html::marc::{
+    ast::{Node, Program, thematic_break::ThematicBreak},
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+};
+
+#[test]
+fn parse_thematic_breaks() {
+    let input = "---\n***\n  _ _ _";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let mut expected_program = Program::new();
+    expected_program.add_block(ThematicBreak::new());
+    expected_program.add_block(ThematicBreak::new());
+    expected_program.add_block(ThematicBreak::new());
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.token_literal(),
+        expected_program.token_literal()
+    );
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<hr />"
+    );
+}
@@ -0,0 +1,71 @@
+use md_to_html::marc::{
+    ast::{Node, toc::TocEntry},
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+};
+
+#[test]
+fn test_collects_headings() {
+    let input = "# Resume\n## Experience\n- item\n\n  ### Nested *heading*\n## Experience";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.headings(),
+        &[
+            TocEntry::new(1, "resume".to_string(), " Resume".to_string()),
+            TocEntry::new(2, "experience".to_string(), " Experience".to_string()),
+            TocEntry::new(
+                3,
+                "nested-heading".to_string(),
+                " Nested heading".to_string()
+            ),
+            TocEntry::new(2, "experience-1".to_string(), " Experience".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_renders_nested_toc() {
+    let input = "[[toc]]\n# Title\n## Install\n### From source\n## Usage\n# Other";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+
+    let parsed_program = p.parse_program();
+    assert_eq!(
+        parsed_program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<nav class=\"toc\"><ol>\
+<li><a href=\"#title\">Title</a><ol>\
+<li><a href=\"#install\">Install</a><ol>\
+<li><a href=\"#from-source\">From source</a></li>\
+</ol></li>\
+<li><a href=\"#usage\">Usage</a></li>\
+</ol></li>\
+<li><a href=\"#other\">Other</a></li>\
+</ol></nav>"
+    );
+}
+
+#[test]
+fn test_toc_levels() {
+    let input = "# Title\n## Install\n### From source\n## Usage\n\n[[toc]]";
+    let lexer = Lexer::from(input);
+    let mut p = Parser::new(lexer);
+    let options = RenderOptions {
+        toc_min_level: 2,
+        toc_max_level: 2,
+        ..Default::default()
+    };
+
+    let parsed_program = p.parse_program();
+    let toc = parsed_program.get_blocks().last().unwrap();
+    assert_eq!(
+        toc.evaluate(&options),
+        "<nav class=\"toc\"><ol>\
+<li><a href=\"#install\">Install</a></li>\
+<li><a href=\"#usage\">Usage</a></li>\
+</ol></nav>"
+    );
+}
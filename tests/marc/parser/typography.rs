@@ -0,0 +1,91 @@
+use md_to_html::marc::{
+    ast::Node,
+    lexer::Lexer,
+    options::{ParseOptions, RenderOptions},
+    parser::Parser,
+    typography::{self, Emoji, SmartPunctuation, TextTransform},
+};
+
+use super::render_with;
+
+fn render(input: &str) -> String {
+    let mut options = RenderOptions::default();
+    options.text_transforms.push(Box::new(Emoji));
+    options.text_transforms.push(Box::new(SmartPunctuation));
+    render_with(input, &options)
+}
+
+#[test]
+fn test_transforms_are_opt_in() {
+    let mut p = Parser::new(Lexer::from("\"ship\" it :rocket: -- now..."));
+    let program = p.parse_program();
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&RenderOptions::default()),
+        "<p>\"ship\" it :rocket: -- now...</p>"
+    );
+}
+
+#[test]
+fn test_replaces_emoji_shortcodes() {
+    assert_eq!(
+        render("ship it :rocket: :+1: but not :nope: or 10:30:00"),
+        "<p>ship it 🚀 👍 but not :nope: or 10:30:00</p>"
+    );
+    assert_eq!(typography::emoji("tada"), Some("🎉"));
+    assert_eq!(typography::emoji("unknown"), None);
+}
+
+#[test]
+fn test_smart_punctuation() {
+    let smart = SmartPunctuation;
+    assert_eq!(
+        smart.transform("\"Hello,\" she said -- it's 'fine'... --- or (\"not\")"),
+        "“Hello,” she said – it’s ‘fine’… — or (“not”)"
+    );
+    assert_eq!(
+        smart.transform("<a title=\"x--y\">'quoted'</a>"),
+        "<a title=\"x--y\">‘quoted’</a>"
+    );
+}
+
+#[test]
+fn test_transforms_leave_code_and_urls_alone() {
+    assert_eq!(
+        render("say *\"hi\"* `\"code\" -- :rocket:` \\\"escaped\\\" https://example.com/a--b"),
+        "<p>say <em>“hi”</em> <code>&quot;code&quot; -- :rocket:</code> &quot;escaped&quot; \
+         https://example.com/a--b</p>"
+    );
+    assert_eq!(
+        render("```\n\"code\" -- :rocket:\n```"),
+        "<pre><code>&quot;code&quot; -- :rocket:\n</code></pre>"
+    );
+
+    let mut p = Parser::new(Lexer::from("see https://example.com/a--b"));
+    p.set_options(ParseOptions { autolinks: true });
+    let program = p.parse_program();
+    let mut options = RenderOptions::default();
+    options.text_transforms.push(Box::new(SmartPunctuation));
+    assert_eq!(
+        program.get_blocks()[0].evaluate(&options),
+        "<p>see <a href = \"https://example.com/a--b\">https://example.com/a--b</a></p>"
+    );
+}
+
+#[test]
+fn test_dashes_at_the_start_of_a_line() {
+    assert_eq!(render("-- x\n--- y"), "<p>– x\n— y</p>");
+}
+
+#[test]
+fn test_smart_quotes_around_other_inlines() {
+    assert_eq!(
+        render("\"**b**\" and \"[a link](/x)\", *foo*'s '*it*' or \"`code`\""),
+        "<p>“<strong>b</strong>” and “<a href = \"/x\">a link</a>”, <em>foo</em>’s \
+         ‘<em>it</em>’ or “<code>code</code>”</p>"
+    );
+    assert_eq!(
+        render("> \"*Quoted*\"\n\n- \"**a**\""),
+        "<blockquote><p>“<em>Quoted</em>”</p></blockquote>\
+         <ul><li>“<strong>a</strong>”</li></ul>"
+    );
+}
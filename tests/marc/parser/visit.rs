@@ -0,0 +1,117 @@
+use md_to_html::marc::{
+    ast::{
+        Block, Inline, Node,
+        heading::Heading,
+        image::Image,
+        text::Text,
+        visit::{self, VisitMut, Visitor},
+    },
+    options::RenderOptions,
+};
+
+use super::{parse, render_blocks};
+
+#[derive(Default)]
+struct ImageCollector {
+    sources: Vec<String>,
+}
+
+impl Visitor for ImageCollector {
+    fn visit_image(&mut self, image: &Image) {
+        self.sources.push(image.src.clone());
+    }
+}
+
+#[test]
+fn test_visitor_reaches_every_image() {
+    let input = "- ![a](a.png)\n- [b](b.md)\n\n![fig](f.png)\n\nTerm\n: def *![c](c.png)*\n\ntext[^1]\n\n> ![e](e.png)\n\n[^1]: note ![d](d.png)";
+    let program = parse(input);
+
+    let mut collector = ImageCollector::default();
+    collector.visit_program(&program);
+    assert_eq!(
+        collector.sources,
+        vec!["a.png", "f.png", "c.png", "e.png", "d.png"]
+    );
+}
+
+#[derive(Default)]
+struct HeadingCounter {
+    headings: usize,
+    texts: usize,
+}
+
+impl Visitor for HeadingCounter {
+    fn visit_heading(&mut self, _heading: &Heading) {
+        // the text of the headings isn't walked, as walk_heading isn't called
+        self.headings += 1;
+    }
+
+    fn visit_text(&mut self, _text: &Text) {
+        self.texts += 1;
+    }
+}
+
+#[test]
+fn test_overridden_method_decides_whether_to_walk() {
+    let program = parse("# One\n\ntext\n\n## Two");
+
+    let mut counter = HeadingCounter::default();
+    counter.visit_program(&program);
+    assert_eq!((counter.headings, counter.texts), (2, 1));
+}
+
+struct DemoteHeadings;
+
+impl VisitMut for DemoteHeadings {
+    fn visit_heading_mut(&mut self, heading: &mut Heading) {
+        heading.level = (heading.level + 1).min(6);
+        visit::walk_heading_mut(self, heading);
+    }
+}
+
+#[test]
+fn test_visit_mut_demotes_headings() {
+    let mut program = parse("# Title\n\n> ## Quoted\n\n###### Last");
+    DemoteHeadings.visit_program_mut(&mut program);
+
+    assert_eq!(
+        render_blocks(&program, &RenderOptions::default()),
+        "<h2 id=\"title\"> Title</h2>\
+         <blockquote><h3 id=\"quoted\"> Quoted</h3></blockquote>\
+         <h6 id=\"last\"> Last</h6>"
+    );
+}
+
+struct UnwrapEmphasis;
+
+impl VisitMut for UnwrapEmphasis {
+    fn visit_inline_mut(&mut self, inline: &mut Inline) {
+        visit::walk_inline_mut(self, inline);
+        if let Inline::Italics(italics) = inline {
+            let mut text = Text::new(italics.plain_text());
+            text.span = italics.span;
+            *inline = Inline::Text(text);
+        }
+    }
+}
+
+#[test]
+fn test_visit_mut_replaces_nodes() {
+    let mut program = parse("some *emphasis* and **bold *nested***");
+    UnwrapEmphasis.visit_program_mut(&mut program);
+
+    let Block::Paragraph(paragraph) = &program.get_blocks()[0] else {
+        panic!("expected a paragraph: {}", program.token_literal());
+    };
+    assert!(
+        paragraph
+            .children
+            .iter()
+            .all(|child| !matches!(child, Inline::Italics(_)))
+    );
+    assert_eq!(
+        render_blocks(&program, &RenderOptions::default()),
+        "<p>some emphasis and <strong>bold nested</strong></p>"
+    );
+}
@@ -0,0 +1,116 @@
+use md_to_html::marc::{
+    ast::{Node, Program, heading::Heading, link::Link, text::Text},
+    lexer::Lexer,
+    options::RenderOptions,
+    parser::Parser,
+    render::{HtmlRenderer, Renderer, html},
+};
+
+fn parse(input: &str) -> Program {
+    let mut parser = Parser::new(Lexer::from(input));
+    parser.parse_program()
+}
+
+#[test]
+fn test_html_renderer_writes_out_what_evaluate_does() {
+    let input = "# Title\n\n- [x] done\n- *todo* [link](/x)\n\n> [!NOTE]\n> note\n\n```rust\nfn main() {}\n```";
+    let program = parse(input);
+    let options = RenderOptions::default();
+
+    let mut renderer = HtmlRenderer::new(&options);
+    assert_eq!(
+        renderer.render_program(&program),
+        program.evaluate(&options)
+    );
+    for block in program.get_blocks() {
+        assert_eq!(renderer.render_block(block), block.evaluate(&options));
+    }
+}
+
+/// `NumberedHeadings` numbers the headings of the document, and leaves the rest of it to the
+/// html renderer
+struct NumberedHeadings {
+    options: RenderOptions,
+    count: usize,
+}
+
+impl Renderer for NumberedHeadings {
+    fn options(&self) -> &RenderOptions {
+        &self.options
+    }
+
+    fn render_heading(&mut self, heading: &Heading) -> String {
+        self.count += 1;
+        let content = self.render_inlines(&heading.children);
+        format!(
+            "<h{}>{}. {}</h{}>",
+            heading.level,
+            self.count,
+            content.trim(),
+            heading.level
+        )
+    }
+}
+
+#[test]
+fn test_overriding_one_node_kind() {
+    let program = parse("# One\n\ntext\n\n- ## Two *in* a list\n\n> ### Three");
+    let mut renderer = NumberedHeadings {
+        options: RenderOptions::default(),
+        count: 0,
+    };
+
+    assert_eq!(
+        renderer.render_blocks(program.get_blocks()),
+        "<h1>1. One</h1><p>text</p>\
+         <ul><li><h2>2. Two <em>in</em> a list</h2></li></ul>\
+         <blockquote><h3>3. Three</h3></blockquote>"
+    );
+}
+
+/// `ExternalLinks` opens the links to other sites in a new tab, by adding to the default html
+/// of the links
+struct ExternalLinks<'a> {
+    options: &'a RenderOptions,
+}
+
+impl Renderer for ExternalLinks<'_> {
+    fn options(&self) -> &RenderOptions {
+        self.options
+    }
+
+    fn render_link(&mut self, link: &Link) -> String {
+        let html = html::link(self, link);
+        if !link.href.starts_with("https://") {
+            return html;
+        }
+        html.replacen("<a ", "<a target=\"_blank\" ", 1)
+    }
+
+    fn render_text(&mut self, text: &Text, _before: Option<char>, _after: Option<char>) -> String {
+        text.value.to_uppercase()
+    }
+}
+
+#[test]
+fn test_delegating_to_the_html_of_a_node() {
+    let program = parse("go [home](/) or [away](https://example.com)");
+    let options = RenderOptions::default();
+    let mut renderer = ExternalLinks { options: &options };
+
+    assert_eq!(
+        renderer.render_blocks(program.get_blocks()),
+        "<p>GO <a href = \"/\">HOME</a> OR \
+         <a target=\"_blank\" href = \"https://example.com\">AWAY</a></p>"
+    );
+}
+
+#[test]
+fn test_nodes_render_with_any_renderer() {
+    let program = parse("some *text*");
+    let options = RenderOptions::default();
+    let mut renderer = ExternalLinks { options: &options };
+
+    let block = &program.get_blocks()[0];
+    assert_eq!(block.render(&mut renderer), "<p>SOME <em>TEXT</em></p>");
+}
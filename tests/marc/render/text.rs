@@ -0,0 +1,103 @@
+use md_to_html::marc::{
+    options::RenderOptions,
+    render::{
+        Renderer,
+        text::{self, TextRenderer},
+    },
+    typography::SmartPunctuation,
+};
+
+use crate::marc::parser::parse;
+
+fn render(input: &str, width: Option<usize>) -> String {
+    let options = RenderOptions::default();
+    let mut renderer = TextRenderer::new(&options);
+    if let Some(width) = width {
+        renderer.set_width(width);
+    }
+    renderer.render_program(&parse(input))
+}
+
+#[test]
+fn test_markup_is_dropped() {
+    let input = "# Title {#t}\n\n[[toc]]\n\nIntro with `code`, an ![alt](a.png) image, a [link](/x) and a footnote[^1].\nSecond line\n\n---\n\n> [!NOTE]\n> quoted *text*\n\n[^1]: the **note**.";
+
+    assert_eq!(
+        render(input, None),
+        "Title\n\n\
+         Intro with code, an alt image, a link and a footnote[1].\nSecond line\n\n\
+         Note\nquoted text\n\n\
+         [1] the note."
+    );
+}
+
+#[test]
+fn test_lists_keep_their_markers() {
+    let input =
+        "1. first\n2. second\n   - nested\n   - [x] done\n3. third\n\nTerm\n: its definition";
+
+    assert_eq!(
+        render(input, None),
+        "1. first\n2. second\n   - nested\n   - [x] done\n3. third\n\nTerm\n  its definition"
+    );
+}
+
+#[test]
+fn test_ordered_lists_are_numbered_from_their_start() {
+    assert_eq!(
+        render("3. third\n4. fourth\n   1. nested", None),
+        "3. third\n4. fourth\n   1. nested"
+    );
+}
+
+#[test]
+fn test_code_blocks_and_tables() {
+    let input = "```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\n| name | value |\n|------|:-----:|\n| a    | **1** |\n| b    | 2     |";
+
+    assert_eq!(
+        render(input, Some(10)),
+        "fn main() {\n    println!(\"hi\");\n}\n\nname | value\na | 1\nb | 2"
+    );
+}
+
+#[test]
+fn test_wrapping() {
+    let input = "a paragraph with enough words to be wrapped\nover lines  \nwith a hard break\n\n- an item that is wrapped too";
+
+    assert_eq!(
+        render(input, Some(20)),
+        "a paragraph with\nenough words to be\nwrapped over lines\nwith a hard break\n\n\
+         - an item that is\n  wrapped too"
+    );
+    assert_eq!(
+        text::wrap("a verylongwordthatdoesntfit b", 8),
+        "a\nverylongwordthatdoesntfit\nb"
+    );
+}
+
+#[test]
+fn test_summary() {
+    let text = "Marc is a markdown dialect.\n\nIt has containers, footnotes and more.";
+
+    assert_eq!(
+        text::summary(text, 200),
+        "Marc is a markdown dialect. It has containers, footnotes and more."
+    );
+    assert_eq!(text::summary(text, 27), "Marc is a markdown dialect…");
+    assert_eq!(text::summary(text, 30), "Marc is a markdown dialect…");
+    assert_eq!(text::summary(text, 8), "Marc is…");
+    assert_eq!(text::summary("unbreakable words", 6), "unbre…");
+    assert!(text::summary(text, 40).chars().count() <= 40);
+}
+
+#[test]
+fn test_smart_quotes_around_other_inlines() {
+    let mut options = RenderOptions::default();
+    options.text_transforms.push(Box::new(SmartPunctuation));
+    let mut renderer = TextRenderer::new(&options);
+
+    assert_eq!(
+        renderer.render_program(&parse("\"*a*\" and\n'**b**'")),
+        "“a” and\n‘b’"
+    );
+}